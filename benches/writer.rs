@@ -0,0 +1,97 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Compares `construct_der`, with and without a length octets hint,
+//! and `construct_der_two_pass`.
+//!
+//! Run with `cargo bench --bench writer`.
+
+#[macro_use]
+extern crate bencher;
+
+use bencher::Bencher;
+use yasna::DERWriter;
+
+/// 32 levels of SEQUENCE around a 1 MiB OCTET STRING. Every level needs
+/// 4 length octets, so `construct_der` moves the whole payload each time.
+fn write_nested_large(writer: DERWriter, payload: &[u8], depth: usize) {
+    if depth == 0 {
+        writer.write_bytes(payload);
+    } else {
+        writer.write_sequence(|writer| {
+            write_nested_large(writer.next(), payload, depth - 1);
+        });
+    }
+}
+
+/// 100 levels of SEQUENCE with small elements at each level.
+fn write_nested_small(writer: DERWriter, depth: usize) {
+    writer.write_sequence(|writer| {
+        writer.next().write_i64(depth as i64);
+        if depth > 0 {
+            write_nested_small(writer.next(), depth - 1);
+        }
+    });
+}
+
+/// A flat SEQUENCE OF 10000 small SEQUENCEs. Each of them needs only
+/// 1 length octet, so a hint saves moving their contents.
+fn write_wide(writer: DERWriter) {
+    writer.write_sequence_of(|writer| {
+        for i in 0..10000 {
+            writer.next().write_sequence(|writer| {
+                writer.next().write_i64(i);
+                writer.next().write_bool(true);
+            });
+        }
+    });
+}
+
+fn nested_large(b: &mut Bencher) {
+    let payload = vec![0; 1 << 20];
+    b.iter(|| yasna::construct_der(|writer| {
+        write_nested_large(writer, &payload, 32)
+    }));
+}
+
+fn nested_large_two_pass(b: &mut Bencher) {
+    let payload = vec![0; 1 << 20];
+    b.iter(|| yasna::construct_der_two_pass(|writer| {
+        write_nested_large(writer, &payload, 32)
+    }));
+}
+
+fn nested_small(b: &mut Bencher) {
+    b.iter(|| yasna::construct_der(|writer| {
+        write_nested_small(writer, 100)
+    }));
+}
+
+fn nested_small_two_pass(b: &mut Bencher) {
+    b.iter(|| yasna::construct_der_two_pass(|writer| {
+        write_nested_small(writer, 100)
+    }));
+}
+
+fn wide(b: &mut Bencher) {
+    b.iter(|| yasna::construct_der(write_wide));
+}
+
+fn wide_two_pass(b: &mut Bencher) {
+    b.iter(|| yasna::construct_der_two_pass(write_wide));
+}
+
+fn wide_hint(b: &mut Bencher) {
+    b.iter(|| yasna::construct_der(|writer| {
+        write_wide(writer.length_octets_hint(1))
+    }));
+}
+
+benchmark_group!(benches,
+    nested_large, nested_large_two_pass,
+    nested_small, nested_small_two_pass,
+    wide, wide_two_pass, wide_hint);
+benchmark_main!(benches);
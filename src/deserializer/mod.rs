@@ -17,7 +17,9 @@ use num_bigint::{BigInt,BigUint};
 use bit_vec::BitVec;
 
 use super::{ASN1Result,BERMode,BERReader,parse_ber_general};
-use super::models::{ObjectIdentifier,TaggedDerValue};
+use super::models::{BitString,ObjectIdentifier,TaggedDerValue,ASN1Value};
+#[cfg(feature = "num-bigint")]
+use super::models::BigObjectIdentifier;
 #[cfg(feature = "time")]
 use super::models::{UTCTime,GeneralizedTime};
 
@@ -46,13 +48,16 @@ use super::models::{UTCTime,GeneralizedTime};
 /// - `()` as NULL decoder.
 /// - Tuples (except `()`) as SEQUENCE decoder.
 /// - `Vec<u8>` as OCTETSTRING decoder.
-/// - `BitVec` as BITSTRING decoder.
+/// - `BitVec`/`BitString` as BITSTRING decoder.
 /// - `String` as UTF8String decoder.
-/// - `i64`, `u64`, `i32`, `u32`, `i16`, `u16`, `BigInt`, `BigUint`
-///   as INTEGER decoder. (`u8` is avoided because of confliction.)
+/// - `i128`, `u128`, `i64`, `u64`, `i32`, `u32`, `i16`, `u16`, `BigInt`,
+///   `BigUint` as INTEGER decoder. (`u8` is avoided because of confliction.)
 /// - `bool` as BOOLEAN decoder.
+/// - `f64` as REAL decoder.
 /// - `ObjectIdentifier` as OBJECTT IDENTIFIER decoder.
+/// - `BigObjectIdentifier` as OBJECT IDENTIFIER decoder.
 /// - `UTCTime`/`GeneralizedTime` as UTCTime/GeneralizedTime decoder.
+/// - `ASN1Value` as a decoder for any value.
 pub trait BERDecodable: Sized {
     /// Reads an ASN.1 value from `BERReader` and converts it to `Self`.
     ///
@@ -164,6 +169,18 @@ impl BERDecodable for u64 {
     }
 }
 
+impl BERDecodable for i128 {
+    fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
+        reader.read_i128()
+    }
+}
+
+impl BERDecodable for u128 {
+    fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
+        reader.read_u128()
+    }
+}
+
 impl BERDecodable for i32 {
     fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
         reader.read_i32()
@@ -208,6 +225,12 @@ impl BERDecodable for bool {
     }
 }
 
+impl BERDecodable for f64 {
+    fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
+        reader.read_real()
+    }
+}
+
 #[cfg(feature = "bit-vec")]
 impl BERDecodable for BitVec {
     fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
@@ -233,6 +256,19 @@ impl BERDecodable for ObjectIdentifier {
     }
 }
 
+impl BERDecodable for BitString {
+    fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
+        reader.read_bitstring()
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl BERDecodable for BigObjectIdentifier {
+    fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
+        reader.read_big_oid()
+    }
+}
+
 #[cfg(feature = "time")]
 impl BERDecodable for UTCTime {
     fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
@@ -467,3 +503,9 @@ impl BERDecodable for TaggedDerValue  {
         reader.read_tagged_der()
     }
 }
+
+impl BERDecodable for ASN1Value {
+    fn decode_ber(reader: BERReader) -> ASN1Result<Self> {
+        reader.read_any()
+    }
+}
@@ -83,11 +83,29 @@ mod deserializer;
 mod serializer;
 
 pub use crate::writer::{construct_der,try_construct_der};
+pub use crate::writer::construct_der_with_capacity;
 pub use crate::writer::{construct_der_seq,try_construct_der_seq};
+pub use crate::writer::{construct_der_two_pass,encoded_len};
+pub use crate::writer::{construct_der_in,BufferTooSmall};
+pub use crate::writer::EmptySetElement;
 pub use crate::writer::{DERWriter,DERWriterSeq,DERWriterSet};
+pub use crate::writer::{construct_der_to_sink,DERSink,Tee};
+#[cfg(feature = "std")]
+pub use crate::writer::{construct_der_to_writer,IoSink};
+#[cfg(feature = "digest")]
+pub use crate::writer::{construct_der_to_digest,DigestSink};
 pub use crate::reader::{parse_ber_general,parse_ber,parse_der,BERMode};
+pub use crate::reader::{parse_ber_with_options,BERReaderOptions};
+pub use crate::reader::{parse_ber_partial,parse_der_partial};
+pub use crate::reader::{parse_ber_multi,parse_der_multi,split_ber,BERDocuments};
+pub use crate::reader::{TlvIter,Tlv,ElementHeader};
+pub use crate::reader::{der_query,ber_query,QueryStep};
+pub use crate::reader::{lint_der,LintFinding};
+pub use crate::reader::BERPushParser;
 pub use crate::reader::{BERReader,BERReaderSeq,BERReaderSet};
-pub use crate::reader::{ASN1Error,ASN1ErrorKind,ASN1Result};
+#[cfg(feature = "std")]
+pub use crate::reader::BERStreamReader;
+pub use crate::reader::{ASN1Error,ASN1ErrorKind,ASN1ErrorContext,ASN1Result};
 pub use crate::deserializer::{BERDecodable,decode_ber_general,decode_ber,decode_der};
 pub use crate::serializer::{DEREncodable,encode_der};
 
@@ -0,0 +1,354 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+#[cfg(feature = "bitvec")]
+use bitvec::order::{BitOrder, Msb0};
+#[cfg(feature = "bitvec")]
+use bitvec::slice::BitSlice;
+#[cfg(feature = "bitvec")]
+use bitvec::store::BitStore;
+
+/// A type that represents ASN.1 BIT STRING values.
+///
+/// The bits are packed into bytes with the first bit in the most
+/// significant bit of the first byte, as in the encoding. The unused bits
+/// in the last byte are always zero.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::BitString;
+/// let mut bits = BitString::new();
+/// bits.push(true);
+/// bits.push(false);
+/// bits.push(true);
+/// assert_eq!(bits.len(), 3);
+/// assert_eq!(bits.as_bytes(), &[160]);
+/// assert_eq!(bits.unused_bits(), 5);
+/// let der = yasna::encode_der(&bits);
+/// assert_eq!(der, vec![3, 2, 5, 160]);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct BitString {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitString {
+    /// Constructs an empty `BitString`.
+    pub fn new() -> Self {
+        return BitString {
+            bytes: Vec::new(),
+            len: 0,
+        };
+    }
+
+    /// Constructs a `BitString` consisting of all the bits in `bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::BitString;
+    /// let bits = BitString::from_bytes(vec![117, 13]);
+    /// assert_eq!(bits.len(), 16);
+    /// ```
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        let len = 8 * bytes.len();
+        return BitString {
+            bytes,
+            len,
+        };
+    }
+
+    /// Constructs a `BitString` consisting of the first `len` bits in
+    /// `bytes`. The remaining bits of the last byte are cleared.
+    ///
+    /// This is the inverse of [`BERReader::read_bitvec_bytes`](
+    /// crate::BERReader::read_bitvec_bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::BitString;
+    /// let bits = BitString::from_bytes_and_len(vec![117, 13, 65], 18);
+    /// assert_eq!(bits.as_bytes(), &[117, 13, 64]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It panics when `bytes` doesn't consist of exactly
+    /// `(len + 7) / 8` bytes.
+    pub fn from_bytes_and_len(mut bytes: Vec<u8>, len: usize) -> Self {
+        assert!(bytes.len() == (len + 7) / 8,
+            "{} bytes don't hold exactly {} bits", bytes.len(), len);
+        if len % 8 != 0 {
+            let last = bytes.len() - 1;
+            bytes[last] &= !(255 >> (len % 8));
+        }
+        return BitString {
+            bytes,
+            len,
+        };
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if there are no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of unused bits in the last byte, as written
+    /// in the encoding.
+    pub fn unused_bits(&self) -> u8 {
+        (8 * self.bytes.len() - self.len) as u8
+    }
+
+    /// Borrows the packed bits.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Extracts the packed bits.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Returns the bit at `index`, or `None` if it is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::BitString;
+    /// let bits = BitString::from_bytes(vec![128]);
+    /// assert_eq!(bits.get(0), Some(true));
+    /// assert_eq!(bits.get(1), Some(false));
+    /// assert_eq!(bits.get(8), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+        return Some(self.bytes[index / 8] & (128 >> (index % 8)) != 0);
+    }
+
+    /// Sets the bit at `index` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// It panics when `index` is out of range.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len,
+            "Index out of range: {} bits, index {}", self.len, index);
+        let mask = 128 >> (index % 8);
+        if value {
+            self.bytes[index / 8] |= mask;
+        } else {
+            self.bytes[index / 8] &= !mask;
+        }
+    }
+
+    /// Appends a bit.
+    pub fn push(&mut self, value: bool) {
+        if self.len % 8 == 0 {
+            self.bytes.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    /// Removes the last bit and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<bool> {
+        let value = self.get(self.len.checked_sub(1)?)?;
+        self.set(self.len - 1, false);
+        self.len -= 1;
+        if self.len % 8 == 0 {
+            self.bytes.pop();
+        }
+        return Some(value);
+    }
+
+    /// Appends all the bits of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::BitString;
+    /// let mut bits = BitString::from_bytes_and_len(vec![160], 3);
+    /// bits.append(&BitString::from_bytes(vec![255]));
+    /// assert_eq!(bits.len(), 11);
+    /// assert_eq!(bits.as_bytes(), &[191, 224]);
+    /// ```
+    pub fn append(&mut self, other: &BitString) {
+        let shift = self.len % 8;
+        if shift == 0 {
+            self.bytes.extend_from_slice(&other.bytes);
+        } else {
+            for &b in &other.bytes {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= b >> shift;
+                self.bytes.push(b << (8 - shift));
+            }
+        }
+        self.len += other.len;
+        self.bytes.truncate((self.len + 7) / 8);
+    }
+
+    /// Iterates over the bits.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |i| self.bytes[i / 8] & (128 >> (i % 8)) != 0)
+    }
+}
+
+impl Extend<bool> for BitString {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl FromIterator<bool> for BitString {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitString::new();
+        bits.extend(iter);
+        return bits;
+    }
+}
+
+#[cfg(feature = "bit-vec")]
+/// Converts from `bit_vec::BitVec`.
+///
+/// # Features
+///
+/// This impl is enabled by `bit-vec` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["bit-vec"] }
+/// ```
+impl<'a> From<&'a bit_vec::BitVec> for BitString {
+    fn from(bits: &'a bit_vec::BitVec) -> BitString {
+        return BitString::from_bytes_and_len(bits.to_bytes(), bits.len());
+    }
+}
+
+#[cfg(feature = "bit-vec")]
+/// Converts into `bit_vec::BitVec`.
+///
+/// # Features
+///
+/// This impl is enabled by `bit-vec` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["bit-vec"] }
+/// ```
+impl From<BitString> for bit_vec::BitVec {
+    fn from(bits: BitString) -> bit_vec::BitVec {
+        let mut ret = bit_vec::BitVec::from_bytes(&bits.bytes);
+        ret.truncate(bits.len);
+        return ret;
+    }
+}
+
+#[cfg(feature = "bitvec")]
+/// Converts from a `bitvec` slice of any storage and bit order.
+///
+/// # Features
+///
+/// This impl is enabled by `bitvec` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["bitvec"] }
+/// ```
+impl<'a, T: BitStore, O: BitOrder> From<&'a BitSlice<T, O>> for BitString {
+    fn from(bits: &'a BitSlice<T, O>) -> BitString {
+        return bits.iter().by_vals().collect();
+    }
+}
+
+#[cfg(feature = "bitvec")]
+/// Converts into a `bitvec` vector with the same packing.
+///
+/// # Features
+///
+/// This impl is enabled by `bitvec` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["bitvec"] }
+/// ```
+impl From<BitString> for bitvec::vec::BitVec<u8, Msb0> {
+    fn from(bits: BitString) -> bitvec::vec::BitVec<u8, Msb0> {
+        let mut ret = bitvec::vec::BitVec::from_vec(bits.bytes);
+        ret.truncate(bits.len);
+        return ret;
+    }
+}
+
+#[test]
+fn test_bitstring_push_pop() {
+    let pattern = [true, false, true, true, false, false, true, false, true];
+    let mut bits = BitString::new();
+    for (i, &b) in pattern.iter().enumerate() {
+        bits.push(b);
+        assert_eq!(bits.len(), i + 1);
+        assert_eq!(bits.as_bytes().len(), (i + 8) / 8);
+    }
+    assert_eq!(bits.as_bytes(), &[178, 128]);
+    assert_eq!(bits.iter().collect::<Vec<_>>(), pattern);
+    bits.set(8, false);
+    bits.set(0, false);
+    assert_eq!(bits.as_bytes(), &[50, 0]);
+    assert_eq!(bits.pop(), Some(false));
+    assert_eq!(bits.as_bytes(), &[50]);
+    assert_eq!(bits.pop(), Some(false));
+    assert_eq!(bits.as_bytes(), &[50]);
+    assert_eq!(bits.unused_bits(), 1);
+    assert_eq!(BitString::new().pop(), None);
+}
+
+#[test]
+fn test_bitstring_append() {
+    for split in 0..20 {
+        let all = (0..20).map(|i| i % 3 == 0).collect::<BitString>();
+        let mut bits = all.iter().take(split).collect::<BitString>();
+        bits.append(&all.iter().skip(split).collect());
+        assert_eq!(bits, all);
+    }
+}
+
+#[cfg(feature = "bit-vec")]
+#[test]
+fn test_bitstring_bit_vec() {
+    let bits = (0..21).map(|i| i % 3 == 0).collect::<BitString>();
+    let bitvec = bit_vec::BitVec::from(bits.clone());
+    assert_eq!(bitvec.iter().collect::<Vec<_>>(),
+        bits.iter().collect::<Vec<_>>());
+    assert_eq!(BitString::from(&bitvec), bits);
+}
+
+#[cfg(feature = "bitvec")]
+#[test]
+fn test_bitstring_bitvec() {
+    use bitvec::order::Lsb0;
+    let bits = (0..21).map(|i| i % 3 == 0).collect::<BitString>();
+    let bitvec = bitvec::vec::BitVec::<u8, Msb0>::from(bits.clone());
+    assert_eq!(bitvec.iter().by_vals().collect::<Vec<_>>(),
+        bits.iter().collect::<Vec<_>>());
+    assert_eq!(BitString::from(bitvec.as_bitslice()), bits);
+    assert_eq!(BitString::from(&bitvec[3..]),
+        bits.iter().skip(3).collect::<BitString>());
+    let lsb0 = bits.iter().collect::<bitvec::vec::BitVec<u16, Lsb0>>();
+    assert_eq!(BitString::from(lsb0.as_bitslice()), bits);
+}
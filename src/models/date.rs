@@ -0,0 +1,354 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use time::{Month, PrimitiveDateTime};
+
+/// A calendar date between 0000-01-01 and 9999-12-31.
+///
+/// Corresponds to ASN.1 DATE type. DER and CER use the basic form
+/// `YYYYMMDD` (X.690 8.26.2).
+///
+/// # Features
+///
+/// This struct is enabled by `time` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["time"] }
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # fn main() {
+/// use yasna::models::Date;
+/// let date = *Date::parse(b"20080401").unwrap().date();
+/// assert_eq!(date.year(), 2008);
+/// assert_eq!(date.month() as u8, 4);
+/// assert_eq!(date.day(), 1);
+/// # }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Date {
+    date: time::Date,
+}
+
+impl Date {
+    /// Parses ASN.1 string representation of DATE.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if the given string does not specify a correct
+    /// date.
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        if buf.len() != 8 {
+            return None;
+        }
+        return Some(Date {
+            date: parse_date(buf)?,
+        });
+    }
+
+    /// Constructs `Date` from a `time::Date`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the year is not between 0 and 9999.
+    pub fn from_date(date: time::Date) -> Self {
+        return Self::from_date_opt(date)
+            .expect("Can't express the year in DATE");
+    }
+
+    /// Constructs `Date` from a `time::Date`.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when the year is not between 0 and 9999.
+    pub fn from_date_opt(date: time::Date) -> Option<Self> {
+        if !(0..=9999).contains(&date.year()) {
+            return None;
+        }
+        return Some(Date {
+            date,
+        });
+    }
+
+    /// Returns the `time::Date` it represents.
+    pub fn date(&self) -> &time::Date {
+        &self.date
+    }
+
+    /// Returns ASN.1 canonical representation of the date as `Vec<u8>`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(8);
+        push_date(&mut buf, &self.date);
+        return buf;
+    }
+
+    /// Returns ASN.1 canonical representation of the date as `String`.
+    pub fn to_string(&self) -> String {
+        String::from_utf8(self.to_bytes()).unwrap()
+    }
+}
+
+/// A time of day between 00:00:00 and 23:59:59, without fractional
+/// seconds and leap seconds.
+///
+/// Corresponds to ASN.1 TIME-OF-DAY type. DER and CER use the basic
+/// form `HHMMSS` (X.690 8.26.2).
+///
+/// # Features
+///
+/// This struct is enabled by `time` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["time"] }
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # fn main() {
+/// use yasna::models::TimeOfDay;
+/// let time = *TimeOfDay::parse(b"123456").unwrap().time();
+/// assert_eq!(time.hour(), 12);
+/// assert_eq!(time.minute(), 34);
+/// assert_eq!(time.second(), 56);
+/// # }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TimeOfDay {
+    time: time::Time,
+}
+
+impl TimeOfDay {
+    /// Parses ASN.1 string representation of TIME-OF-DAY.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if the given string does not specify a correct
+    /// time.
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        if buf.len() != 6 {
+            return None;
+        }
+        return Some(TimeOfDay {
+            time: parse_time(buf)?,
+        });
+    }
+
+    /// Constructs `TimeOfDay` from a `time::Time`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when it has a non-zero nanosecond value.
+    pub fn from_time(time: time::Time) -> Self {
+        return Self::from_time_opt(time)
+            .expect("Can't express a nanosecond in TIME-OF-DAY");
+    }
+
+    /// Constructs `TimeOfDay` from a `time::Time`.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when it has a non-zero nanosecond value.
+    pub fn from_time_opt(time: time::Time) -> Option<Self> {
+        if time.nanosecond() != 0 {
+            return None;
+        }
+        return Some(TimeOfDay {
+            time,
+        });
+    }
+
+    /// Returns the `time::Time` it represents.
+    pub fn time(&self) -> &time::Time {
+        &self.time
+    }
+
+    /// Returns ASN.1 canonical representation of the time as `Vec<u8>`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(6);
+        push_time(&mut buf, &self.time);
+        return buf;
+    }
+
+    /// Returns ASN.1 canonical representation of the time as `String`.
+    pub fn to_string(&self) -> String {
+        String::from_utf8(self.to_bytes()).unwrap()
+    }
+}
+
+/// A local date and time between 0000-01-01T00:00:00 and
+/// 9999-12-31T23:59:59, without fractional seconds and leap seconds.
+///
+/// Corresponds to ASN.1 DATE-TIME type. DER and CER use the basic form
+/// `YYYYMMDDHHMMSS` (X.690 8.26.2).
+///
+/// # Features
+///
+/// This struct is enabled by `time` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["time"] }
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # fn main() {
+/// use yasna::models::DateTime;
+/// let datetime = *DateTime::parse(b"20080401123456").unwrap()
+///     .datetime();
+/// assert_eq!(datetime.year(), 2008);
+/// assert_eq!(datetime.hour(), 12);
+/// # }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DateTime {
+    datetime: PrimitiveDateTime,
+}
+
+impl DateTime {
+    /// Parses ASN.1 string representation of DATE-TIME.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if the given string does not specify a correct
+    /// datetime.
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        if buf.len() != 14 {
+            return None;
+        }
+        let date = parse_date(&buf[..8])?;
+        let time = parse_time(&buf[8..])?;
+        return Some(DateTime {
+            datetime: PrimitiveDateTime::new(date, time),
+        });
+    }
+
+    /// Constructs `DateTime` from a `PrimitiveDateTime`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when DATE-TIME can't represent the datetime. That is:
+    ///
+    /// - The year is not between 0 and 9999.
+    /// - It has a non-zero nanosecond value.
+    pub fn from_datetime(datetime: PrimitiveDateTime) -> Self {
+        return Self::from_datetime_opt(datetime)
+            .expect("Can't express the datetime in DATE-TIME");
+    }
+
+    /// Constructs `DateTime` from a `PrimitiveDateTime`.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when DATE-TIME can't represent the datetime.
+    pub fn from_datetime_opt(datetime: PrimitiveDateTime) -> Option<Self> {
+        if !(0..=9999).contains(&datetime.year()) ||
+            datetime.nanosecond() != 0 {
+            return None;
+        }
+        return Some(DateTime {
+            datetime,
+        });
+    }
+
+    /// Returns the `PrimitiveDateTime` it represents.
+    pub fn datetime(&self) -> &PrimitiveDateTime {
+        &self.datetime
+    }
+
+    /// Returns ASN.1 canonical representation of the datetime as `Vec<u8>`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(14);
+        push_date(&mut buf, &self.datetime.date());
+        push_time(&mut buf, &self.datetime.time());
+        return buf;
+    }
+
+    /// Returns ASN.1 canonical representation of the datetime as `String`.
+    pub fn to_string(&self) -> String {
+        String::from_utf8(self.to_bytes()).unwrap()
+    }
+}
+
+/// Parses a fixed-width decimal number.
+fn parse_digits(buf: &[u8]) -> Option<u32> {
+    if !buf.iter().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    return Some(buf.iter().fold(0, |x, &b| x * 10 + (b - b'0') as u32));
+}
+
+/// Parses `YYYYMMDD`.
+fn parse_date(buf: &[u8]) -> Option<time::Date> {
+    let year = parse_digits(&buf[0..4])? as i32;
+    let month = Month::try_from(parse_digits(&buf[4..6])? as u8).ok()?;
+    let day = parse_digits(&buf[6..8])? as u8;
+    return time::Date::from_calendar_date(year, month, day).ok();
+}
+
+/// Parses `HHMMSS`.
+fn parse_time(buf: &[u8]) -> Option<time::Time> {
+    let hour = parse_digits(&buf[0..2])? as u8;
+    let minute = parse_digits(&buf[2..4])? as u8;
+    let second = parse_digits(&buf[4..6])? as u8;
+    return time::Time::from_hms(hour, minute, second).ok();
+}
+
+fn push_date(buf: &mut Vec<u8>, date: &time::Date) {
+    buf.push((date.year() / 1000 % 10) as u8 + b'0');
+    buf.push((date.year() / 100 % 10) as u8 + b'0');
+    buf.push((date.year() / 10 % 10) as u8 + b'0');
+    buf.push((date.year() % 10) as u8 + b'0');
+    buf.push((date.month() as u8 / 10 % 10) + b'0');
+    buf.push((date.month() as u8 % 10) + b'0');
+    buf.push((date.day() / 10 % 10) + b'0');
+    buf.push((date.day() % 10) + b'0');
+}
+
+fn push_time(buf: &mut Vec<u8>, time: &time::Time) {
+    buf.push((time.hour() / 10 % 10) + b'0');
+    buf.push((time.hour() % 10) + b'0');
+    buf.push((time.minute() / 10 % 10) + b'0');
+    buf.push((time.minute() % 10) + b'0');
+    buf.push((time.second() / 10 % 10) + b'0');
+    buf.push((time.second() % 10) + b'0');
+}
+
+#[test]
+fn test_date_parse() {
+    let date = Date::parse(b"20080229").unwrap();
+    assert_eq!(&date.to_string(), "20080229");
+    let date = Date::parse(b"00000101").unwrap();
+    assert_eq!(&date.to_string(), "00000101");
+    let time = TimeOfDay::parse(b"235959").unwrap();
+    assert_eq!(&time.to_string(), "235959");
+    let datetime = DateTime::parse(b"99991231000000").unwrap();
+    assert_eq!(&datetime.to_string(), "99991231000000");
+}
+
+#[test]
+fn test_date_parse_err() {
+    assert!(Date::parse(b"20070229").is_none());
+    assert!(Date::parse(b"2008-02-29").is_none());
+    assert!(Date::parse(b"2008229").is_none());
+    assert!(Date::parse(b"+0080229").is_none());
+    assert!(TimeOfDay::parse(b"240000").is_none());
+    assert!(TimeOfDay::parse(b"120060").is_none());
+    assert!(TimeOfDay::parse(b"12:00:00").is_none());
+    assert!(TimeOfDay::parse(b"1200").is_none());
+    assert!(DateTime::parse(b"2008-02-29T12:00:00").is_none());
+    assert!(DateTime::parse(b"20080229T120000").is_none());
+    assert!(DateTime::parse(b"20080229120000Z").is_none());
+}
@@ -0,0 +1,147 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A duration given in years, months, days, hours, minutes and seconds.
+///
+/// Corresponds to ASN.1 DURATION type, whose string representation is
+/// the ISO 8601 form `PnYnMnDTnHnMnS`. The week form `PnW` and
+/// fractional components are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::Duration;
+/// let duration = Duration::parse(b"P1Y2MT30S").unwrap();
+/// assert_eq!(duration.years, 1);
+/// assert_eq!(duration.months, 2);
+/// assert_eq!(duration.seconds, 30);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Duration {
+    /// The number of years.
+    pub years: u32,
+    /// The number of months.
+    pub months: u32,
+    /// The number of days.
+    pub days: u32,
+    /// The number of hours.
+    pub hours: u32,
+    /// The number of minutes.
+    pub minutes: u32,
+    /// The number of seconds.
+    pub seconds: u32,
+}
+
+impl Duration {
+    /// Parses ASN.1 string representation of DURATION.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if the given string is not a duration of the
+    /// supported form.
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        if buf.len() < 3 || buf[0] != b'P' {
+            return None;
+        }
+        let mut duration = Duration::default();
+        let mut in_time = false;
+        // The designators not yet used, in the order they must appear.
+        let mut designators : &[u8] = b"YMD";
+        let mut pos = 1;
+        while pos < buf.len() {
+            if buf[pos] == b'T' {
+                if in_time || pos + 1 == buf.len() {
+                    return None;
+                }
+                in_time = true;
+                designators = b"HMS";
+                pos += 1;
+                continue;
+            }
+            let len = buf[pos..].iter().take_while(|b| b.is_ascii_digit())
+                .count();
+            if len == 0 || pos + len == buf.len() {
+                return None;
+            }
+            let mut value : u32 = 0;
+            for &b in &buf[pos..pos+len] {
+                value = value.checked_mul(10)?.checked_add((b - b'0') as u32)?;
+            }
+            let i = designators.iter().position(|&d| d == buf[pos+len])?;
+            let field = match (in_time, designators[i]) {
+                (false, b'Y') => &mut duration.years,
+                (false, b'M') => &mut duration.months,
+                (false, _) => &mut duration.days,
+                (true, b'H') => &mut duration.hours,
+                (true, b'M') => &mut duration.minutes,
+                (true, _) => &mut duration.seconds,
+            };
+            *field = value;
+            designators = &designators[i+1..];
+            pos += len + 1;
+        }
+        return Some(duration);
+    }
+
+    /// Returns ASN.1 canonical representation of the duration as `Vec<u8>`.
+    ///
+    /// Zero components are omitted; the zero duration is `P0D`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        use alloc::format;
+        let mut string = String::from("P");
+        for &(value, designator) in &[
+            (self.years, 'Y'), (self.months, 'M'), (self.days, 'D')] {
+            if value != 0 {
+                string += &format!("{}{}", value, designator);
+            }
+        }
+        if self.hours != 0 || self.minutes != 0 || self.seconds != 0 {
+            string.push('T');
+            for &(value, designator) in &[
+                (self.hours, 'H'), (self.minutes, 'M'), (self.seconds, 'S')] {
+                if value != 0 {
+                    string += &format!("{}{}", value, designator);
+                }
+            }
+        }
+        if string.len() == 1 {
+            string.push_str("0D");
+        }
+        return string.into_bytes();
+    }
+
+    /// Returns ASN.1 canonical representation of the duration as `String`.
+    pub fn to_string(&self) -> String {
+        String::from_utf8(self.to_bytes()).unwrap()
+    }
+}
+
+#[test]
+fn test_duration_parse() {
+    let tests : &[(&[u8], &str)] = &[
+        (b"P1Y2M3DT4H5M6S", "P1Y2M3DT4H5M6S"),
+        (b"P1D", "P1D"),
+        (b"PT36H", "PT36H"),
+        (b"P0Y0M0DT0H0M0S", "P0D"),
+        (b"PT0S", "P0D"),
+        (b"P10M", "P10M"),
+        (b"PT10M", "PT10M"),
+    ];
+    for &(input, expected) in tests {
+        let duration = Duration::parse(input).unwrap();
+        assert_eq!(&duration.to_string(), expected);
+    }
+    let tests : &[&[u8]] = &[
+        b"", b"P", b"PT", b"P1", b"P1DT", b"P1D1Y", b"P1Y1Y", b"PT1D",
+        b"P1H", b"P2W", b"PT1.5S", b"P-1D", b"P4294967296D", b"1D",
+    ];
+    for &input in tests {
+        assert!(Duration::parse(input).is_none());
+    }
+}
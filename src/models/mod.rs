@@ -11,11 +11,31 @@
 #![forbid(missing_docs)]
 
 mod oid;
+mod oid_names;
+mod bitstring;
 #[cfg(feature = "time")]
 mod time;
+#[cfg(feature = "time")]
+mod date;
+mod time_fields;
+mod duration;
 mod der;
+mod pdv;
+mod value;
 
-pub use self::oid::{ObjectIdentifier, ParseOidError};
+pub use self::oid::{ObjectIdentifier, RelativeOid};
+pub use self::oid::{ParseOidError, ParseOidErrorKind};
+pub use self::oid_names::OidRegistry;
+pub use self::bitstring::BitString;
+#[cfg(feature = "num-bigint")]
+pub use self::oid::BigObjectIdentifier;
 #[cfg(feature = "time")]
 pub use self::time::{UTCTime,GeneralizedTime};
+#[cfg(feature = "time")]
+pub use self::date::{Date,TimeOfDay,DateTime};
+pub use self::time_fields::{UTCTimeFields,GeneralizedTimeFields};
+pub use self::time_fields::TimeZoneDesignator;
+pub use self::duration::Duration;
 pub use self::der::TaggedDerValue;
+pub use self::pdv::{EmbeddedPdv, PdvIdentification};
+pub use self::value::ASN1Value;
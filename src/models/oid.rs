@@ -7,14 +7,19 @@
 // except according to those terms.
 
 use core::fmt::{self, Display};
+use core::iter::Copied;
+use core::slice;
 #[cfg(feature = "std")]
 use std::error::Error;
 use alloc::str::FromStr;
 use alloc::vec::Vec;
+#[cfg(feature = "num-bigint")]
+use num_bigint::BigUint;
 
 /// A type that represents object identifiers.
 ///
-/// This is actually a thin wrapper of `Vec<u64>`.
+/// This is actually a thin wrapper of `Vec<u64>`. The components are not
+/// checked on construction; see [`is_valid`](Self::is_valid).
 ///
 /// # Examples
 ///
@@ -62,6 +67,53 @@ impl ObjectIdentifier {
         };
     }
 
+    /// Constructs a new `ObjectIdentifier` from `Vec<u64>`, returning
+    /// `None` unless it is [valid](Self::is_valid).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::ObjectIdentifier;
+    /// assert!(ObjectIdentifier::try_new(vec![1, 2, 840]).is_some());
+    /// assert!(ObjectIdentifier::try_new(vec![1, 40]).is_none());
+    /// ```
+    pub fn try_new(components: Vec<u64>) -> Option<Self> {
+        let oid = ObjectIdentifier::new(components);
+        if !oid.is_valid() {
+            return None;
+        }
+        return Some(oid);
+    }
+
+    /// Checks if it can be encoded as an ASN.1 OBJECT IDENTIFIER.
+    ///
+    /// A valid OID has at least two components. The first one is 0, 1
+    /// or 2, and the second one is less than 40 unless the first one
+    /// is 2.
+    ///
+    /// [`DERWriter::write_oid`](crate::DERWriter::write_oid) panics on
+    /// invalid OIDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::ObjectIdentifier;
+    /// assert!(ObjectIdentifier::from_slice(&[2, 999, 3]).is_valid());
+    /// assert!(!ObjectIdentifier::from_slice(&[1]).is_valid());
+    /// assert!(!ObjectIdentifier::from_slice(&[3, 1]).is_valid());
+    /// assert!(!ObjectIdentifier::from_slice(&[0, 40]).is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        if self.components.len() < 2 {
+            return false;
+        }
+        let id0 = self.components[0];
+        let id1 = self.components[1];
+        // The first two components are encoded as id0 * 40 + id1.
+        return id0 < 3 && (id0 == 2 || id1 < 40) &&
+            id1 <= core::u64::MAX - 80;
+    }
+
     /// Borrows its internal vector of components.
     ///
     /// # Examples
@@ -103,6 +155,80 @@ impl ObjectIdentifier {
     pub fn into_components(self) -> Vec<u64> {
         self.components
     }
+
+    /// Iterates over the components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::ObjectIdentifier;
+    /// let pkcs1 = ObjectIdentifier::from_slice(&
+    ///     [1, 2, 840, 113549, 1, 1]);
+    /// assert_eq!(pkcs1.iter().max(), Some(113549));
+    /// ```
+    pub fn iter(&self) -> Copied<slice::Iter<'_, u64>> {
+        self.components.iter().copied()
+    }
+
+    /// Constructs the OID of a child node, with `arc` appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::ObjectIdentifier;
+    /// let pkcs1 = ObjectIdentifier::from_slice(&
+    ///     [1, 2, 840, 113549, 1, 1]);
+    /// let sha256_with_rsa = pkcs1.child(11);
+    /// assert_eq!(sha256_with_rsa.to_string(), "1.2.840.113549.1.1.11");
+    /// ```
+    pub fn child(&self, arc: u64) -> ObjectIdentifier {
+        let mut components = Vec::with_capacity(self.components.len() + 1);
+        components.extend_from_slice(&self.components);
+        components.push(arc);
+        return ObjectIdentifier::new(components);
+    }
+
+    /// Constructs the OID of the parent node, with the last component
+    /// removed.
+    ///
+    /// It returns `None` if there are less than two components. Note
+    /// that the parent of a two-component OID, such as `1` for `1.2`,
+    /// is not [valid](Self::is_valid) to encode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::ObjectIdentifier;
+    /// let sha256_with_rsa = ObjectIdentifier::from_slice(&
+    ///     [1, 2, 840, 113549, 1, 1, 11]);
+    /// let pkcs1 = sha256_with_rsa.parent().unwrap();
+    /// assert_eq!(pkcs1.to_string(), "1.2.840.113549.1.1");
+    /// assert_eq!(ObjectIdentifier::from_slice(&[1]).parent(), None);
+    /// ```
+    pub fn parent(&self) -> Option<ObjectIdentifier> {
+        if self.components.len() < 2 {
+            return None;
+        }
+        let components = &self.components[..self.components.len() - 1];
+        return Some(ObjectIdentifier::from_slice(components));
+    }
+
+    /// Checks if `prefix` is an ancestor of this OID or the OID itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::ObjectIdentifier;
+    /// let pkcs1 = ObjectIdentifier::from_slice(&
+    ///     [1, 2, 840, 113549, 1, 1]);
+    /// let sha256_with_rsa = pkcs1.child(11);
+    /// assert!(sha256_with_rsa.starts_with(&pkcs1));
+    /// assert!(pkcs1.starts_with(&pkcs1));
+    /// assert!(!pkcs1.starts_with(&sha256_with_rsa));
+    /// ```
+    pub fn starts_with(&self, prefix: &ObjectIdentifier) -> bool {
+        self.components.starts_with(&prefix.components)
+    }
 }
 
 impl Display for ObjectIdentifier {
@@ -120,28 +246,122 @@ impl Display for ObjectIdentifier {
     }
 }
 
-#[derive(Debug, Clone)]
-/// An error indicating failure to parse an Object identifier
-pub struct ParseOidError(());
+/// An error indicating failure to parse an object identifier from the
+/// dotted notation, such as `"1.2.840.113549.1.1.11"`.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::{ObjectIdentifier, ParseOidErrorKind};
+/// let err = "1.2.x.4".parse::<ObjectIdentifier>().unwrap_err();
+/// assert_eq!(err.kind(), ParseOidErrorKind::InvalidDigit);
+/// assert_eq!(err.component(), Some(2));
+/// assert_eq!(err.to_string(),
+///     "Failed to parse OID: invalid digit in component 2");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseOidError {
+    kind: ParseOidErrorKind,
+    component: Option<usize>,
+}
+
+/// The category of a [`ParseOidError`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParseOidErrorKind {
+    /// A component was empty, as in `"1..2"` or `"1.2."`.
+    Empty,
+    /// A component contained a character other than `0`-`9`.
+    InvalidDigit,
+    /// A component does not fit into `u64`. Use [`BigObjectIdentifier`]
+    /// for such OIDs.
+    ///
+    /// [`BigObjectIdentifier`]: crate::models::BigObjectIdentifier
+    Overflow,
+    /// The components don't form a valid OID; see
+    /// [`ObjectIdentifier::is_valid`].
+    InvalidOid,
+}
+
+impl ParseOidError {
+    fn new(kind: ParseOidErrorKind, component: Option<usize>) -> Self {
+        return ParseOidError {
+            kind,
+            component,
+        };
+    }
+
+    /// The category of the error.
+    pub fn kind(&self) -> ParseOidErrorKind {
+        self.kind
+    }
+
+    /// The index of the offending component, if the error is about
+    /// a single component.
+    pub fn component(&self) -> Option<usize> {
+        self.component
+    }
+}
 
 #[cfg(feature = "std")]
 impl Error for ParseOidError {}
 
 impl Display for ParseOidError {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        f.write_str("Failed to parse OID")
+        f.write_str("Failed to parse OID: ")?;
+        f.write_str(match self.kind {
+            ParseOidErrorKind::Empty => "empty component",
+            ParseOidErrorKind::InvalidDigit => "invalid digit",
+            ParseOidErrorKind::Overflow => "too large component",
+            ParseOidErrorKind::InvalidOid => "invalid first components",
+        })?;
+        if let Some(component) = self.component {
+            write!(f, " in component {}", component)?;
+        }
+        return Ok(());
     }
 }
 
+/// Parses the components of the dotted notation.
+fn parse_components<T: FromStr>(s: &str) -> Result<Vec<T>, ParseOidError> {
+    return s.split('.').enumerate().map(|(i, part)| {
+        if part.is_empty() {
+            return Err(ParseOidError::new(ParseOidErrorKind::Empty, Some(i)));
+        }
+        // Rejects signs, which `u64::from_str` accepts.
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseOidError::new(
+                ParseOidErrorKind::InvalidDigit, Some(i)));
+        }
+        return part.parse().map_err(|_| {
+            ParseOidError::new(ParseOidErrorKind::Overflow, Some(i))
+        });
+    }).collect();
+}
 
 impl FromStr for ObjectIdentifier {
     type Err = ParseOidError;
 
+    /// Parses the dotted notation, such as `"1.2.840.113549.1.1.11"`.
+    ///
+    /// Unlike [`ObjectIdentifier::new`], it rejects OIDs that are not
+    /// [valid](ObjectIdentifier::is_valid).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::{ObjectIdentifier, ParseOidErrorKind};
+    /// let oid: ObjectIdentifier = "1.2.840.113549.1.1.11".parse().unwrap();
+    /// assert_eq!(oid.components(), &[1, 2, 840, 113549, 1, 1, 11]);
+    /// assert_eq!(oid.to_string(), "1.2.840.113549.1.1.11");
+    ///
+    /// let err = "1.40".parse::<ObjectIdentifier>().unwrap_err();
+    /// assert_eq!(err.kind(), ParseOidErrorKind::InvalidOid);
+    /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.split(".")
-            .map(|s| s.parse().map_err(|_| ParseOidError(()) ))
-            .collect::<Result<_, _>>()
-            .map(ObjectIdentifier::new)
+        let components = parse_components(s)?;
+        return ObjectIdentifier::try_new(components).ok_or_else(|| {
+            ParseOidError::new(ParseOidErrorKind::InvalidOid, None)
+        });
     }
 }
 
@@ -157,6 +377,239 @@ impl From<Vec<u64>> for ObjectIdentifier {
     }
 }
 
+impl<'a> IntoIterator for &'a ObjectIdentifier {
+    type Item = u64;
+    type IntoIter = Copied<slice::Iter<'a, u64>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+/// A type that represents object identifiers with arbitrarily large
+/// components.
+///
+/// Some OIDs, such as UUID-based ones under `2.25`, have components
+/// exceeding `u64`, which [`ObjectIdentifier`] cannot hold.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::BigObjectIdentifier;
+/// let oid: BigObjectIdentifier =
+///     "2.25.329800735698586629295641978511506172918".parse().unwrap();
+/// assert_eq!(oid.components().len(), 3);
+/// assert!(oid.to_oid().is_none());
+/// ```
+///
+/// # Features
+///
+/// This struct is enabled by `num-bigint` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["num-bigint"] }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BigObjectIdentifier {
+    components: Vec<BigUint>,
+}
+
+#[cfg(feature = "num-bigint")]
+impl BigObjectIdentifier {
+    /// Constructs a new `BigObjectIdentifier` from `Vec<BigUint>`.
+    pub fn new(components: Vec<BigUint>) -> Self {
+        return BigObjectIdentifier {
+            components,
+        };
+    }
+
+    /// Constructs a new `BigObjectIdentifier` from `Vec<BigUint>`,
+    /// returning `None` unless it is [valid](Self::is_valid).
+    pub fn try_new(components: Vec<BigUint>) -> Option<Self> {
+        let oid = BigObjectIdentifier::new(components);
+        if !oid.is_valid() {
+            return None;
+        }
+        return Some(oid);
+    }
+
+    /// Checks if it can be encoded as an ASN.1 OBJECT IDENTIFIER.
+    ///
+    /// The conditions are the same as in
+    /// [`ObjectIdentifier::is_valid`], except that the second component
+    /// may be arbitrarily large under 2.
+    pub fn is_valid(&self) -> bool {
+        if self.components.len() < 2 {
+            return false;
+        }
+        let id0 = &self.components[0];
+        let id1 = &self.components[1];
+        return *id0 < BigUint::from(3u8) &&
+            (*id0 == BigUint::from(2u8) || *id1 < BigUint::from(40u8));
+    }
+
+    /// Borrows its internal vector of components.
+    pub fn components(&self) -> &Vec<BigUint> {
+        &self.components
+    }
+
+    /// Mutably borrows its internal vector of components.
+    pub fn components_mut(&mut self) -> &mut Vec<BigUint> {
+        &mut self.components
+    }
+
+    /// Extracts its internal vector of components.
+    pub fn into_components(self) -> Vec<BigUint> {
+        self.components
+    }
+
+    /// Converts it to [`ObjectIdentifier`], returning `None` if some
+    /// component doesn't fit into `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::{BigObjectIdentifier, ObjectIdentifier};
+    /// let oid: BigObjectIdentifier = "1.2.840".parse().unwrap();
+    /// assert_eq!(oid.to_oid(),
+    ///     Some(ObjectIdentifier::from_slice(&[1, 2, 840])));
+    /// ```
+    pub fn to_oid(&self) -> Option<ObjectIdentifier> {
+        let mut components = Vec::with_capacity(self.components.len());
+        for component in &self.components {
+            let digits = component.to_u64_digits();
+            if digits.len() > 1 {
+                return None;
+            }
+            components.push(digits.first().map_or(0, |&d| d));
+        }
+        return Some(ObjectIdentifier::new(components));
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl Display for BigObjectIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut fst = true;
+        for component in &self.components {
+            if fst {
+                write!(f, "{}", component)?;
+            } else {
+                write!(f, ".{}", component)?;
+            }
+            fst = false;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl FromStr for BigObjectIdentifier {
+    type Err = ParseOidError;
+
+    /// Parses the dotted notation. See [`ObjectIdentifier::from_str`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let components = parse_components(s)?;
+        return BigObjectIdentifier::try_new(components).ok_or_else(|| {
+            ParseOidError::new(ParseOidErrorKind::InvalidOid, None)
+        });
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<ObjectIdentifier> for BigObjectIdentifier {
+    fn from(oid: ObjectIdentifier) -> BigObjectIdentifier {
+        return BigObjectIdentifier::new(
+            oid.components.into_iter().map(BigUint::from).collect());
+    }
+}
+
+/// A type that represents relative object identifiers (RELATIVE-OID).
+///
+/// Unlike [`ObjectIdentifier`], the components are relative to some
+/// other, implied object identifier, so there are no constraints on the
+/// first two arcs.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::RelativeOid;
+/// let roid = RelativeOid::from_slice(&[8571, 3, 2]);
+/// println!("{}", roid);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RelativeOid {
+    components: Vec<u64>,
+}
+
+impl RelativeOid {
+    /// Constructs a new `RelativeOid` from `Vec<u64>`.
+    pub fn new(components: Vec<u64>) -> Self {
+        return RelativeOid {
+            components,
+        };
+    }
+
+    /// Constructs a new `RelativeOid` from `&[u64]`.
+    pub fn from_slice(components: &[u64]) -> Self {
+        return RelativeOid {
+            components: components.to_vec(),
+        };
+    }
+
+    /// Borrows its internal vector of components.
+    pub fn components(&self) -> &Vec<u64> {
+        &self.components
+    }
+
+    /// Mutably borrows its internal vector of components.
+    pub fn components_mut(&mut self) -> &mut Vec<u64> {
+        &mut self.components
+    }
+
+    /// Extracts its internal vector of components.
+    pub fn into_components(self) -> Vec<u64> {
+        self.components
+    }
+}
+
+impl Display for RelativeOid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut fst = true;
+        for &component in &self.components {
+            if fst {
+                write!(f, "{}", component)?;
+            } else {
+                write!(f, ".{}", component)?;
+            }
+            fst = false;
+        }
+        return Ok(());
+    }
+}
+
+impl FromStr for RelativeOid {
+    type Err = ParseOidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return parse_components(s).map(RelativeOid::new);
+    }
+}
+
+impl AsRef<[u64]> for RelativeOid {
+    fn as_ref(&self) -> &[u64] {
+        &self.components
+    }
+}
+
+impl From<Vec<u64>> for RelativeOid {
+    fn from(components: Vec<u64>) -> RelativeOid {
+        Self::new(components)
+    }
+}
+
 #[test]
 fn test_display_oid() {
     use alloc::format;
@@ -171,3 +624,52 @@ fn parse_oid() {
     "1.2.840.113549.1.1x".parse::<ObjectIdentifier>().unwrap_err();
     "".parse::<ObjectIdentifier>().unwrap_err();
 }
+
+#[test]
+fn oid_navigation() {
+    let rsadsi = ObjectIdentifier::from_slice(&[1, 2, 840, 113549]);
+    let pkcs1 = rsadsi.child(1).child(1);
+    assert_eq!(pkcs1.components(), &[1, 2, 840, 113549, 1, 1]);
+    assert_eq!(pkcs1.parent().and_then(|oid| oid.parent()),
+        Some(rsadsi.clone()));
+    assert!(pkcs1.starts_with(&rsadsi));
+    assert!(!rsadsi.starts_with(&pkcs1));
+    assert!(!pkcs1.starts_with(&ObjectIdentifier::from_slice(&[1, 3])));
+    assert!(pkcs1.starts_with(&ObjectIdentifier::new(Vec::new())));
+    assert_eq!(ObjectIdentifier::new(Vec::new()).parent(), None);
+    let mut sum = 0;
+    for arc in &pkcs1 {
+        sum += arc;
+    }
+    assert_eq!(sum, pkcs1.iter().sum());
+}
+
+#[test]
+fn parse_oid_errors() {
+    let tests: &[(&str, ParseOidErrorKind, Option<usize>)] = &[
+        ("", ParseOidErrorKind::Empty, Some(0)),
+        ("1..2", ParseOidErrorKind::Empty, Some(1)),
+        ("1.2.", ParseOidErrorKind::Empty, Some(2)),
+        ("1.+2", ParseOidErrorKind::InvalidDigit, Some(1)),
+        ("1.2.-3", ParseOidErrorKind::InvalidDigit, Some(2)),
+        ("1.2.18446744073709551616", ParseOidErrorKind::Overflow, Some(2)),
+        ("1", ParseOidErrorKind::InvalidOid, None),
+        ("3.1", ParseOidErrorKind::InvalidOid, None),
+        ("0.40", ParseOidErrorKind::InvalidOid, None),
+    ];
+    for &(s, kind, component) in tests {
+        let err = s.parse::<ObjectIdentifier>().unwrap_err();
+        assert_eq!((err.kind(), err.component()), (kind, component), "{}", s);
+    }
+    assert!("2.999.18446744073709551615".parse::<ObjectIdentifier>().is_ok());
+}
+
+#[test]
+fn parse_relative_oid() {
+    use alloc::format;
+    let roid = "8571.3.2".parse::<RelativeOid>().unwrap();
+    assert_eq!(roid.components(), &[8571, 3, 2]);
+    assert_eq!(format!("{}", roid), "8571.3.2");
+    "8571.".parse::<RelativeOid>().unwrap_err();
+    "".parse::<RelativeOid>().unwrap_err();
+}
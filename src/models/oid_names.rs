@@ -0,0 +1,180 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::ObjectIdentifier;
+
+/// The built-in names: the attribute types of RFC 4514 and a few others
+/// common in distinguished names, followed by common algorithms and
+/// certificate extensions.
+static BUILTIN: &[(&[u64], &str)] = &[
+    (&[2, 5, 4, 3], "CN"),
+    (&[2, 5, 4, 4], "SN"),
+    (&[2, 5, 4, 5], "serialNumber"),
+    (&[2, 5, 4, 6], "C"),
+    (&[2, 5, 4, 7], "L"),
+    (&[2, 5, 4, 8], "ST"),
+    (&[2, 5, 4, 9], "STREET"),
+    (&[2, 5, 4, 10], "O"),
+    (&[2, 5, 4, 11], "OU"),
+    (&[2, 5, 4, 12], "title"),
+    (&[2, 5, 4, 42], "GN"),
+    (&[2, 5, 4, 43], "initials"),
+    (&[2, 5, 4, 46], "dnQualifier"),
+    (&[2, 5, 4, 65], "pseudonym"),
+    (&[0, 9, 2342, 19200300, 100, 1, 1], "UID"),
+    (&[0, 9, 2342, 19200300, 100, 1, 25], "DC"),
+    (&[1, 2, 840, 113549, 1, 9, 1], "emailAddress"),
+    (&[1, 2, 840, 113549, 1, 1, 1], "rsaEncryption"),
+    (&[1, 2, 840, 113549, 1, 1, 10], "RSASSA-PSS"),
+    (&[1, 2, 840, 113549, 1, 1, 11], "sha256WithRSAEncryption"),
+    (&[1, 2, 840, 113549, 1, 1, 12], "sha384WithRSAEncryption"),
+    (&[1, 2, 840, 113549, 1, 1, 13], "sha512WithRSAEncryption"),
+    (&[1, 2, 840, 10045, 2, 1], "ecPublicKey"),
+    (&[1, 2, 840, 10045, 3, 1, 7], "prime256v1"),
+    (&[1, 3, 132, 0, 34], "secp384r1"),
+    (&[1, 3, 132, 0, 35], "secp521r1"),
+    (&[1, 2, 840, 10045, 4, 3, 2], "ecdsa-with-SHA256"),
+    (&[1, 2, 840, 10045, 4, 3, 3], "ecdsa-with-SHA384"),
+    (&[1, 2, 840, 10045, 4, 3, 4], "ecdsa-with-SHA512"),
+    (&[1, 3, 101, 112], "Ed25519"),
+    (&[1, 3, 101, 113], "Ed448"),
+    (&[2, 16, 840, 1, 101, 3, 4, 2, 1], "sha256"),
+    (&[2, 16, 840, 1, 101, 3, 4, 2, 2], "sha384"),
+    (&[2, 16, 840, 1, 101, 3, 4, 2, 3], "sha512"),
+    (&[2, 5, 29, 14], "subjectKeyIdentifier"),
+    (&[2, 5, 29, 15], "keyUsage"),
+    (&[2, 5, 29, 17], "subjectAltName"),
+    (&[2, 5, 29, 19], "basicConstraints"),
+    (&[2, 5, 29, 31], "cRLDistributionPoints"),
+    (&[2, 5, 29, 32], "certificatePolicies"),
+    (&[2, 5, 29, 35], "authorityKeyIdentifier"),
+    (&[2, 5, 29, 37], "extKeyUsage"),
+];
+
+/// A table mapping object identifiers to short names and vice versa.
+///
+/// [`new`](Self::new) starts with built-in names for the attribute types
+/// in distinguished names (such as `CN` for `2.5.4.3`), common signature
+/// and hash algorithms, and certificate extensions. More entries can be
+/// added with [`register`](Self::register).
+///
+/// Names are looked up case-insensitively, as attribute types in string
+/// representations of distinguished names are.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::{ObjectIdentifier, OidRegistry};
+/// let mut registry = OidRegistry::new();
+/// let cn = ObjectIdentifier::from_slice(&[2, 5, 4, 3]);
+/// assert_eq!(registry.name(&cn), Some("CN"));
+/// assert_eq!(registry.oid("cn"), Some(cn));
+///
+/// let example = ObjectIdentifier::from_slice(&[1, 3, 6, 1, 4, 1, 32473, 1]);
+/// registry.register(example.clone(), "exampleAttribute");
+/// assert_eq!(registry.name(&example), Some("exampleAttribute"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OidRegistry {
+    builtin: bool,
+    entries: Vec<(ObjectIdentifier, String)>,
+}
+
+impl OidRegistry {
+    /// Constructs a table with the built-in names.
+    pub fn new() -> Self {
+        return OidRegistry {
+            builtin: true,
+            entries: Vec::new(),
+        };
+    }
+
+    /// Constructs a table without any names.
+    pub fn empty() -> Self {
+        return OidRegistry {
+            builtin: false,
+            entries: Vec::new(),
+        };
+    }
+
+    /// Adds a name for `oid`.
+    ///
+    /// Entries registered later take precedence, in both directions,
+    /// over earlier and built-in ones.
+    pub fn register<S: Into<String>>(&mut self, oid: ObjectIdentifier,
+            name: S) {
+        self.entries.push((oid, name.into()));
+    }
+
+    /// Looks up the name of `oid`.
+    pub fn name(&self, oid: &ObjectIdentifier) -> Option<&str> {
+        if let Some(entry) = self.entries.iter().rev()
+                .find(|entry| entry.0 == *oid) {
+            return Some(&entry.1);
+        }
+        return self.builtin()
+            .find(|entry| entry.0 == oid.components().as_slice())
+            .map(|entry| entry.1);
+    }
+
+    /// Looks up the OID named `name`.
+    pub fn oid(&self, name: &str) -> Option<ObjectIdentifier> {
+        if let Some(entry) = self.entries.iter().rev()
+                .find(|entry| entry.1.eq_ignore_ascii_case(name)) {
+            return Some(entry.0.clone());
+        }
+        return self.builtin()
+            .find(|entry| entry.1.eq_ignore_ascii_case(name))
+            .map(|entry| ObjectIdentifier::from_slice(entry.0));
+    }
+
+    fn builtin(&self) -> impl Iterator<Item = &'static (&'static [u64],
+            &'static str)> {
+        let builtin = if self.builtin { BUILTIN } else { &[] };
+        return builtin.iter();
+    }
+}
+
+impl Default for OidRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn test_builtin_names() {
+    let registry = OidRegistry::new();
+    for &(oid, name) in BUILTIN {
+        let oid = ObjectIdentifier::from_slice(oid);
+        assert!(oid.is_valid(), "{}", oid);
+        assert_eq!(registry.name(&oid), Some(name));
+        assert_eq!(registry.oid(name), Some(oid));
+    }
+    let empty = OidRegistry::empty();
+    assert_eq!(empty.name(&ObjectIdentifier::from_slice(&[2, 5, 4, 3])), None);
+    assert_eq!(empty.oid("CN"), None);
+}
+
+#[test]
+fn test_register_names() {
+    let mut registry = OidRegistry::new();
+    let cn = ObjectIdentifier::from_slice(&[2, 5, 4, 3]);
+    let example = ObjectIdentifier::from_slice(&[1, 3, 6, 1, 4, 1, 32473]);
+    assert_eq!(registry.name(&example), None);
+    assert_eq!(registry.oid("example"), None);
+    registry.register(example.clone(), "example");
+    assert_eq!(registry.name(&example), Some("example"));
+    assert_eq!(registry.oid("EXAMPLE"), Some(example.clone()));
+    registry.register(cn.clone(), "commonName");
+    assert_eq!(registry.name(&cn), Some("commonName"));
+    assert_eq!(registry.oid("commonName"), Some(cn.clone()));
+    assert_eq!(registry.oid("CN"), Some(cn));
+    registry.register(example.clone(), "CN");
+    assert_eq!(registry.oid("CN"), Some(example));
+}
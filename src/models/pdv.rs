@@ -0,0 +1,58 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::vec::Vec;
+use super::ObjectIdentifier;
+
+/// A type that represents ASN.1 EMBEDDED PDV values.
+///
+/// As encoded by X.690 8.11, it consists of the `identification` and the
+/// `data-value`; the `data-value-descriptor` component is always absent.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::{EmbeddedPdv, ObjectIdentifier, PdvIdentification};
+/// let pdv = EmbeddedPdv {
+///     identification: PdvIdentification::Syntax(
+///         ObjectIdentifier::from_slice(&[2, 1, 1])),
+///     data_value: b"Hi!".to_vec(),
+/// };
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct EmbeddedPdv {
+    /// The `identification` component.
+    pub identification: PdvIdentification,
+    /// The `data-value` component.
+    pub data_value: Vec<u8>,
+}
+
+/// The `identification` CHOICE of EMBEDDED PDV.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PdvIdentification {
+    /// The `syntaxes` alternative: abstract and transfer syntaxes.
+    Syntaxes {
+        /// The `abstract` component.
+        abstract_syntax: ObjectIdentifier,
+        /// The `transfer` component.
+        transfer_syntax: ObjectIdentifier,
+    },
+    /// The `syntax` alternative.
+    Syntax(ObjectIdentifier),
+    /// The `presentation-context-id` alternative.
+    PresentationContextId(i64),
+    /// The `context-negotiation` alternative.
+    ContextNegotiation {
+        /// The `presentation-context-id` component.
+        presentation_context_id: i64,
+        /// The `transfer-syntax` component.
+        transfer_syntax: ObjectIdentifier,
+    },
+    /// The `transfer-syntax` alternative.
+    TransferSyntax(ObjectIdentifier),
+    /// The `fixed` alternative.
+    Fixed,
+}
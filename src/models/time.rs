@@ -179,11 +179,38 @@ impl UTCTime {
         });
     }
 
+    /// Constructs `UTCTime` from a `PrimitiveDateTime` in UTC.
+    ///
+    /// # Panics
+    ///
+    /// Panics when UTCTime can't represent the datetime. See
+    /// [`from_datetime`](Self::from_datetime).
+    pub fn from_primitive_datetime(datetime: PrimitiveDateTime) -> Self {
+        return Self::from_datetime(datetime.assume_utc());
+    }
+
+    /// Constructs `UTCTime` from a `PrimitiveDateTime` in UTC.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when UTCTime can't represent the datetime. See
+    /// [`from_datetime_opt`](Self::from_datetime_opt).
+    pub fn from_primitive_datetime_opt(datetime: PrimitiveDateTime)
+            -> Option<Self> {
+        return Self::from_datetime_opt(datetime.assume_utc());
+    }
+
     /// Returns the `OffsetDateTime` it represents.
     pub fn datetime(&self) -> &OffsetDateTime {
         &self.datetime
     }
 
+    /// Returns the datetime it represents in UTC as `PrimitiveDateTime`.
+    pub fn primitive_datetime(&self) -> PrimitiveDateTime {
+        return PrimitiveDateTime::new(self.datetime.date(),
+            self.datetime.time());
+    }
+
     /// Returns ASN.1 canonical representation of the datetime as `Vec<u8>`.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(13);
@@ -527,6 +554,27 @@ impl GeneralizedTime {
         });
     }
 
+    /// Constructs `GeneralizedTime` from a `PrimitiveDateTime` in UTC.
+    ///
+    /// # Panics
+    ///
+    /// Panics when GeneralizedTime can't represent the datetime. See
+    /// [`from_datetime`](Self::from_datetime).
+    pub fn from_primitive_datetime(datetime: PrimitiveDateTime) -> Self {
+        return Self::from_datetime(datetime.assume_utc());
+    }
+
+    /// Constructs `GeneralizedTime` from a `PrimitiveDateTime` in UTC.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when GeneralizedTime can't represent the datetime.
+    /// See [`from_datetime_opt`](Self::from_datetime_opt).
+    pub fn from_primitive_datetime_opt(datetime: PrimitiveDateTime)
+            -> Option<Self> {
+        return Self::from_datetime_opt(datetime.assume_utc());
+    }
+
     /// Returns the `OffsetDateTime` it represents.
     ///
     /// Leap seconds and sub-nanoseconds digits will be discarded.
@@ -534,6 +582,14 @@ impl GeneralizedTime {
         &self.datetime
     }
 
+    /// Returns the datetime it represents in UTC as `PrimitiveDateTime`.
+    ///
+    /// Leap seconds and sub-nanoseconds digits will be discarded.
+    pub fn primitive_datetime(&self) -> PrimitiveDateTime {
+        return PrimitiveDateTime::new(self.datetime.date(),
+            self.datetime.time());
+    }
+
     /// Returns sub-nanoseconds digits of the datetime.
     pub fn sub_nano(&self) -> &[u8] {
         &self.sub_nano
@@ -664,3 +720,25 @@ fn test_generalized_time_parse() {
     assert_eq!(&datetime.to_string(),
         "20080229083411.362543198461239167239162553291863600068Z");
 }
+
+#[test]
+fn test_primitive_datetime() {
+    let primitive = PrimitiveDateTime::new(
+        Date::from_calendar_date(1982, Month::January, 2).unwrap(),
+        Time::from_hms(12, 0, 0).unwrap());
+    let datetime = UTCTime::from_primitive_datetime(primitive);
+    assert_eq!(&datetime.to_string(), "820102120000Z");
+    assert_eq!(datetime.primitive_datetime(), primitive);
+    let datetime = GeneralizedTime::from_primitive_datetime(primitive);
+    assert_eq!(&datetime.to_string(), "19820102120000Z");
+    assert_eq!(datetime.primitive_datetime(), primitive);
+
+    let datetime = UTCTime::parse(b"8201020700-0500").unwrap();
+    assert_eq!(datetime.primitive_datetime(), primitive);
+
+    let primitive = PrimitiveDateTime::new(
+        Date::from_calendar_date(2050, Month::January, 1).unwrap(),
+        Time::from_hms(0, 0, 0).unwrap());
+    assert!(UTCTime::from_primitive_datetime_opt(primitive).is_none());
+    assert!(GeneralizedTime::from_primitive_datetime_opt(primitive).is_some());
+}
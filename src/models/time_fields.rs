@@ -0,0 +1,1101 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! UTCTime and GeneralizedTime values as written, without depending on
+//! a date and time library.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone, Timelike,
+    Utc};
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The time zone designator of [`UTCTimeFields`] or
+/// [`GeneralizedTimeFields`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TimeZoneDesignator {
+    /// No designator, meaning the local time. Only GeneralizedTime
+    /// allows this.
+    Local,
+    /// `Z`, meaning UTC.
+    Utc,
+    /// `+hhmm` or `-hhmm`: the offset from UTC in minutes, positive
+    /// to the east.
+    Offset(i16),
+}
+
+/// An ASN.1 UTCTime value, holding the fields as written.
+///
+/// Unlike [`UTCTime`](crate::models::UTCTime), it doesn't depend on the
+/// `time` crate, doesn't convert the value to UTC, and keeps whether the
+/// seconds were present.
+///
+/// The two-digit year is interpreted as between 1950 and 2049, as X.501
+/// specifies.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::{TimeZoneDesignator, UTCTimeFields};
+/// let time = UTCTimeFields::parse(b"8201020700-0500").unwrap();
+/// assert_eq!(time.year(), 1982);
+/// assert_eq!((time.hour(), time.minute(), time.second()), (7, 0, None));
+/// assert_eq!(time.zone(), TimeZoneDesignator::Offset(-300));
+/// assert!(!time.is_der());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct UTCTimeFields {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: Option<u8>,
+    zone: TimeZoneDesignator,
+}
+
+impl UTCTimeFields {
+    /// Constructs a UTC value with seconds, which is the form DER
+    /// requires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::UTCTimeFields;
+    /// let time = UTCTimeFields::new(2019, 12, 31, 23, 59, 59).unwrap();
+    /// assert_eq!(time.to_string(), "191231235959Z");
+    /// assert!(time.is_der());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if the year is not between 1950 and 2049, or
+    /// the other fields don't specify a correct time.
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8,
+            second: u8) -> Option<Self> {
+        if !(1950..2050).contains(&year) {
+            return None;
+        }
+        let time = UTCTimeFields {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second: Some(second),
+            zone: TimeZoneDesignator::Utc,
+        };
+        if !time.is_valid() {
+            return None;
+        }
+        return Some(time);
+    }
+
+    /// Parses ASN.1 string representation of UTCTime.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if the given string does not specify a correct
+    /// time.
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        let mut cursor = Cursor::new(buf);
+        let year_short = cursor.digits(2)?;
+        let year = if year_short < 50 {
+            2000 + year_short
+        } else {
+            1900 + year_short
+        };
+        let month = cursor.digits(2)? as u8;
+        let day = cursor.digits(2)? as u8;
+        let hour = cursor.digits(2)? as u8;
+        let minute = cursor.digits(2)? as u8;
+        let second = cursor.optional_digits(2).map(|s| s as u8);
+        let zone = cursor.zone(false)?;
+        if !cursor.is_end() || zone == TimeZoneDesignator::Local {
+            return None;
+        }
+        let time = UTCTimeFields {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            zone,
+        };
+        if !time.is_valid() {
+            return None;
+        }
+        return Some(time);
+    }
+
+    fn is_valid(&self) -> bool {
+        return is_valid_date(self.year, self.month, self.day) &&
+            self.hour < 24 && self.minute < 60 &&
+            self.second.map_or(true, |second| second < 60);
+    }
+
+    /// Returns the year, between 1950 and 2049.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month, between 1 and 12.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, starting from 1.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the hour, between 0 and 23.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute, between 0 and 59.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns the second, between 0 and 59, or `None` if omitted.
+    pub fn second(&self) -> Option<u8> {
+        self.second
+    }
+
+    /// Returns the time zone designator, which is never `Local`.
+    pub fn zone(&self) -> TimeZoneDesignator {
+        self.zone
+    }
+
+    /// Checks if it is in the form DER requires: with seconds, in UTC.
+    pub fn is_der(&self) -> bool {
+        self.second.is_some() && self.zone == TimeZoneDesignator::Utc
+    }
+
+    /// Returns ASN.1 string representation of the value as `Vec<u8>`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(17);
+        push_digits(&mut buf, u32::from(self.year % 100), 2);
+        push_digits(&mut buf, u32::from(self.month), 2);
+        push_digits(&mut buf, u32::from(self.day), 2);
+        push_digits(&mut buf, u32::from(self.hour), 2);
+        push_digits(&mut buf, u32::from(self.minute), 2);
+        if let Some(second) = self.second {
+            push_digits(&mut buf, u32::from(second), 2);
+        }
+        push_zone(&mut buf, self.zone);
+        return buf;
+    }
+}
+
+impl Display for UTCTimeFields {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for &b in &self.to_bytes() {
+            write!(f, "{}", b as char)?;
+        }
+        return Ok(());
+    }
+}
+
+/// An ASN.1 GeneralizedTime value, holding the fields as written.
+///
+/// Unlike [`GeneralizedTime`](crate::models::GeneralizedTime), it
+/// doesn't depend on the `time` crate, doesn't convert the value to UTC,
+/// and keeps the precision it was written in: the minutes and seconds
+/// may be omitted, and the fraction applies to the last unit present.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::models::{GeneralizedTimeFields, TimeZoneDesignator};
+/// let time = GeneralizedTimeFields::parse(b"1985110621.14159Z").unwrap();
+/// assert_eq!(time.year(), 1985);
+/// assert_eq!((time.hour(), time.minute(), time.second()), (21, None, None));
+/// // 0.14159 hours
+/// assert_eq!(time.fraction(), b"14159");
+/// assert_eq!(time.zone(), TimeZoneDesignator::Utc);
+/// assert!(!time.is_der());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct GeneralizedTimeFields {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: Option<u8>,
+    second: Option<u8>,
+    fraction: Vec<u8>,
+    zone: TimeZoneDesignator,
+}
+
+impl GeneralizedTimeFields {
+    /// Constructs a UTC value with seconds and no fraction, which is
+    /// a form DER allows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::GeneralizedTimeFields;
+    /// let time = GeneralizedTimeFields::new(2049, 12, 31, 23, 59, 59)
+    ///     .unwrap().with_fraction(b"125").unwrap();
+    /// assert_eq!(time.to_string(), "20491231235959.125Z");
+    /// assert!(time.is_der());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if the year is greater than 9999, or the other
+    /// fields don't specify a correct time. The second may be 60 for
+    /// a leap second.
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8,
+            second: u8) -> Option<Self> {
+        let time = GeneralizedTimeFields {
+            year,
+            month,
+            day,
+            hour,
+            minute: Some(minute),
+            second: Some(second),
+            fraction: Vec::new(),
+            zone: TimeZoneDesignator::Utc,
+        };
+        if !time.is_valid() {
+            return None;
+        }
+        return Some(time);
+    }
+
+    /// Replaces the fraction with the decimal `digits`, given as ASCII.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if `digits` contains a non-digit character.
+    pub fn with_fraction(mut self, digits: &[u8]) -> Option<Self> {
+        if !digits.iter().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        self.fraction = digits.to_vec();
+        return Some(self);
+    }
+
+    /// Parses ASN.1 string representation of GeneralizedTime.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` if the given string does not specify a correct
+    /// time.
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        let mut cursor = Cursor::new(buf);
+        let year = cursor.digits(4)?;
+        let month = cursor.digits(2)? as u8;
+        let day = cursor.digits(2)? as u8;
+        let hour = cursor.digits(2)? as u8;
+        let minute = cursor.optional_digits(2).map(|m| m as u8);
+        let second = if minute.is_some() {
+            cursor.optional_digits(2).map(|s| s as u8)
+        } else {
+            None
+        };
+        let mut fraction = Vec::new();
+        if cursor.skip_if(|b| b == b'.' || b == b',') {
+            fraction = cursor.take_while(|b| b.is_ascii_digit()).to_vec();
+            if fraction.is_empty() {
+                return None;
+            }
+        }
+        let zone = cursor.zone(true)?;
+        if !cursor.is_end() {
+            return None;
+        }
+        let time = GeneralizedTimeFields {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            fraction,
+            zone,
+        };
+        if !time.is_valid() {
+            return None;
+        }
+        return Some(time);
+    }
+
+    fn is_valid(&self) -> bool {
+        return self.year < 10000 &&
+            is_valid_date(self.year, self.month, self.day) &&
+            self.hour < 24 && self.minute.map_or(true, |minute| minute < 60) &&
+            self.second.map_or(true, |second| second <= 60);
+    }
+
+    /// Returns the year, between 0 and 9999.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month, between 1 and 12.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, starting from 1.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the hour, between 0 and 23.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute, between 0 and 59, or `None` if omitted.
+    pub fn minute(&self) -> Option<u8> {
+        self.minute
+    }
+
+    /// Returns the second, between 0 and 60, or `None` if omitted.
+    pub fn second(&self) -> Option<u8> {
+        self.second
+    }
+
+    /// Returns the decimal digits of the fraction as ASCII, which is
+    /// empty if omitted.
+    ///
+    /// The fraction is of the last unit present: the second, the minute
+    /// or the hour.
+    pub fn fraction(&self) -> &[u8] {
+        &self.fraction
+    }
+
+    /// Returns the time zone designator.
+    pub fn zone(&self) -> TimeZoneDesignator {
+        self.zone
+    }
+
+    /// Checks if it is in the form DER requires: with seconds, in UTC,
+    /// and without trailing zeros in the fraction.
+    pub fn is_der(&self) -> bool {
+        self.second.is_some() && self.zone == TimeZoneDesignator::Utc &&
+            self.fraction.last() != Some(&b'0')
+    }
+
+    /// Returns ASN.1 string representation of the value as `Vec<u8>`.
+    ///
+    /// The fraction is always separated by `.`, and an offset is always
+    /// written with minutes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(20 + self.fraction.len());
+        push_digits(&mut buf, u32::from(self.year), 4);
+        push_digits(&mut buf, u32::from(self.month), 2);
+        push_digits(&mut buf, u32::from(self.day), 2);
+        push_digits(&mut buf, u32::from(self.hour), 2);
+        if let Some(minute) = self.minute {
+            push_digits(&mut buf, u32::from(minute), 2);
+        }
+        if let Some(second) = self.second {
+            push_digits(&mut buf, u32::from(second), 2);
+        }
+        if !self.fraction.is_empty() {
+            buf.push(b'.');
+            buf.extend_from_slice(&self.fraction);
+        }
+        push_zone(&mut buf, self.zone);
+        return buf;
+    }
+}
+
+impl Display for GeneralizedTimeFields {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for &b in &self.to_bytes() {
+            write!(f, "{}", b as char)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(feature = "chrono")]
+/// Conversions from and to `chrono::DateTime<Utc>`.
+///
+/// # Features
+///
+/// These methods are enabled by `chrono` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["chrono"] }
+/// ```
+impl UTCTimeFields {
+    /// Constructs the DER form of `datetime`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when UTCTime can't represent the datetime. That is:
+    ///
+    /// - The year is not between 1950 and 2049.
+    /// - It is in a leap second.
+    /// - It has a non-zero nanosecond value.
+    pub fn from_chrono(datetime: &DateTime<Utc>) -> Self {
+        assert!(1950 <= datetime.year() && datetime.year() < 2050,
+            "Can't express a year {:?} in UTCTime", datetime.year());
+        assert!(datetime.nanosecond() < 1_000_000_000,
+            "Can't express a leap second in UTCTime");
+        assert!(datetime.nanosecond() == 0,
+            "Can't express a non-zero nanosecond in UTCTime");
+        return Self::from_chrono_opt(datetime).unwrap();
+    }
+
+    /// Constructs the DER form of `datetime`.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when UTCTime can't represent the datetime. That is:
+    ///
+    /// - The year is not between 1950 and 2049.
+    /// - It is in a leap second.
+    /// - It has a non-zero nanosecond value.
+    pub fn from_chrono_opt(datetime: &DateTime<Utc>) -> Option<Self> {
+        if datetime.nanosecond() != 0 ||
+                !(1950..2050).contains(&datetime.year()) {
+            return None;
+        }
+        return Self::new(datetime.year() as u16, datetime.month() as u8,
+            datetime.day() as u8, datetime.hour() as u8,
+            datetime.minute() as u8, datetime.second() as u8);
+    }
+
+    /// Converts it to UTC as `chrono::DateTime<Utc>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::UTCTimeFields;
+    /// let time = UTCTimeFields::parse(b"8201020700-0500").unwrap();
+    /// assert_eq!(time.to_chrono().timestamp(), 378820800);
+    /// ```
+    pub fn to_chrono(&self) -> DateTime<Utc> {
+        let second = self.second.unwrap_or(0);
+        return to_chrono(self.year, self.month, self.day,
+            (self.hour, Some(self.minute), Some(second)), &[], self.zone)
+            .unwrap();
+    }
+}
+
+#[cfg(feature = "chrono")]
+/// Conversions from and to `chrono::DateTime<Utc>`.
+///
+/// # Features
+///
+/// These methods are enabled by `chrono` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["chrono"] }
+/// ```
+impl GeneralizedTimeFields {
+    /// Constructs the DER form of `datetime`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the year is not between 0 and 9999.
+    pub fn from_chrono(datetime: &DateTime<Utc>) -> Self {
+        assert!(0 <= datetime.year() && datetime.year() < 10000,
+            "Can't express a year {:?} in GeneralizedTime", datetime.year());
+        return Self::from_chrono_opt(datetime).unwrap();
+    }
+
+    /// Constructs the DER form of `datetime`.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when the year is not between 0 and 9999.
+    pub fn from_chrono_opt(datetime: &DateTime<Utc>) -> Option<Self> {
+        if !(0..10000).contains(&datetime.year()) {
+            return None;
+        }
+        // chrono represents a leap second as nanoseconds beyond a second.
+        let leap = datetime.nanosecond() >= 1_000_000_000;
+        let nanosecond = datetime.nanosecond() % 1_000_000_000;
+        let mut fraction = Vec::with_capacity(9);
+        push_digits(&mut fraction, nanosecond, 9);
+        while fraction.last() == Some(&b'0') {
+            fraction.pop();
+        }
+        let time = Self::new(datetime.year() as u16, datetime.month() as u8,
+            datetime.day() as u8, datetime.hour() as u8,
+            datetime.minute() as u8,
+            datetime.second() as u8 + if leap { 1 } else { 0 })?;
+        return time.with_fraction(&fraction);
+    }
+
+    /// Converts it to UTC as `chrono::DateTime<Utc>`.
+    ///
+    /// Digits of the fraction beyond nanoseconds are truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::models::GeneralizedTimeFields;
+    /// let time = GeneralizedTimeFields::parse(b"1985110621.14159Z").unwrap();
+    /// let datetime = time.to_chrono().unwrap();
+    /// assert_eq!(datetime.timestamp(), 500159309);
+    /// assert_eq!(datetime.timestamp_subsec_nanos(), 724_000_000);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when it is in the local time, without a time
+    /// zone, or chrono can't represent the datetime.
+    pub fn to_chrono(&self) -> Option<DateTime<Utc>> {
+        return to_chrono(self.year, self.month, self.day,
+            (self.hour, self.minute, self.second), &self.fraction, self.zone);
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn to_chrono(year: u16, month: u8, day: u8,
+        (hour, minute, second): (u8, Option<u8>, Option<u8>),
+        fraction: &[u8], zone: TimeZoneDesignator) -> Option<DateTime<Utc>> {
+    let offset = match zone {
+        TimeZoneDesignator::Local => return None,
+        TimeZoneDesignator::Utc => 0,
+        TimeZoneDesignator::Offset(offset) => i64::from(offset),
+    };
+    let nanosecond = fraction_nanos(minute, second, fraction) as i64;
+    let second = second.unwrap_or(0);
+    let date = NaiveDate::from_ymd_opt(i32::from(year), u32::from(month),
+        u32::from(day))?;
+    let datetime: NaiveDateTime = if second == 60 {
+        // Only a fraction of a second is added here, so it stays in the
+        // leap second.
+        date.and_hms_nano_opt(u32::from(hour), u32::from(minute.unwrap_or(0)),
+            59, 1_000_000_000 + nanosecond as u32)?
+    } else {
+        date.and_hms_opt(u32::from(hour), u32::from(minute.unwrap_or(0)),
+            u32::from(second))?
+            .checked_add_signed(chrono::Duration::nanoseconds(nanosecond))?
+    };
+    let datetime = datetime
+        .checked_sub_signed(chrono::Duration::minutes(offset))?;
+    return Some(Utc.from_utc_datetime(&datetime));
+}
+
+#[cfg(feature = "std")]
+/// Conversions from and to `std::time::SystemTime`.
+///
+/// `SystemTime` doesn't count leap seconds, like Unix time.
+///
+/// # Features
+///
+/// These methods are enabled by `std` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["std"] }
+/// ```
+impl UTCTimeFields {
+    /// Constructs the DER form of `time`, discarding the fraction of
+    /// a second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use yasna::models::UTCTimeFields;
+    /// let now = SystemTime::now();
+    /// let not_before = UTCTimeFields::from_system_time(now);
+    /// let not_after = UTCTimeFields::from_system_time(
+    ///     now + Duration::from_secs(365 * 24 * 60 * 60));
+    /// # assert!(not_before.is_der() && not_after.is_der());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when the year is not between 1950 and 2049.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        return Self::from_system_time_opt(time)
+            .expect("Can't express the year in UTCTime");
+    }
+
+    /// Constructs the DER form of `time`, discarding the fraction of
+    /// a second.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when the year is not between 1950 and 2049.
+    pub fn from_system_time_opt(time: SystemTime) -> Option<Self> {
+        let (secs, _) = unix_time(time);
+        let (year, month, day, hour, minute, second) = civil_from_secs(secs);
+        if !(1950..2050).contains(&year) {
+            return None;
+        }
+        return Self::new(year as u16, month, day, hour, minute, second);
+    }
+
+    /// Converts it to `SystemTime`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use yasna::models::UTCTimeFields;
+    /// let time = UTCTimeFields::parse(b"8201020700-0500").unwrap();
+    /// assert_eq!(time.to_system_time(),
+    ///     Some(UNIX_EPOCH + Duration::from_secs(378820800)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when `SystemTime` can't represent it on the
+    /// platform.
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        return to_system_time(self.year, self.month, self.day,
+            (self.hour, Some(self.minute), self.second), &[], self.zone);
+    }
+}
+
+#[cfg(feature = "std")]
+/// Conversions from and to `std::time::SystemTime`.
+///
+/// `SystemTime` doesn't count leap seconds, like Unix time.
+///
+/// # Features
+///
+/// These methods are enabled by `std` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["std"] }
+/// ```
+impl GeneralizedTimeFields {
+    /// Constructs the DER form of `time`, including the fraction of
+    /// a second if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the year is not between 0 and 9999.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        return Self::from_system_time_opt(time)
+            .expect("Can't express the year in GeneralizedTime");
+    }
+
+    /// Constructs the DER form of `time`, including the fraction of
+    /// a second if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use yasna::models::GeneralizedTimeFields;
+    /// let time = UNIX_EPOCH + Duration::from_millis(500159309724);
+    /// let time = GeneralizedTimeFields::from_system_time_opt(time).unwrap();
+    /// assert_eq!(time.to_string(), "19851106210829.724Z");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when the year is not between 0 and 9999.
+    pub fn from_system_time_opt(time: SystemTime) -> Option<Self> {
+        let (secs, nanos) = unix_time(time);
+        let (year, month, day, hour, minute, second) = civil_from_secs(secs);
+        if !(0..10000).contains(&year) {
+            return None;
+        }
+        let mut fraction = Vec::with_capacity(9);
+        push_digits(&mut fraction, nanos, 9);
+        while fraction.last() == Some(&b'0') {
+            fraction.pop();
+        }
+        return Self::new(year as u16, month, day, hour, minute, second)?
+            .with_fraction(&fraction);
+    }
+
+    /// Converts it to `SystemTime`.
+    ///
+    /// Digits of the fraction beyond nanoseconds are truncated. A leap
+    /// second is taken as the first second of the next minute.
+    ///
+    /// # Errors
+    ///
+    /// It returns `None` when it is in the local time, without a time
+    /// zone, or `SystemTime` can't represent it on the platform.
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        return to_system_time(self.year, self.month, self.day,
+            (self.hour, self.minute, self.second), &self.fraction, self.zone);
+    }
+}
+
+#[cfg(feature = "std")]
+/// Splits `time` into seconds since the Unix epoch, rounded down, and
+/// nanoseconds.
+fn unix_time(time: SystemTime) -> (i128, u32) {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => {
+            return (i128::from(duration.as_secs()), duration.subsec_nanos());
+        },
+        Err(err) => {
+            let duration = err.duration();
+            let secs = -i128::from(duration.as_secs());
+            if duration.subsec_nanos() == 0 {
+                return (secs, 0);
+            }
+            return (secs - 1, 1_000_000_000 - duration.subsec_nanos());
+        },
+    }
+}
+
+#[cfg(feature = "std")]
+/// Breaks down seconds since the Unix epoch into the date and time in UTC,
+/// in the proleptic Gregorian calendar.
+fn civil_from_secs(secs: i128) -> (i64, u8, u8, u8, u8, u8) {
+    let days = if secs >= 0 { secs / 86400 } else { (secs - 86399) / 86400 };
+    let time = (secs - days * 86400) as u32;
+    // Counting from 0000-03-01, so that the leap day is at the end of
+    // a year.
+    let days = days + 719468;
+    let era = if days >= 0 { days / 146097 } else { (days - 146096) / 146097 };
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 -
+        day_of_era / 146096) / 365;
+    let day_of_year = day_of_era -
+        (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    return (year as i64, month as u8, day as u8, (time / 3600) as u8,
+        (time / 60 % 60) as u8, (time % 60) as u8);
+}
+
+#[cfg(feature = "std")]
+fn to_system_time(year: u16, month: u8, day: u8,
+        (hour, minute, second): (u8, Option<u8>, Option<u8>),
+        fraction: &[u8], zone: TimeZoneDesignator) -> Option<SystemTime> {
+    let offset = match zone {
+        TimeZoneDesignator::Local => return None,
+        TimeZoneDesignator::Utc => 0,
+        TimeZoneDesignator::Offset(offset) => i128::from(offset),
+    };
+    // Counting from 0000-03-01, as in civil_from_secs.
+    let (year, month) = if month <= 2 {
+        (i128::from(year) - 1, i128::from(month) + 9)
+    } else {
+        (i128::from(year), i128::from(month) - 3)
+    };
+    let era = if year >= 0 { year / 400 } else { (year - 399) / 400 };
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * month + 2) / 5 + i128::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 +
+        day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+    let secs = days * 86400 + i128::from(hour) * 3600 +
+        i128::from(minute.unwrap_or(0)) * 60 +
+        i128::from(second.unwrap_or(0)) - offset * 60;
+    let nanos = secs * 1_000_000_000 +
+        i128::from(fraction_nanos(minute, second, fraction));
+    let duration = Duration::new((nanos.abs() / 1_000_000_000) as u64,
+        (nanos.abs() % 1_000_000_000) as u32);
+    if nanos >= 0 {
+        return UNIX_EPOCH.checked_add(duration);
+    } else {
+        return UNIX_EPOCH.checked_sub(duration);
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "std"))]
+/// Returns the fraction in nanoseconds. Digits beyond nanoseconds are
+/// truncated.
+fn fraction_nanos(minute: Option<u8>, second: Option<u8>, fraction: &[u8])
+        -> u64 {
+    // The fraction is of the last unit present.
+    let unit: u128 = match (minute, second) {
+        (None, _) => 3_600_000_000_000,
+        (Some(_), None) => 60_000_000_000,
+        (Some(_), Some(_)) => 1_000_000_000,
+    };
+    let fraction = &fraction[..fraction.len().min(18)];
+    let numerator = fraction.iter()
+        .fold(0u128, |acc, &b| acc * 10 + u128::from(b - b'0'));
+    return (unit * numerator / 10u128.pow(fraction.len() as u32)) as u64;
+}
+
+fn is_valid_date(year: u16, month: u8, day: u8) -> bool {
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    return 1 <= day && day <= days;
+}
+
+fn push_digits(buf: &mut Vec<u8>, value: u32, width: u32) {
+    for i in (0..width).rev() {
+        buf.push((value / 10u32.pow(i) % 10) as u8 + b'0');
+    }
+}
+
+fn push_zone(buf: &mut Vec<u8>, zone: TimeZoneDesignator) {
+    match zone {
+        TimeZoneDesignator::Local => {},
+        TimeZoneDesignator::Utc => buf.push(b'Z'),
+        TimeZoneDesignator::Offset(offset) => {
+            buf.push(if offset < 0 { b'-' } else { b'+' });
+            let offset = i32::from(offset).abs() as u32;
+            push_digits(buf, offset / 60, 2);
+            push_digits(buf, offset % 60, 2);
+        },
+    }
+}
+
+/// A simple scanner over the string representation.
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        return Cursor {
+            buf,
+            pos: 0,
+        };
+    }
+
+    fn is_end(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+
+    fn skip_if<P: Fn(u8) -> bool>(&mut self, pred: P) -> bool {
+        if self.pos < self.buf.len() && pred(self.buf[self.pos]) {
+            self.pos += 1;
+            return true;
+        }
+        return false;
+    }
+
+    fn take_while<P: Fn(u8) -> bool>(&mut self, pred: P) -> &'a [u8] {
+        let start = self.pos;
+        while self.skip_if(&pred) {}
+        return &self.buf[start..self.pos];
+    }
+
+    /// Reads exactly `width` decimal digits.
+    fn digits(&mut self, width: usize) -> Option<u16> {
+        let digits = self.buf.get(self.pos..self.pos + width)?;
+        if !digits.iter().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        self.pos += width;
+        return Some(digits.iter()
+            .fold(0, |acc, &b| acc * 10 + u16::from(b - b'0')));
+    }
+
+    /// Reads `width` decimal digits if they are there.
+    fn optional_digits(&mut self, width: usize) -> Option<u16> {
+        let pos = self.pos;
+        let value = self.digits(width);
+        if value.is_none() {
+            self.pos = pos;
+        }
+        return value;
+    }
+
+    /// Reads a time zone designator. The minutes of an offset are
+    /// optional if `short_offset` is set.
+    fn zone(&mut self, short_offset: bool) -> Option<TimeZoneDesignator> {
+        if self.skip_if(|b| b == b'Z') {
+            return Some(TimeZoneDesignator::Utc);
+        }
+        let negative = self.skip_if(|b| b == b'-');
+        if !negative && !self.skip_if(|b| b == b'+') {
+            return Some(TimeZoneDesignator::Local);
+        }
+        let hour = self.digits(2)?;
+        let minute = if short_offset {
+            self.optional_digits(2).unwrap_or(0)
+        } else {
+            self.digits(2)?
+        };
+        if !(hour < 24 && minute < 60) {
+            return None;
+        }
+        let offset = (hour * 60 + minute) as i16;
+        return Some(TimeZoneDesignator::Offset(
+            if negative { -offset } else { offset }));
+    }
+}
+
+#[test]
+fn test_utctime_fields_parse() {
+    let tests: &[(&str, Option<&str>)] = &[
+        ("8201021200Z", Some("8201021200Z")),
+        ("820102120034Z", Some("820102120034Z")),
+        ("0101021200Z", Some("0101021200Z")),
+        ("000229123456Z", Some("000229123456Z")),
+        ("8201020700-0500", Some("8201020700-0500")),
+        ("820102070012+0930", Some("820102070012+0930")),
+        ("990229123456Z", None),
+        ("8213021200Z", None),
+        ("8201022400Z", None),
+        ("820102120060Z", None),
+        ("8201021200", None),
+        ("8201021200+05", None),
+        ("8201021200+2400", None),
+        ("82010212Z", None),
+        ("8201021200ZZ", None),
+        ("82010212000Z", None),
+    ];
+    for &(s, expected) in tests {
+        let time = UTCTimeFields::parse(s.as_bytes());
+        assert_eq!(time.map(|time| time.to_bytes()),
+            expected.map(|e| e.as_bytes().to_vec()), "{}", s);
+    }
+    let time = UTCTimeFields::parse(b"4912312359Z").unwrap();
+    assert_eq!(time.year(), 2049);
+    let time = UTCTimeFields::parse(b"5001010000Z").unwrap();
+    assert_eq!(time.year(), 1950);
+    assert_eq!(UTCTimeFields::new(2050, 1, 1, 0, 0, 0), None);
+    assert_eq!(UTCTimeFields::new(2000, 2, 30, 0, 0, 0), None);
+}
+
+#[test]
+fn test_generalized_time_fields_parse() {
+    let tests: &[(&str, Option<&str>)] = &[
+        ("19851106210627.3Z", Some("19851106210627.3Z")),
+        ("19851106210627,30Z", Some("19851106210627.30Z")),
+        ("198511062106Z", Some("198511062106Z")),
+        ("198511062106.456Z", Some("198511062106.456Z")),
+        ("1985110621Z", Some("1985110621Z")),
+        ("1985110621.14159", Some("1985110621.14159")),
+        ("19990101085960.1234+0900", Some("19990101085960.1234+0900")),
+        ("19851106210627-05", Some("19851106210627-0500")),
+        ("00000101000000Z", Some("00000101000000Z")),
+        ("20000229000000Z", Some("20000229000000Z")),
+        ("19000229000000Z", None),
+        ("19851106210627.Z", None),
+        ("19851106210661Z", None),
+        ("198511062160Z", None),
+        ("1985110624Z", None),
+        ("19851106210627+2400", None),
+        ("19851106210627+05000", None),
+        ("198511062Z", None),
+        ("19851106210627Z0", None),
+    ];
+    for &(s, expected) in tests {
+        let time = GeneralizedTimeFields::parse(s.as_bytes());
+        assert_eq!(time.map(|time| time.to_bytes()),
+            expected.map(|e| e.as_bytes().to_vec()), "{}", s);
+    }
+    let time = GeneralizedTimeFields::parse(b"19851106210627.30Z").unwrap();
+    assert_eq!(time.second(), Some(27));
+    assert_eq!(time.fraction(), b"30");
+    assert!(!time.is_der());
+    let time = GeneralizedTimeFields::parse(b"19851106210627.3Z").unwrap();
+    assert!(time.is_der());
+    assert_eq!(GeneralizedTimeFields::new(10000, 1, 1, 0, 0, 0), None);
+    assert!(GeneralizedTimeFields::new(2016, 12, 31, 23, 59, 60).is_some());
+    assert_eq!(GeneralizedTimeFields::new(2016, 12, 31, 23, 59, 0).unwrap()
+        .with_fraction(b"1x"), None);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_time_fields_chrono() {
+    let tests: &[(&str, i64, u32)] = &[
+        ("19851106210627.3Z", 500159187, 300_000_000),
+        ("19851106210627.3-0500", 500177187, 300_000_000),
+        ("1985110621.14159Z", 500159309, 724_000_000),
+        ("198511062106.5+0130", 500153790, 0),
+        ("19851106210627.1234567891Z", 500159187, 123_456_789),
+        ("19691231235959.999999999Z", -1, 999_999_999),
+    ];
+    for &(s, secs, nanos) in tests {
+        let time = GeneralizedTimeFields::parse(s.as_bytes()).unwrap();
+        let datetime = time.to_chrono().unwrap();
+        assert_eq!((datetime.timestamp(), datetime.timestamp_subsec_nanos()),
+            (secs, nanos), "{}", s);
+        let der = GeneralizedTimeFields::from_chrono(&datetime);
+        assert!(der.is_der());
+        assert_eq!(der.to_chrono(), Some(datetime));
+    }
+    assert_eq!(GeneralizedTimeFields::parse(b"19851106210627").unwrap()
+        .to_chrono(), None);
+
+    let leap = GeneralizedTimeFields::parse(b"20161231235960.5Z").unwrap();
+    let datetime = leap.to_chrono().unwrap();
+    assert_eq!(datetime.second(), 59);
+    assert_eq!(datetime.nanosecond(), 1_500_000_000);
+    assert_eq!(GeneralizedTimeFields::from_chrono(&datetime), leap);
+    assert_eq!(UTCTimeFields::from_chrono_opt(&datetime), None);
+
+    let time = UTCTimeFields::parse(b"4912312359-0100").unwrap();
+    let datetime = time.to_chrono();
+    assert_eq!(datetime.year(), 2050);
+    assert_eq!(UTCTimeFields::from_chrono_opt(&datetime), None);
+    let datetime = Utc.timestamp_opt(378820800, 0).unwrap();
+    assert_eq!(UTCTimeFields::from_chrono(&datetime).to_bytes(),
+        b"820102120000Z");
+    let datetime = Utc.timestamp_opt(378820800, 1).unwrap();
+    assert_eq!(UTCTimeFields::from_chrono_opt(&datetime), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_time_fields_system_time() {
+    let tests: &[(&str, i64, u32)] = &[
+        ("19700101000000Z", 0, 0),
+        ("19851106210627.3Z", 500159187, 300_000_000),
+        ("19851106210627.3-0500", 500177187, 300_000_000),
+        ("1985110621.14159Z", 500159309, 724_000_000),
+        ("198511062106.5+0130", 500153790, 0),
+        ("19691231235959.999999999Z", -1, 999_999_999),
+        ("19000228235959Z", -2203891201, 0),
+        ("19000301000000Z", -2203891200, 0),
+        ("20000229120000Z", 951825600, 0),
+        ("21000301000000Z", 4107542400, 0),
+        ("99991231235959Z", 253402300799, 0),
+    ];
+    for &(s, secs, nanos) in tests {
+        let expected = if secs >= 0 {
+            UNIX_EPOCH + Duration::new(secs as u64, nanos)
+        } else {
+            UNIX_EPOCH - Duration::new(-secs as u64, 0) +
+                Duration::new(0, nanos)
+        };
+        let time = GeneralizedTimeFields::parse(s.as_bytes()).unwrap();
+        assert_eq!(time.to_system_time(), Some(expected), "{}", s);
+        let der = GeneralizedTimeFields::from_system_time(expected);
+        assert!(der.is_der());
+        assert_eq!(der.to_system_time(), Some(expected), "{}", s);
+    }
+    assert_eq!(GeneralizedTimeFields::parse(b"19851106210627").unwrap()
+        .to_system_time(), None);
+    let leap = GeneralizedTimeFields::parse(b"20161231235960Z").unwrap();
+    assert_eq!(leap.to_system_time(),
+        Some(UNIX_EPOCH + Duration::from_secs(1483228800)));
+
+    let time = UNIX_EPOCH + Duration::new(378820800, 500_000_000);
+    assert_eq!(UTCTimeFields::from_system_time(time).to_bytes(),
+        b"820102120000Z");
+    assert_eq!(UTCTimeFields::parse(b"8201020700-0500").unwrap()
+        .to_system_time(), Some(UNIX_EPOCH + Duration::from_secs(378820800)));
+    let time = UNIX_EPOCH + Duration::from_secs(2524608000);
+    assert_eq!(UTCTimeFields::from_system_time_opt(time), None);
+    let time = UNIX_EPOCH - Duration::from_secs(631152001);
+    assert_eq!(UTCTimeFields::from_system_time_opt(time), None);
+    let time = UNIX_EPOCH - Duration::from_secs(631152000);
+    assert_eq!(UTCTimeFields::from_system_time(time).to_bytes(),
+        b"500101000000Z");
+}
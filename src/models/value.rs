@@ -0,0 +1,68 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::vec::Vec;
+use alloc::string::String;
+use super::super::Tag;
+use super::ObjectIdentifier;
+
+/// A dynamically typed ASN.1 value, for data whose schema is not known
+/// in advance (the ANY type).
+///
+/// Obtained by `BERReader::read_any`. Universal types with a
+/// dedicated variant are decoded and checked; everything else is
+/// kept as [`Primitive`](ASN1Value::Primitive) contents or as a
+/// [`Constructed`](ASN1Value::Constructed) list of children.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// use yasna::models::ASN1Value;
+/// let data = &[48, 7, 2, 1, 10, 161, 2, 5, 0];
+/// let asn = yasna::parse_der(data, |reader| reader.read_any()).unwrap();
+/// match asn {
+///     ASN1Value::Sequence(ref children) => {
+///         assert_eq!(children[0], ASN1Value::Integer(vec![10]));
+///     },
+///     _ => panic!(),
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ASN1Value {
+    /// A BOOLEAN value.
+    Boolean(bool),
+    /// An INTEGER value as big-endian two's complement octets.
+    Integer(Vec<u8>),
+    /// A BIT STRING value as octets and length in bits.
+    BitString(Vec<u8>, usize),
+    /// An OCTET STRING value.
+    OctetString(Vec<u8>),
+    /// A NULL value.
+    Null,
+    /// An OBJECT IDENTIFIER value.
+    ObjectIdentifier(ObjectIdentifier),
+    /// A UTF8String value.
+    UTF8String(String),
+    /// A NumericString value.
+    NumericString(String),
+    /// A PrintableString value.
+    PrintableString(String),
+    /// An IA5String value.
+    IA5String(String),
+    /// A VisibleString value.
+    VisibleString(String),
+    /// A BMPString value.
+    BMPString(String),
+    /// A SEQUENCE or SEQUENCE OF value.
+    Sequence(Vec<ASN1Value>),
+    /// A SET or SET OF value.
+    Set(Vec<ASN1Value>),
+    /// Any other primitive value, with its tag and contents.
+    Primitive(Tag, Vec<u8>),
+    /// Any other constructed value, with its tag and children.
+    Constructed(Tag, Vec<ASN1Value>),
+}
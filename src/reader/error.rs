@@ -9,14 +9,67 @@
 #[cfg(feature = "std")]
 use std::{error::Error, io};
 use core::fmt::{self, Display};
+use super::super::{Tag,TagClass};
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+use alloc::boxed::Box;
+
+// Positions inside `ASN1ErrorContext` are stored with a sentinel rather
+// than as `Option<usize>`.
+const UNKNOWN : usize = core::usize::MAX;
+
+fn known(x: usize) -> Option<usize> {
+    if x == UNKNOWN { None } else { Some(x) }
+}
+
+/// An error raised while reading BER/DER-encoded data.
+///
+/// Errors compare equal if their [`kind`](Self::kind)s are equal; the
+/// position and the context are not compared.
+#[derive(Debug, Clone)]
 pub struct ASN1Error {
     kind: ASN1ErrorKind,
+    // Boxed so that `ASN1Result<T>` stays small; errors created outside
+    // the reader have no location.
+    location: Option<Box<ErrorLocation>>,
+}
+
+#[derive(Debug, Clone)]
+struct ErrorLocation {
+    offset: usize,
+    length: Option<usize>,
+    context: ASN1ErrorContext,
 }
+
+static EMPTY_CONTEXT : ASN1ErrorContext = ASN1ErrorContext {
+    frames: [EMPTY_FRAME; MAX_CONTEXT_DEPTH],
+    len: 0,
+    truncated: false,
+    outer_offset: UNKNOWN,
+};
+
+/// The category of an [`ASN1Error`].
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum ASN1ErrorKind {
-    Eof, Extra, IntegerOverflow, StackOverflow, Invalid,
+    /// The input ended in the middle of a value (truncated data).
+    Eof,
+    /// Data remained after the value was read.
+    Extra,
+    /// A number does not fit into the requested type.
+    IntegerOverflow,
+    /// The nesting depth limit was exceeded.
+    StackOverflow,
+    /// Any other malformed or non-canonical encoding.
+    Invalid,
+    /// An element had a different tag than expected.
+    UnexpectedTag,
+    /// Length octets were malformed, non-minimal (in DER),
+    /// indefinite where not allowed, or above the configured limit.
+    InvalidLength,
+    /// A character string contained a character outside its
+    /// character set.
+    CharsetViolation,
+    /// A declared length does not fit into `usize` on this target.
+    LengthOverflow,
 }
 
 pub type ASN1Result<T> = Result<T, ASN1Error>;
@@ -25,21 +78,209 @@ impl ASN1Error {
     pub fn new(kind: ASN1ErrorKind) -> Self {
         ASN1Error {
             kind,
+            location: None,
         }
     }
 
     pub fn kind(&self) -> ASN1ErrorKind {
         self.kind
     }
+
+    /// The absolute offset in the input of the innermost element
+    /// (or trailing data) where the error was detected, if known.
+    pub fn offset(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.offset)
+    }
+
+    /// The declared length (including tag and length octets) of
+    /// the offending element, if known.
+    pub fn length(&self) -> Option<usize> {
+        self.location.as_ref().and_then(|location| location.length)
+    }
+
+    /// The chain of elements enclosing the point of failure,
+    /// such as `SEQUENCE[0] → SET[2] → INTEGER`.
+    pub fn context(&self) -> &ASN1ErrorContext {
+        match self.location {
+            Some(ref location) => &location.context,
+            None => &EMPTY_CONTEXT,
+        }
+    }
+
+    fn location_mut(&mut self) -> &mut ErrorLocation {
+        return self.location.get_or_insert_with(|| Box::new(ErrorLocation {
+            offset: UNKNOWN,
+            length: None,
+            context: EMPTY_CONTEXT,
+        }));
+    }
+
+    pub(crate) fn push_context(mut self, tag: Tag, offset: usize,
+            index: Option<usize>) -> Self {
+        self.location_mut().context.push(tag, offset, index);
+        return self;
+    }
+
+    pub(crate) fn with_position(mut self, offset: usize,
+            length: Option<usize>) -> Self {
+        let location = self.location_mut();
+        location.offset = offset;
+        location.length = length;
+        return self;
+    }
 }
 
+impl PartialEq for ASN1Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
+impl Eq for ASN1Error {}
+
 impl Display for ASN1Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{:?}", self)?;
+        write!(f, "{:?}", self.kind)?;
+        if let Some(offset) = self.offset() {
+            write!(f, " at offset {}", offset)?;
+        }
+        if !self.context().is_empty() {
+            write!(f, " in {}", self.context())?;
+        }
         return Ok(());
     }
 }
 
+const MAX_CONTEXT_DEPTH : usize = 6;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct ContextFrame {
+    tag_class: TagClass,
+    // Both saturate at `u32::MAX`; an index of `u32::MAX` means none.
+    tag_number: u32,
+    index: u32,
+}
+
+const EMPTY_FRAME : ContextFrame = ContextFrame {
+    tag_class: TagClass::Universal,
+    tag_number: 0,
+    index: core::u32::MAX,
+};
+
+/// A breadcrumb trail of the elements enclosing the point where
+/// a reader error was detected.
+///
+/// The trail is collected while the error propagates outwards.
+/// Only the innermost few levels are kept; [`Display`] marks
+/// an omitted outer part with `…`. Tag numbers and indices are
+/// saturated at `u32::MAX`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ASN1ErrorContext {
+    // Innermost first.
+    frames: [ContextFrame; MAX_CONTEXT_DEPTH],
+    len: u8,
+    truncated: bool,
+    outer_offset: usize,
+}
+
+impl ASN1ErrorContext {
+    fn push(&mut self, tag: Tag, offset: usize, index: Option<usize>) {
+        if (self.len as usize) < MAX_CONTEXT_DEPTH {
+            self.frames[self.len as usize] = ContextFrame {
+                tag_class: tag.tag_class,
+                tag_number: tag.tag_number.min(core::u32::MAX as u64) as u32,
+                index: index.map_or(core::u32::MAX,
+                    |i| i.min(core::u32::MAX as usize - 1) as u32),
+            };
+            self.len += 1;
+        } else {
+            self.truncated = true;
+        }
+        self.outer_offset = offset;
+    }
+
+    /// Returns `true` if no enclosing element is known.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterates over the recorded elements from the outermost to
+    /// the innermost one. Each item is the element's tag and, unless it
+    /// is the innermost one, the index of the next item among its
+    /// children.
+    pub fn iter<'a>(&'a self)
+            -> impl Iterator<Item = (Tag, Option<usize>)> + 'a {
+        self.frames[..self.len as usize].iter().rev().map(|f| {
+            let tag = Tag {
+                tag_class: f.tag_class,
+                tag_number: f.tag_number as u64,
+            };
+            let index = if f.index == core::u32::MAX {
+                None
+            } else {
+                Some(f.index as usize)
+            };
+            (tag, index)
+        })
+    }
+
+    pub(crate) fn outer_offset(&self) -> Option<usize> {
+        known(self.outer_offset)
+    }
+}
+
+impl Display for ASN1ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.truncated {
+            write!(f, "…")?;
+        }
+        for (i, (tag, index)) in self.iter().enumerate() {
+            if i > 0 || self.truncated {
+                write!(f, " → ")?;
+            }
+            match (tag.tag_class, tag_name(tag)) {
+                (TagClass::Universal, Some(name)) => write!(f, "{}", name)?,
+                (TagClass::Universal, None) =>
+                    write!(f, "[UNIVERSAL {}]", tag.tag_number)?,
+                (TagClass::Application, _) =>
+                    write!(f, "[APPLICATION {}]", tag.tag_number)?,
+                (TagClass::ContextSpecific, _) =>
+                    write!(f, "[{}]", tag.tag_number)?,
+                (TagClass::Private, _) =>
+                    write!(f, "[PRIVATE {}]", tag.tag_number)?,
+            }
+            if let Some(index) = index {
+                write!(f, "[{}]", index)?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+fn tag_name(tag: Tag) -> Option<&'static str> {
+    const NAMES : [&str; 37] = [
+        "EOC", "BOOLEAN", "INTEGER", "BIT STRING", "OCTET STRING", "NULL",
+        "OBJECT IDENTIFIER", "ObjectDescriptor", "EXTERNAL", "REAL",
+        "ENUMERATED", "EMBEDDED PDV", "UTF8String", "RELATIVE-OID", "TIME",
+        "", "SEQUENCE", "SET", "NumericString", "PrintableString",
+        "TeletexString", "VideotexString", "IA5String", "UTCTime",
+        "GeneralizedTime", "GraphicString", "VisibleString",
+        "GeneralString", "UniversalString", "CHARACTER STRING",
+        "BMPString", "DATE", "TIME-OF-DAY", "DATE-TIME", "DURATION",
+        "OID-IRI", "RELATIVE-OID-IRI",
+    ];
+    // Compare as u64 first so large tag numbers aren't truncated
+    // on targets with a narrower usize.
+    if tag.tag_class != TagClass::Universal ||
+            tag.tag_number >= NAMES.len() as u64 {
+        return None;
+    }
+    match NAMES.get(tag.tag_number as usize) {
+        Some(&name) if !name.is_empty() => Some(name),
+        _ => None,
+    }
+}
+
 #[cfg(feature = "std")]
 impl Error for ASN1Error {
     fn description(&self) -> &str {
@@ -49,6 +290,10 @@ impl Error for ASN1Error {
             ASN1ErrorKind::IntegerOverflow => "Integer overflow",
             ASN1ErrorKind::StackOverflow => "Stack overflow",
             ASN1ErrorKind::Invalid => "Invalid data",
+            ASN1ErrorKind::UnexpectedTag => "Unexpected tag",
+            ASN1ErrorKind::InvalidLength => "Invalid length",
+            ASN1ErrorKind::CharsetViolation => "Character set violation",
+            ASN1ErrorKind::LengthOverflow => "Length overflow",
         }
     }
 }
@@ -0,0 +1,235 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{parse_der,BERMode,BERReader,BERReaderImpl,BERReaderOptions};
+use super::{ASN1ErrorKind,ASN1Result,BER_READER_STACK_DEPTH};
+use super::tlv::{Tlv,TlvIter};
+use super::super::{PCBit,Tag,TagClass};
+use super::super::tags::*;
+
+/// A problem found by [`lint_der`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LintFinding {
+    offset: usize,
+    kind: ASN1ErrorKind,
+    description: &'static str,
+}
+
+impl LintFinding {
+    fn new(offset: usize, kind: ASN1ErrorKind, description: &'static str)
+            -> Self {
+        return LintFinding {
+            offset,
+            kind,
+            description,
+        };
+    }
+
+    /// Returns the offset of the offending element (or octet, for
+    /// framing errors) in the input.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the category of the problem.
+    pub fn kind(&self) -> ASN1ErrorKind {
+        self.kind
+    }
+
+    /// Returns a short human-readable description of the problem.
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at offset {}", self.description, self.offset)
+    }
+}
+
+/// Checks BER-encoded data for DER conformance, collecting every
+/// problem found instead of stopping at the first one.
+///
+/// The data is walked structurally: violations of DER encoding rules
+/// (non-minimal lengths, indefinite lengths, constructed strings,
+/// non-canonical contents of common universal types, unsorted SETs)
+/// are reported and the walk goes on. A framing error ends the walk
+/// of the enclosing element only. Values inside explicitly or
+/// implicitly tagged elements are checked structurally only.
+///
+/// The contents of DATE, TIME-OF-DAY and DATE-TIME values are only
+/// checked when the `time` feature is enabled.
+///
+/// An empty result means the data is valid DER as far as these checks
+/// go.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// // A SEQUENCE with an indefinite length and a non-minimal INTEGER.
+/// let data = &[48, 128, 2, 2, 0, 10, 1, 1, 1, 0, 0];
+/// let findings = yasna::lint_der(data);
+/// let offsets = findings.iter().map(|f| f.offset()).collect::<Vec<_>>();
+/// assert_eq!(offsets, vec![0, 2, 6]);
+/// ```
+pub fn lint_der(buf: &[u8]) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let options = BERReaderOptions::new(BERMode::Ber)
+        .allow_non_minimal_integers(true);
+    let mut iter = TlvIter::with_options(buf, options);
+    match iter.next() {
+        Some(Ok(tlv)) => {
+            lint_element(&tlv, 0, &mut findings);
+            if tlv.raw().len() < buf.len() {
+                findings.push(LintFinding::new(tlv.raw().len(),
+                    ASN1ErrorKind::Extra, "trailing data"));
+            }
+        },
+        Some(Err(e)) => findings.push(framing_finding(e.kind(),
+            e.offset().unwrap_or(0))),
+        None => findings.push(LintFinding::new(0, ASN1ErrorKind::Eof,
+            "empty input")),
+    }
+    return findings;
+}
+
+fn framing_finding(kind: ASN1ErrorKind, offset: usize) -> LintFinding {
+    LintFinding::new(offset, kind, "malformed element")
+}
+
+fn lint_element(tlv: &Tlv, depth: usize, findings: &mut Vec<LintFinding>) {
+    let offset = tlv.offset();
+    let identifier_len = lint_header(tlv, findings);
+    let tag = tlv.tag();
+    if tlv.pcbit() == PCBit::Primitive {
+        if tag == TAG_SEQUENCE || tag == TAG_SET {
+            findings.push(LintFinding::new(offset, ASN1ErrorKind::Invalid,
+                "primitive SEQUENCE or SET"));
+        } else if tag.tag_class == TagClass::Universal {
+            if let Err(e) = lint_contents(tlv, identifier_len) {
+                findings.push(LintFinding::new(offset, e,
+                    "non-canonical contents"));
+            }
+        }
+        return;
+    }
+    if tag.tag_class == TagClass::Universal &&
+            tag != TAG_SEQUENCE && tag != TAG_SET &&
+            tag != TAG_EXT && tag != TAG_EMBEDDED_PDV {
+        findings.push(LintFinding::new(offset, ASN1ErrorKind::Invalid,
+            "constructed encoding of a primitive type"));
+    }
+    if depth >= BER_READER_STACK_DEPTH {
+        findings.push(LintFinding::new(offset, ASN1ErrorKind::StackOverflow,
+            "nesting too deep"));
+        return;
+    }
+    let mut prev: Option<Tlv> = None;
+    // A SET is sorted by tags (X.690 10.3), a SET OF by encodings
+    // (X.690 11.6). Since the type is unknown, either order will do.
+    let mut tag_order = true;
+    let mut encoding_order = true;
+    for child in tlv.children() {
+        let child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                findings.push(framing_finding(e.kind(),
+                    e.offset().unwrap_or(offset)));
+                break;
+            },
+        };
+        lint_element(&child, depth + 1, findings);
+        if tag == TAG_SET && (tag_order || encoding_order) {
+            if let Some(ref prev) = prev {
+                tag_order = tag_order && prev.tag() < child.tag();
+                encoding_order = encoding_order && prev.raw() <= child.raw();
+                if !tag_order && !encoding_order {
+                    findings.push(LintFinding::new(child.offset(),
+                        ASN1ErrorKind::Invalid, "unsorted SET element"));
+                }
+            }
+        }
+        prev = Some(child);
+    }
+}
+
+// Checks the length octets. Returns the length of the identifier
+// octets, which BER and DER constrain alike and TlvIter already checked.
+fn lint_header(tlv: &Tlv, findings: &mut Vec<LintFinding>) -> usize {
+    let offset = tlv.offset();
+    let mut probe = BERReaderImpl::new(tlv.raw(),
+        BERReaderOptions::new(BERMode::Der));
+    let _ = probe.read_identifier();
+    let identifier_len = probe.pos;
+    match probe.read_length() {
+        Ok(Some(_)) => {},
+        Ok(None) => findings.push(LintFinding::new(offset,
+            ASN1ErrorKind::InvalidLength, "indefinite length")),
+        Err(_) => findings.push(LintFinding::new(offset,
+            ASN1ErrorKind::InvalidLength, "non-minimal length")),
+    }
+    return identifier_len;
+}
+
+// Re-reads a primitive universal element in DER mode, with canonical
+// length octets so that only the contents are judged.
+fn lint_contents(tlv: &Tlv, identifier_len: usize)
+        -> Result<(), ASN1ErrorKind> {
+    let contents = tlv.contents();
+    let mut buf = Vec::with_capacity(identifier_len + contents.len() + 9);
+    buf.extend_from_slice(&tlv.raw()[..identifier_len]);
+    let len = contents.len();
+    if len < 128 {
+        buf.push(len as u8);
+    } else {
+        let mut octets = Vec::new();
+        let mut rest = len;
+        while rest > 0 {
+            octets.push(rest as u8);
+            rest >>= 8;
+        }
+        buf.push(128 | octets.len() as u8);
+        buf.extend(octets.iter().rev());
+    }
+    buf.extend_from_slice(contents);
+    let result = parse_der(&buf, |reader| check_universal(tlv.tag(), reader));
+    return result.map_err(|e| e.kind());
+}
+
+fn check_universal(tag: Tag, reader: BERReader) -> ASN1Result<()> {
+    match tag {
+        TAG_BOOLEAN => { reader.read_bool()?; },
+        TAG_INTEGER | TAG_ENUM => { reader.read_integer_bytes(tag)?; },
+        TAG_BITSTRING => { reader.read_bitvec_bytes()?; },
+        TAG_NULL => { reader.read_null()?; },
+        TAG_OID => { reader.read_oid()?; },
+        TAG_RELATIVE_OID => { reader.read_relative_oid()?; },
+        TAG_REAL => { reader.read_real()?; },
+        TAG_UTF8STRING => { reader.read_utf8string_ref()?; },
+        TAG_NUMERICSTRING => { reader.read_numeric_string_cow()?; },
+        TAG_PRINTABLESTRING => { reader.read_printable_string_cow()?; },
+        TAG_IA5STRING => { reader.read_ia5_string_cow()?; },
+        TAG_VISIBLESTRING => { reader.read_visible_string_cow()?; },
+        TAG_OBJECT_DESCRIPTOR => { reader.read_object_descriptor()?; },
+        TAG_BMPSTRING => { reader.read_bmp_string()?; },
+        TAG_UTCTIME => { reader.read_utctime_fields()?; },
+        TAG_GENERALIZEDTIME => { reader.read_generalized_time_fields()?; },
+        #[cfg(feature = "time")]
+        TAG_DATE => { reader.read_date()?; },
+        #[cfg(feature = "time")]
+        TAG_TIME_OF_DAY => { reader.read_time_of_day()?; },
+        #[cfg(feature = "time")]
+        TAG_DATE_TIME => { reader.read_date_time()?; },
+        TAG_DURATION => { reader.read_duration()?; },
+        _ => { reader.skip_value()?; },
+    }
+    return Ok(());
+}
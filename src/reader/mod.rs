@@ -11,37 +11,94 @@
 
 use alloc::vec::Vec;
 use alloc::string::String;
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow,ToOwned};
 
 mod error;
+#[cfg(feature = "std")]
+mod stream;
+mod push;
+mod tlv;
+mod lint;
 
 #[cfg(feature = "num-bigint")]
 use num_bigint::{BigInt,BigUint,Sign};
+#[cfg(feature = "num-bigint")]
+use super::models::BigObjectIdentifier;
 #[cfg(feature = "bit-vec")]
 use bit_vec::BitVec;
+#[cfg(feature = "bitvec")]
+use bitvec::order::Msb0;
 
 use super::{PCBit,Tag,TAG_CLASSES};
-use super::tags::{TAG_EOC,TAG_BOOLEAN,TAG_INTEGER,TAG_OCTETSTRING};
+use super::tags::{TAG_EOC,TAG_BOOLEAN,TAG_INTEGER,TAG_BITSTRING,TAG_OCTETSTRING};
 use super::tags::{TAG_NULL,TAG_OID,TAG_UTF8STRING,TAG_SEQUENCE,TAG_SET,TAG_ENUM};
+use super::tags::TAG_REAL;
 use super::tags::{TAG_NUMERICSTRING,TAG_PRINTABLESTRING,TAG_VISIBLESTRING,TAG_IA5STRING,TAG_BMPSTRING};
-use super::models::{ObjectIdentifier,TaggedDerValue};
+use super::models::{ObjectIdentifier,RelativeOid,TaggedDerValue,ASN1Value};
+use super::models::{BitString,EmbeddedPdv,PdvIdentification};
 #[cfg(feature = "time")]
-use super::models::{UTCTime,GeneralizedTime};
+use super::models::{UTCTime,GeneralizedTime,Date,TimeOfDay,DateTime};
+use super::models::Duration;
+use super::models::{UTCTimeFields,GeneralizedTimeFields};
 pub use self::error::*;
+#[cfg(feature = "std")]
+pub use self::stream::BERStreamReader;
+pub use self::push::BERPushParser;
+pub use self::tlv::{TlvIter,Tlv,ElementHeader};
+pub use self::tlv::{der_query,ber_query,QueryStep};
+pub use self::lint::{lint_der,LintFinding};
 
 /// Parses DER/BER-encoded data.
 ///
 /// [`parse_ber`] and [`parse_der`] are shorthands
 /// for this function.
+///
+/// Declared lengths are checked against the remaining input before
+/// the contents are touched, so a short header claiming a huge length
+/// fails immediately and no allocation grows beyond the size of `buf`.
+///
+/// Any contiguous input that derefs to `[u8]` (`Vec<u8>`, memory-mapped
+/// files, `bytes::Bytes`, ...) can be passed without copying; for other
+/// [`AsRef<[u8]>`](AsRef) types, see [`parse_ber_with_options`]. To read
+/// from an [`io::Read`](std::io::Read), possibly seeking over large
+/// values, see `BERStreamReader` (requires the `std` feature).
 pub fn parse_ber_general<'a, T, F>(buf: &'a [u8], mode: BERMode, callback: F)
         -> ASN1Result<T>
         where F: for<'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T> {
-    let mut reader_impl = BERReaderImpl::new(buf, mode);
+    parse_ber_with_options(buf, BERReaderOptions::new(mode), callback)
+}
+
+/// Parses DER/BER-encoded data with fine-grained [`BERReaderOptions`].
+///
+/// The input can be anything that is [`AsRef<[u8]>`](AsRef), which the
+/// values read may borrow from.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{self,BERMode,BERReaderOptions};
+/// let data : &[u8] = &[2, 2, 0, 10, 5, 0];
+/// let options = BERReaderOptions::new(BERMode::Der)
+///     .allow_non_minimal_integers(true)
+///     .allow_trailing_data(true);
+/// let asn = yasna::parse_ber_with_options(data, options, |reader| {
+///     reader.read_i64()
+/// }).unwrap();
+/// assert_eq!(asn, 10);
+/// ```
+pub fn parse_ber_with_options<'a, B, T, F>(buf: &'a B,
+        options: BERReaderOptions, callback: F)
+        -> ASN1Result<T>
+        where B: AsRef<[u8]> + ?Sized,
+              F: for<'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T> {
+    let mut reader_impl = BERReaderImpl::new(buf.as_ref(), options);
     let result;
     {
         result = callback(BERReader::new(&mut reader_impl))?;
     }
-    reader_impl.end_of_buf()?;
+    if !options.allow_trailing_data {
+        reader_impl.end_of_buf()?;
+    }
     return Ok(result);
 }
 
@@ -100,6 +157,173 @@ pub fn parse_der<'a, T, F>(buf: &'a [u8], callback: F)
     parse_ber_general(buf, BERMode::Der, callback)
 }
 
+/// Parses BER-encoded data at the beginning of `buf`.
+///
+/// Unlike [`parse_ber`], data following the value is not an error.
+/// Returns the result together with the number of bytes consumed.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let data = &[2, 1, 10, 5, 0];
+/// let (asn, len) = yasna::parse_ber_partial(data, |reader| {
+///     reader.read_i64()
+/// }).unwrap();
+/// assert_eq!((asn, len), (10, 3));
+/// ```
+pub fn parse_ber_partial<'a, T, F>(buf: &'a [u8], callback: F)
+        -> ASN1Result<(T, usize)>
+        where F: for<'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T> {
+    parse_partial_general(buf, BERReaderOptions::new(BERMode::Ber), callback)
+}
+
+/// Parses DER-encoded data at the beginning of `buf`.
+///
+/// Unlike [`parse_der`], data following the value is not an error.
+/// Returns the result together with the number of bytes consumed.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let data = &[2, 1, 10, 5, 0];
+/// let (asn, len) = yasna::parse_der_partial(data, |reader| {
+///     reader.read_i64()
+/// }).unwrap();
+/// assert_eq!((asn, len), (10, 3));
+/// let (asn, len) = yasna::parse_der_partial(&data[len..], |reader| {
+///     reader.read_null()
+/// }).unwrap();
+/// assert_eq!((asn, len), ((), 2));
+/// ```
+pub fn parse_der_partial<'a, T, F>(buf: &'a [u8], callback: F)
+        -> ASN1Result<(T, usize)>
+        where F: for<'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T> {
+    parse_partial_general(buf, BERReaderOptions::new(BERMode::Der), callback)
+}
+
+/// Parses a sequence of back-to-back BER-encoded values, such as
+/// a bundle of concatenated certificates.
+///
+/// `callback` is called once per top-level value until
+/// the input is exhausted. Empty input yields an empty vector.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let data = &[2, 1, 10, 2, 1, 20, 2, 1, 30];
+/// let asn = yasna::parse_ber_multi(data, |reader| {
+///     reader.read_i64()
+/// }).unwrap();
+/// assert_eq!(asn, vec![10, 20, 30]);
+/// ```
+pub fn parse_ber_multi<'a, T, F>(buf: &'a [u8], callback: F)
+        -> ASN1Result<Vec<T>>
+        where F: for<'b> FnMut(BERReader<'a, 'b>) -> ASN1Result<T> {
+    parse_multi_general(buf, BERMode::Ber, callback)
+}
+
+/// Parses a sequence of back-to-back DER-encoded values, such as
+/// a bundle of concatenated certificates.
+///
+/// `callback` is called once per top-level value until
+/// the input is exhausted. Empty input yields an empty vector.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let data = &[4, 1, 1, 4, 2, 2, 3];
+/// let asn = yasna::parse_der_multi(data, |reader| {
+///     reader.read_bytes()
+/// }).unwrap();
+/// assert_eq!(asn, vec![vec![1], vec![2, 3]]);
+/// ```
+pub fn parse_der_multi<'a, T, F>(buf: &'a [u8], callback: F)
+        -> ASN1Result<Vec<T>>
+        where F: for<'b> FnMut(BERReader<'a, 'b>) -> ASN1Result<T> {
+    parse_multi_general(buf, BERMode::Der, callback)
+}
+
+fn parse_multi_general<'a, T, F>(buf: &'a [u8], mode: BERMode,
+        mut callback: F) -> ASN1Result<Vec<T>>
+        where F: for<'b> FnMut(BERReader<'a, 'b>) -> ASN1Result<T> {
+    let mut reader_impl = BERReaderImpl::new(buf, BERReaderOptions::new(mode));
+    let mut ret = Vec::new();
+    while reader_impl.pos < buf.len() {
+        let old_pos = reader_impl.pos;
+        ret.push(callback(BERReader::new(&mut reader_impl))?);
+        if reader_impl.pos == old_pos {
+            // Nothing was read; bail out instead of looping forever.
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+        }
+    }
+    return Ok(ret);
+}
+
+/// Splits back-to-back BER/DER-encoded values into raw slices.
+///
+/// Each item is a complete top-level value including its tag and
+/// length. After an error is returned, the iteration stops.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{self,BERMode};
+/// let data = &[2, 1, 10, 5, 0];
+/// let docs = yasna::split_ber(data, BERMode::Der)
+///     .collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(docs, vec![&[2, 1, 10][..], &[5, 0][..]]);
+/// ```
+pub fn split_ber<'a>(buf: &'a [u8], mode: BERMode) -> BERDocuments<'a> {
+    BERDocuments {
+        inner: BERReaderImpl::new(buf, BERReaderOptions::new(mode)),
+        failed: false,
+    }
+}
+
+/// An iterator over back-to-back BER/DER-encoded values,
+/// created by [`split_ber`].
+#[derive(Debug)]
+pub struct BERDocuments<'a> {
+    inner: BERReaderImpl<'a>,
+    failed: bool,
+}
+
+impl<'a> Iterator for BERDocuments<'a> {
+    type Item = ASN1Result<&'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.inner.pos >= self.inner.buf.len() {
+            return None;
+        }
+        let result = self.inner.read_with_buffer(|inner| {
+            inner.skip_general()
+        });
+        match result {
+            Ok((_, buf)) => Some(Ok(buf)),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            },
+        }
+    }
+}
+
+fn parse_partial_general<'a, T, F>(buf: &'a [u8],
+        options: BERReaderOptions, callback: F)
+        -> ASN1Result<(T, usize)>
+        where F: for<'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T> {
+    let mut reader_impl = BERReaderImpl::new(buf, options);
+    let result;
+    {
+        result = callback(BERReader::new(&mut reader_impl))?;
+    }
+    return Ok((result, reader_impl.pos));
+}
+
 /// Used by [`BERReader`] to determine whether or not to enforce
 /// DER restrictions when parsing.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -110,12 +334,126 @@ pub enum BERMode {
     Der,
 }
 
+/// Options for [`parse_ber_with_options`].
+///
+/// The defaults are derived from a [`BERMode`]; individual restrictions
+/// can then be relaxed or tightened with the builder methods. Rules not
+/// covered by an option (e.g. canonical BOOLEAN or SET ordering)
+/// follow the mode.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{BERMode,BERReaderOptions};
+/// let options = BERReaderOptions::new(BERMode::Der)
+///     .allow_indefinite_length(true)
+///     .max_length(1 << 20);
+/// assert_eq!(options.mode(), BERMode::Der);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct BERReaderOptions {
+    mode: BERMode,
+    allow_indefinite_length: bool,
+    allow_non_minimal_integers: bool,
+    allow_constructed_strings: bool,
+    allow_trailing_data: bool,
+    check_charsets: bool,
+    max_length: Option<usize>,
+    max_total_length: Option<usize>,
+}
+
+impl BERReaderOptions {
+    /// Constructs the default options for the given mode.
+    pub fn new(mode: BERMode) -> Self {
+        let ber = mode == BERMode::Ber;
+        return BERReaderOptions {
+            mode,
+            allow_indefinite_length: ber,
+            allow_non_minimal_integers: false,
+            allow_constructed_strings: ber,
+            allow_trailing_data: false,
+            check_charsets: true,
+            max_length: None,
+            max_total_length: None,
+        };
+    }
+
+    /// Tells which format is parsed, BER or DER.
+    pub fn mode(&self) -> BERMode {
+        self.mode
+    }
+
+    /// Accepts (or rejects) indefinite length encoding.
+    pub fn allow_indefinite_length(mut self, allow: bool) -> Self {
+        self.allow_indefinite_length = allow;
+        return self;
+    }
+
+    /// Accepts (or rejects) INTEGER/ENUMERATED values with redundant
+    /// leading `0x00` or `0xFF` octets.
+    pub fn allow_non_minimal_integers(mut self, allow: bool) -> Self {
+        self.allow_non_minimal_integers = allow;
+        return self;
+    }
+
+    /// Accepts (or rejects) constructed encoding of
+    /// BIT STRING, OCTET STRING and character strings.
+    pub fn allow_constructed_strings(mut self, allow: bool) -> Self {
+        self.allow_constructed_strings = allow;
+        return self;
+    }
+
+    /// Accepts (or rejects) data after the top-level value.
+    pub fn allow_trailing_data(mut self, allow: bool) -> Self {
+        self.allow_trailing_data = allow;
+        return self;
+    }
+
+    /// Enables (or disables) character set checks of NumericString,
+    /// PrintableString, IA5String and VisibleString. Contents must
+    /// still be valid UTF-8 to be returned as `String`.
+    pub fn check_charsets(mut self, check: bool) -> Self {
+        self.check_charsets = check;
+        return self;
+    }
+
+    /// Rejects elements whose declared length exceeds `max_length`.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        return self;
+    }
+
+    /// Rejects top-level values longer than `max_total_length` in total,
+    /// including tag and length octets.
+    ///
+    /// Unlike [`max_length`](Self::max_length), this also bounds
+    /// indefinite-length values made of many small elements. It limits
+    /// what [`BERStreamReader`] and [`BERPushParser`] buffer, and the
+    /// memory the reader allocates, which is proportional to the input.
+    /// A declared length beyond the limit fails before anything else is
+    /// read.
+    pub fn max_total_length(mut self, max_total_length: usize) -> Self {
+        self.max_total_length = Some(max_total_length);
+        return self;
+    }
+}
+
+impl From<BERMode> for BERReaderOptions {
+    fn from(mode: BERMode) -> Self {
+        BERReaderOptions::new(mode)
+    }
+}
+
 #[derive(Debug)]
 struct BERReaderImpl<'a> {
     buf: &'a [u8],
     pos: usize,
-    mode: BERMode,
+    options: BERReaderOptions,
     depth: usize,
+    // The start of the innermost `read_optional` attempt. An error that
+    // leaves the position there only means the element is absent, so it
+    // isn't worth locating.
+    optional_pos: Option<usize>,
 }
 
 const PC_BITS : [PCBit; 2] = [PCBit::Primitive, PCBit::Constructed];
@@ -129,21 +467,24 @@ enum Contents<'a, 'b> where 'a: 'b {
 const BER_READER_STACK_DEPTH : usize = 100;
 
 impl<'a> BERReaderImpl<'a> {
-    fn new(buf: &'a [u8], mode: BERMode) -> Self {
+    fn new(buf: &'a [u8], options: BERReaderOptions) -> Self {
         return BERReaderImpl {
             buf,
             pos: 0,
-            mode,
+            options,
             depth: 0,
+            optional_pos: None,
         };
     }
 
-    fn with_pos(buf: &'a [u8], pos: usize, mode: BERMode) -> Self {
+    fn with_pos(buf: &'a [u8], pos: usize, options: BERReaderOptions)
+            -> Self {
         return BERReaderImpl {
             buf,
             pos,
-            mode,
+            options,
             depth: 0,
+            optional_pos: None,
         };
     }
 
@@ -159,7 +500,8 @@ impl<'a> BERReaderImpl<'a> {
 
     fn end_of_buf(&mut self) -> ASN1Result<()> {
         if self.pos != self.buf.len() {
-            return Err(ASN1Error::new(ASN1ErrorKind::Extra));
+            return Err(ASN1Error::new(ASN1ErrorKind::Extra).with_position(
+                self.pos, Some(self.buf.len() - self.pos)));
         }
         return Ok(());
     }
@@ -185,6 +527,11 @@ impl<'a> BERReaderImpl<'a> {
             tag_number = 0;
             loop {
                 let b = self.read_u8()? as u64;
+                if tag_number == 0 && b == 128 {
+                    // Leading zero bits are forbidden even in BER
+                    // (X.690 8.1.2.4.2 c).
+                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                }
                 let x =
                     tag_number.checked_mul(128).ok_or(
                         ASN1Error::new(ASN1ErrorKind::IntegerOverflow))?;
@@ -205,67 +552,84 @@ impl<'a> BERReaderImpl<'a> {
     }
 
     fn lookahead_tag(&self) -> ASN1Result<Tag> {
-        let mut pos = self.pos;
-        let mut read_u8 = || {
-            if pos < self.buf.len() {
-                let ret = self.buf[pos];
-                pos += 1;
-                return Ok(ret);
-            } else {
-                return Err(ASN1Error::new(ASN1ErrorKind::Eof));
-            }
-        };
-        let tagbyte = read_u8()?;
-        let tag_class = TAG_CLASSES[(tagbyte >> 6) as usize];
-        let mut tag_number = (tagbyte & 31) as u64;
-        if tag_number == 31 {
-            tag_number = 0;
-            loop {
-                let b = read_u8()? as u64;
-                let x =
-                    tag_number.checked_mul(128).ok_or(
-                        ASN1Error::new(ASN1ErrorKind::IntegerOverflow))?;
-                tag_number = x + (b & 127);
-                if (b & 128) == 0 {
-                    break;
-                }
-            }
-            if tag_number < 31 {
-                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-            }
-        }
-        let tag = Tag {
-            tag_class,
-            tag_number,
-        };
+        let mut probe = BERReaderImpl::with_pos(self.buf, self.pos,
+            self.options);
+        let (tag, _) = probe.read_identifier()?;
         return Ok(tag);
     }
 
+    fn read_header(&mut self) -> ASN1Result<ElementHeader> {
+        let offset = self.pos;
+        let (tag, pcbit) = self.read_identifier()?;
+        let length = self.read_length()?;
+        return Ok(ElementHeader {
+            tag,
+            pcbit,
+            offset,
+            header_len: self.pos - offset,
+            length,
+        });
+    }
+
     fn read_length(&mut self) -> ASN1Result<Option<usize>> {
         let lbyte = self.read_u8()? as usize;
         if lbyte == 128 {
             return Ok(None);
         }
         if lbyte == 255 {
-            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
         }
         if (lbyte & 128) == 0 {
-            return Ok(Some(lbyte));
+            return self.check_max_length(lbyte);
         }
         let mut length : usize = 0;
         for _ in 0..(lbyte & 127) {
             let x = length.checked_mul(256).ok_or(
-                ASN1Error::new(ASN1ErrorKind::Eof))?;
-            length = x + (self.read_u8()? as usize);
+                ASN1Error::new(ASN1ErrorKind::LengthOverflow))?;
+            let b = self.read_u8()? as usize;
+            if self.options.mode == BERMode::Der && x == 0 && b == 0 {
+                // Leading zero octets make the length non-minimal.
+                return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
+            }
+            length = x + b;
         }
-        if self.mode == BERMode::Der && length < 128 {
-            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+        if self.options.mode == BERMode::Der && length < 128 {
+            return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
+        }
+        return self.check_max_length(length);
+    }
+
+    /// Checks the extent of a top-level value starting at `start`, which
+    /// may be only partially known.
+    fn check_total_length(&self, start: usize, end: usize) -> ASN1Result<()> {
+        if let Some(max_total_length) = self.options.max_total_length {
+            if end - start > max_total_length {
+                return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
+            }
+        }
+        return Ok(());
+    }
+
+    fn check_max_length(&self, length: usize) -> ASN1Result<Option<usize>> {
+        if let Some(max_length) = self.options.max_length {
+            if length > max_length {
+                return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
+            }
         }
         return Ok(Some(length));
     }
 
     fn read_general<T, F>(&mut self, tag: Tag, callback: F) -> ASN1Result<T>
             where F: for<'b> FnOnce(Contents<'a, 'b>) -> ASN1Result<T> {
+        let old_buf = self.buf;
+        let old_pos = self.pos;
+        return self.read_general_impl(tag, callback)
+            .map_err(|e| self.locate_error(e, old_buf, old_pos));
+    }
+
+    fn read_general_impl<T, F>(&mut self, tag: Tag, callback: F)
+            -> ASN1Result<T>
+            where F: for<'b> FnOnce(Contents<'a, 'b>) -> ASN1Result<T> {
         if self.depth > BER_READER_STACK_DEPTH {
             return Err(ASN1Error::new(ASN1ErrorKind::StackOverflow));
         }
@@ -273,16 +637,20 @@ impl<'a> BERReaderImpl<'a> {
         let (tag2, pcbit) = self.read_identifier()?;
         if tag2 != tag {
             self.pos = old_pos;
-            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            return Err(ASN1Error::new(ASN1ErrorKind::UnexpectedTag));
         }
         let length_spec = self.read_length()?;
         let old_buf = self.buf;
+        let top_level = self.depth == 0;
         match length_spec {
             Some(length) => {
                 let limit = match self.pos.checked_add(length) {
                     Some(l) => l,
-                    None => return Err(ASN1Error::new(ASN1ErrorKind::IntegerOverflow)),
+                    None => return Err(ASN1Error::new(ASN1ErrorKind::LengthOverflow)),
                 };
+                if top_level {
+                    self.check_total_length(old_pos, limit)?;
+                }
 
                 if old_buf.len() < limit {
                     return Err(ASN1Error::new(ASN1ErrorKind::Eof));
@@ -290,11 +658,9 @@ impl<'a> BERReaderImpl<'a> {
                 self.buf = &old_buf[..limit];
             },
             None => {
-                if pcbit != PCBit::Constructed {
-                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-                }
-                if self.mode == BERMode::Der {
-                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                if pcbit != PCBit::Constructed ||
+                        !self.options.allow_indefinite_length {
+                    return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
                 }
             },
         };
@@ -314,13 +680,62 @@ impl<'a> BERReaderImpl<'a> {
             },
             None => {
                 self.end_of_contents()?;
+                if top_level {
+                    self.check_total_length(old_pos, self.pos)?;
+                }
             },
         };
         self.buf = old_buf;
         return Ok(result);
     }
 
+    /// Attaches the position of the element starting at `pos` to `e`,
+    /// unless a more specific (inner) position is already known.
+    fn locate_error(&self, e: ASN1Error, buf: &'a [u8], pos: usize)
+            -> ASN1Error {
+        if self.optional_pos == Some(pos) && self.pos == pos {
+            return e;
+        }
+        let mut probe = BERReaderImpl::with_pos(buf, pos, self.options);
+        let (tag, _) = match probe.read_identifier() {
+            Ok(identifier) => identifier,
+            Err(_) => return e.with_position(pos, None),
+        };
+        let length = match probe.read_length() {
+            Ok(Some(length)) => (probe.pos - pos).checked_add(length),
+            _ => None,
+        };
+        let data_pos = probe.pos;
+        let e = if e.offset().is_some() {
+            e
+        } else {
+            e.with_position(pos, length)
+        };
+        // Locate the failing child among the contents of this element.
+        let index = e.context().outer_offset().and_then(|child_pos| {
+            let mut index = 0;
+            while probe.pos < child_pos {
+                probe.skip_general_impl().ok()?;
+                index += 1;
+            }
+            if probe.pos == child_pos && data_pos <= child_pos {
+                Some(index)
+            } else {
+                None
+            }
+        });
+        return e.push_context(tag, pos, index);
+    }
+
     fn skip_general(&mut self) -> ASN1Result<(Tag, PCBit, usize)> {
+        let old_buf = self.buf;
+        let old_pos = self.pos;
+        return self.skip_general_impl()
+            .map_err(|e| self.locate_error(e, old_buf, old_pos));
+    }
+
+    fn skip_general_impl(&mut self) -> ASN1Result<(Tag, PCBit, usize)> {
+        let start = self.pos;
         let mut skip_depth = 0;
         let mut skip_tag = None;
         let mut data_pos = None;
@@ -337,6 +752,7 @@ impl<'a> BERReaderImpl<'a> {
                 if self.read_u8()? != 0 {
                     return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
                 }
+                self.check_total_length(start, self.pos)?;
                 continue;
             }
             if skip_depth == 0 {
@@ -346,7 +762,11 @@ impl<'a> BERReaderImpl<'a> {
                 if skip_depth == 0 {
                     data_pos = Some(self.pos);
                 }
-                let limit = self.pos+length;
+                let limit = match self.pos.checked_add(length) {
+                    Some(l) => l,
+                    None => return Err(ASN1Error::new(ASN1ErrorKind::LengthOverflow)),
+                };
+                self.check_total_length(start, limit)?;
                 if self.buf.len() < limit {
                     return Err(ASN1Error::new(ASN1ErrorKind::Eof));
                 }
@@ -355,8 +775,9 @@ impl<'a> BERReaderImpl<'a> {
                 if skip_depth == 0 {
                     data_pos = Some(self.pos);
                 }
-                if pcbit != PCBit::Constructed || self.mode == BERMode::Der {
-                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                if pcbit != PCBit::Constructed ||
+                        !self.options.allow_indefinite_length {
+                    return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
                 }
                 skip_depth += 1;
             }
@@ -374,10 +795,28 @@ impl<'a> BERReaderImpl<'a> {
         return Ok((result, buf));
     }
 
+    fn try_read<T, F>(&mut self, callback: F) -> ASN1Result<T>
+            where F: FnOnce(&mut Self) -> ASN1Result<T> {
+        let old_buf = self.buf;
+        let old_pos = self.pos;
+        let old_depth = self.depth;
+        let result = callback(self);
+        if result.is_err() {
+            self.buf = old_buf;
+            self.pos = old_pos;
+            self.depth = old_depth;
+        }
+        return result;
+    }
+
     fn read_optional<T, F>(&mut self, callback: F) -> ASN1Result<Option<T>>
             where F: FnOnce(&mut Self) -> ASN1Result<T> {
         let old_pos = self.pos;
-        match callback(self) {
+        let old_optional_pos = self.optional_pos;
+        self.optional_pos = Some(old_pos);
+        let result = callback(self);
+        self.optional_pos = old_optional_pos;
+        match result {
             Ok(result) => Ok(Some(result)),
             Err(e) =>
                 if old_pos == self.pos {
@@ -430,7 +869,12 @@ impl<'a, 'b> BERReader<'a, 'b> {
 
     /// Tells which format we are parsing, BER or DER.
     pub fn mode(&self) -> BERMode {
-        self.inner.mode
+        self.inner.options.mode
+    }
+
+    /// Returns the options the data is parsed with.
+    pub fn options(&self) -> BERReaderOptions {
+        self.inner.options
     }
 
     /// Reads an ASN.1 BOOLEAN value as `bool`.
@@ -465,7 +909,10 @@ impl<'a, 'b> BERReader<'a, 'b> {
         })
     }
 
-    fn read_integer(self, tag: Tag) -> ASN1Result<i64> {
+    // Reads the contents of an INTEGER-like value, checking that they
+    // are well-formed but not their magnitude.
+    fn read_integer_bytes(self, tag: Tag) -> ASN1Result<&'a [u8]> {
+        let lenient = self.inner.options.allow_non_minimal_integers;
         self.read_general(tag, |contents| {
             let buf = match contents {
                 Contents::Primitive(buf) => buf,
@@ -473,26 +920,24 @@ impl<'a, 'b> BERReader<'a, 'b> {
                     return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
                 },
             };
-            if buf.len() == 0 {
-                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-            } else if buf.len() == 1 {
-                return Ok(buf[0] as i8 as i64);
-            }
-            let mut x = ((buf[0] as i8 as i64) << 8) + (buf[1] as i64);
-            if -128 <= x && x < 128 {
+            let buf = if lenient { strip_integer_padding(buf) } else { buf };
+            if buf.is_empty() {
                 return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            } else if buf.len() >= 2 {
+                let x2 = ((buf[0] as i8 as i32) << 8) + (buf[1] as i32);
+                if (-128..128).contains(&x2) {
+                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                }
             }
-            if buf.len() > 8 {
-                return Err(ASN1Error::new(
-                    ASN1ErrorKind::IntegerOverflow));
-            }
-            for &b in buf[2..].iter() {
-                x = (x << 8) | (b as i64);
-            }
-            return Ok(x);
+            return Ok(buf);
         })
     }
 
+    fn read_integer(self, tag: Tag) -> ASN1Result<i64> {
+        let buf = self.read_integer_bytes(tag)?;
+        return Ok(fold_integer_bytes(buf, true, 8)? as i64);
+    }
+
     /// Reads an ASN.1 ENUMERATED value as `i64`.
     ///
     /// # Examples
@@ -533,42 +978,72 @@ impl<'a, 'b> BERReader<'a, 'b> {
         self.read_integer(TAG_INTEGER)
     }
 
-    /// Reads an ASN.1 INTEGER value as `u64`.
+    /// Reads an ASN.1 ENUMERATED value as `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[10, 9, 0, 255, 255, 255, 255, 255, 255, 255, 255];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_enum_u64()
+    /// }).unwrap();
+    /// assert_eq!(asn, u64::max_value());
+    /// ```
     ///
     /// # Errors
     ///
     /// Except parse errors, it can raise integer overflow errors.
-    pub fn read_u64(self) -> ASN1Result<u64> {
-        self.read_general(TAG_INTEGER, |contents| {
-            let buf = match contents {
-                Contents::Primitive(buf) => buf,
-                Contents::Constructed(_) => {
-                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-                },
-            };
-            if buf.len() == 0 {
-                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-            } else if buf[0] >= 128 {
-                return Err(ASN1Error::new(ASN1ErrorKind::IntegerOverflow));
-            } else if buf.len() == 1 {
-                return Ok(buf[0] as u64);
-            }
-            let mut x = ((buf[0] as u64) << 8) + (buf[1] as u64);
-            if x < 128 {
-                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-            }
-            if buf.len() > 9 || (buf.len() == 9 && buf[0] != 0) {
-                return Err(ASN1Error::new(
-                    ASN1ErrorKind::IntegerOverflow));
-            }
-            for &b in buf[2..].iter() {
-                x = (x << 8) | (b as u64);
-            }
-            return Ok(x);
-        })
+    pub fn read_enum_u64(self) -> ASN1Result<u64> {
+        self.read_uinteger(TAG_ENUM)
     }
 
-    /// Reads an ASN.1 INTEGER value as `i32`.
+    /// Reads an ASN.1 INTEGER value as `u64`.
+    ///
+    /// # Errors
+    ///
+    /// Except parse errors, it can raise integer overflow errors.
+    pub fn read_u64(self) -> ASN1Result<u64> {
+        self.read_uinteger(TAG_INTEGER)
+    }
+
+    fn read_uinteger(self, tag: Tag) -> ASN1Result<u64> {
+        let buf = self.read_integer_bytes(tag)?;
+        return Ok(fold_integer_bytes(buf, false, 8)? as u64);
+    }
+
+    /// Reads an ASN.1 INTEGER value as `i128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[2, 9, 255, 0, 0, 0, 0, 0, 0, 0, 0];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_i128()
+    /// }).unwrap();
+    /// assert_eq!(asn, -18446744073709551616);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Except parse errors, it can raise integer overflow errors.
+    pub fn read_i128(self) -> ASN1Result<i128> {
+        let buf = self.read_integer_bytes(TAG_INTEGER)?;
+        return Ok(fold_integer_bytes(buf, true, 16)? as i128);
+    }
+
+    /// Reads an ASN.1 INTEGER value as `u128`.
+    ///
+    /// # Errors
+    ///
+    /// Except parse errors, it can raise integer overflow errors.
+    pub fn read_u128(self) -> ASN1Result<u128> {
+        let buf = self.read_integer_bytes(TAG_INTEGER)?;
+        return fold_integer_bytes(buf, false, 16);
+    }
+
+    /// Reads an ASN.1 INTEGER value as `i32`.
     ///
     /// # Errors
     ///
@@ -652,6 +1127,25 @@ impl<'a, 'b> BERReader<'a, 'b> {
         }
     }
 
+    /// Reads an ASN.1 INTEGER value as big-endian two's complement bytes.
+    ///
+    /// The returned bytes are the minimal encoding of the integer, as
+    /// required in DER.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[2, 3, 0, 128, 1];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_int_bytes()
+    /// }).unwrap();
+    /// assert_eq!(&asn, &[0, 128, 1]);
+    /// ```
+    pub fn read_int_bytes(self) -> ASN1Result<Vec<u8>> {
+        return Ok(self.read_integer_bytes(TAG_INTEGER)?.to_vec());
+    }
+
     #[cfg(feature = "num-bigint")]
     /// Reads an ASN.1 INTEGER value as `BigInt`.
     ///
@@ -678,6 +1172,7 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// yasna = { version = "*", features = ["num"] }
     /// ```
     pub fn read_bigint(self) -> ASN1Result<BigInt> {
+        let lenient = self.inner.options.allow_non_minimal_integers;
         self.read_general(TAG_INTEGER, |contents| {
             let buf = match contents {
                 Contents::Primitive(buf) => buf,
@@ -685,6 +1180,7 @@ impl<'a, 'b> BERReader<'a, 'b> {
                     return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
                 },
             };
+            let buf = if lenient { strip_integer_padding(buf) } else { buf };
             if buf.len() == 0 {
                 return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
             } else if buf.len() == 1 {
@@ -726,6 +1222,7 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// yasna = { version = "*", features = ["num"] }
     /// ```
     pub fn read_biguint(self) -> ASN1Result<BigUint> {
+        let lenient = self.inner.options.allow_non_minimal_integers;
         self.read_general(TAG_INTEGER, |contents| {
             let buf = match contents {
                 Contents::Primitive(buf) => buf,
@@ -733,6 +1230,7 @@ impl<'a, 'b> BERReader<'a, 'b> {
                     return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
                 },
             };
+            let buf = if lenient { strip_integer_padding(buf) } else { buf };
             if buf.len() == 0 {
                 return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
             } else if buf[0] >= 128 {
@@ -752,11 +1250,11 @@ impl<'a, 'b> BERReader<'a, 'b> {
 
     fn read_bitvec_impl(self, unused_bits: &mut usize, bytes: &mut Vec<u8>)
             -> ASN1Result<()> {
-        use super::tags::TAG_BITSTRING;
         if *unused_bits != 0 {
             return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
         }
-        let mode = self.inner.mode;
+        let mode = self.inner.options.mode;
+        let allow_constructed = self.inner.options.allow_constructed_strings;
         self.read_general(TAG_BITSTRING, |contents| {
             match contents {
                 Contents::Primitive(buf) => {
@@ -780,7 +1278,7 @@ impl<'a, 'b> BERReader<'a, 'b> {
                     return Ok(());
                 },
                 Contents::Constructed(inner) => {
-                    if mode == BERMode::Der {
+                    if !allow_constructed {
                         return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
                     }
                     loop {
@@ -849,14 +1347,15 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// # }
     /// ```
     ///
-    /// # Features
-    ///
-    /// This method is enabled by `bit-vec` feature.
+    /// The second component is the length in bits. The unused-bits octet
+    /// must be less than 8 and, in DER, the unused bits must be zero.
+    /// In BER, only the last segment of a constructed BITSTRING may
+    /// have unused bits.
     ///
-    /// ```toml
-    /// [dependencies]
-    /// yasna = { version = "*", features = ["bit-vec"] }
-    /// ```
+    /// This function is similar to `read_bitvec`, but is available
+    /// even if the `bit-vec` feature is disabled. To test individual
+    /// named bits (such as `KeyUsage` flags), use
+    /// [`read_bitstring`](Self::read_bitstring) instead.
     pub fn read_bitvec_bytes(self) -> ASN1Result<(Vec<u8>, usize)> {
         let mut unused_bits = 0;
         let mut bytes = Vec::new();
@@ -865,32 +1364,90 @@ impl<'a, 'b> BERReader<'a, 'b> {
         return Ok((bytes, len));
     }
 
-    fn read_bytes_impl(self, vec: &mut Vec<u8>) -> ASN1Result<()> {
+    /// Reads an ASN.1 BITSTRING value as [`BitString`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[3, 4, 6, 117, 13, 64];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_bitstring()
+    /// }).unwrap();
+    /// assert_eq!(asn.len(), 18);
+    /// assert_eq!(asn.as_bytes(), &[117, 13, 64]);
+    /// ```
+    ///
+    /// The checks are the same as in
+    /// [`read_bitvec_bytes`](Self::read_bitvec_bytes).
+    pub fn read_bitstring(self) -> ASN1Result<BitString> {
+        let (bytes, len) = self.read_bitvec_bytes()?;
+        return Ok(BitString::from_bytes_and_len(bytes, len));
+    }
+
+    #[cfg(feature = "bitvec")]
+    /// Reads an ASN.1 BITSTRING value as `bitvec::vec::BitVec<u8, Msb0>`,
+    /// whose storage has the same packing as the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use bitvec::prelude::*;
+    /// let data = &[3, 2, 5, 160];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_bitvec_msb0()
+    /// }).unwrap();
+    /// assert_eq!(asn, bits![1, 0, 1]);
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `bitvec` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["bitvec"] }
+    /// ```
+    pub fn read_bitvec_msb0(self)
+            -> ASN1Result<bitvec::vec::BitVec<u8, Msb0>> {
+        return Ok(self.read_bitstring()?.into());
+    }
+
+    /// Calls `segment` with the contents of each primitive segment of
+    /// an OCTETSTRING value, in order.
+    fn read_bytes_with<S>(self, segment: &mut S) -> ASN1Result<()>
+            where S: FnMut(&'a [u8]) -> ASN1Result<()> {
         self.read_general(TAG_OCTETSTRING, |contents| {
             match contents {
                 Contents::Primitive(buf) => {
-                    vec.extend(buf);
-                    return Ok(());
+                    return segment(buf);
                 },
                 Contents::Constructed(inner) => {
-                    if inner.mode == BERMode::Der {
-                        return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-                    }
-                    loop {
-                        let result = inner.read_optional(|inner| {
-                            BERReader::new(inner).read_bytes_impl(vec)
-                        })?;
-                        match result {
-                            Some(()) => {},
-                            None => { break; },
-                        }
-                    }
-                    return Ok(());
+                    return BERReader::read_bytes_segments(inner, segment);
                 },
             };
         })
     }
 
+    fn read_bytes_segments<S>(inner: &mut BERReaderImpl<'a>,
+            segment: &mut S) -> ASN1Result<()>
+            where S: FnMut(&'a [u8]) -> ASN1Result<()> {
+        if !inner.options.allow_constructed_strings {
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+        }
+        loop {
+            let result = inner.read_optional(|inner| {
+                BERReader::new(inner).read_bytes_with(segment)
+            })?;
+            match result {
+                Some(()) => {},
+                None => { break; },
+            }
+        }
+        return Ok(());
+    }
+
     /// Reads an ASN.1 OCTETSTRING value as `Vec<u8>`.
     ///
     /// # Examples
@@ -905,10 +1462,106 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// ```
     pub fn read_bytes(self) -> ASN1Result<Vec<u8>> {
         let mut ret = Vec::new();
-        self.read_bytes_impl(&mut ret)?;
+        self.read_bytes_with(&mut |buf| {
+            ret.extend_from_slice(buf);
+            return Ok(());
+        })?;
         return Ok(ret);
     }
 
+    /// Reads an ASN.1 OCTETSTRING value into a caller-provided buffer,
+    /// without allocating. Returns the number of bytes written.
+    ///
+    /// Constructed (BER) values are reassembled into `buf` as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[4, 6, 72, 101, 108, 108, 111, 33];
+    /// let mut buf = [0; 16];
+    /// let len = yasna::parse_der(data, |reader| {
+    ///     reader.read_bytes_into(&mut buf)
+    /// }).unwrap();
+    /// assert_eq!(&buf[..len], b"Hello!");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Except parse errors, it raises an `InvalidLength` error
+    /// if the contents do not fit into `buf`.
+    pub fn read_bytes_into(self, buf: &mut [u8]) -> ASN1Result<usize> {
+        let mut len = 0;
+        self.read_bytes_with(&mut |src| {
+            let end = len + src.len();
+            if end > buf.len() {
+                return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
+            }
+            buf[len..end].copy_from_slice(src);
+            len = end;
+            return Ok(());
+        })?;
+        return Ok(len);
+    }
+
+    /// Reads an ASN.1 OCTETSTRING value, borrowing from the input when
+    /// it is encoded as a single primitive value and reassembling the
+    /// segments of a constructed (BER) value otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use yasna;
+    /// let data = &[36, 128, 4, 2, 72, 101, 4, 1, 121, 0, 0];
+    /// let asn = yasna::parse_ber(data, |reader| {
+    ///     reader.read_bytes_cow()
+    /// }).unwrap();
+    /// assert_eq!(asn, Cow::Owned::<[u8]>(b"Hey".to_vec()));
+    /// ```
+    pub fn read_bytes_cow(self) -> ASN1Result<Cow<'a, [u8]>> {
+        self.read_general(TAG_OCTETSTRING, |contents| {
+            match contents {
+                Contents::Primitive(buf) => Ok(Cow::Borrowed(buf)),
+                Contents::Constructed(inner) => {
+                    let mut vec = Vec::new();
+                    BERReader::read_bytes_segments(inner, &mut |buf| {
+                        vec.extend_from_slice(buf);
+                        return Ok(());
+                    })?;
+                    return Ok(Cow::Owned(vec));
+                },
+            }
+        })
+    }
+
+    /// Reads an ASN.1 OCTETSTRING value as a slice borrowed from
+    /// the input, without copying.
+    ///
+    /// Only the primitive encoding can be borrowed; constructed (BER)
+    /// values are rejected. Use [`read_bytes`](BERReader::read_bytes)
+    /// to accept them as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[4, 6, 72, 101, 108, 108, 111, 33];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_bytes_ref()
+    /// }).unwrap();
+    /// assert_eq!(asn, b"Hello!");
+    /// ```
+    pub fn read_bytes_ref(self) -> ASN1Result<&'a [u8]> {
+        self.read_general(TAG_OCTETSTRING, |contents| {
+            match contents {
+                Contents::Primitive(buf) => Ok(buf),
+                Contents::Constructed(_) =>
+                    Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+            }
+        })
+    }
+
     /// Reads the ASN.1 NULL value.
     ///
     /// # Examples
@@ -936,6 +1589,59 @@ impl<'a, 'b> BERReader<'a, 'b> {
         })
     }
 
+    /// Reads an ASN.1 REAL value as `f64`.
+    ///
+    /// Binary (base 2, 8 and 16), decimal (ISO 6093 NR1, NR2 and NR3)
+    /// and special (PLUS-INFINITY, MINUS-INFINITY, NOT-A-NUMBER and
+    /// minus zero) encodings are accepted. In DER, only the canonical
+    /// forms of X.690 11.3 are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[9, 3, 128, 255, 3];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_real()
+    /// }).unwrap();
+    /// assert_eq!(asn, 1.5);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Except parse errors, it can raise integer overflow errors when
+    /// the exponent doesn't fit in `i64`. Values out of the range of
+    /// `f64` are rounded to infinity or zero.
+    pub fn read_real(self) -> ASN1Result<f64> {
+        let mode = self.inner.options.mode;
+        self.read_general(TAG_REAL, |contents| {
+            let buf = match contents {
+                Contents::Primitive(buf) => buf,
+                Contents::Constructed(_) => {
+                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                },
+            };
+            if buf.len() == 0 {
+                return Ok(0.0);
+            }
+            if buf[0] & 128 != 0 {
+                return decode_binary_real(buf, mode);
+            } else if buf[0] & 64 == 0 {
+                return decode_decimal_real(buf, mode);
+            }
+            if buf.len() != 1 {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            match buf[0] {
+                64 => Ok(core::f64::INFINITY),
+                65 => Ok(core::f64::NEG_INFINITY),
+                66 => Ok(core::f64::NAN),
+                67 => Ok(-0.0),
+                _ => Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+            }
+        })
+    }
+
     /// Reads an ASN.1 object identifier.
     ///
     /// # Examples
@@ -990,84 +1696,340 @@ impl<'a, 'b> BERReader<'a, 'b> {
         })
     }
 
-    /// Reads an ASN.1 UTF8String.
+    #[cfg(feature = "num-bigint")]
+    /// Reads an ASN.1 object identifier with arbitrarily large
+    /// components.
     ///
     /// # Examples
     ///
     /// ```
     /// use yasna;
-    /// let data = &[
-    ///     12, 29, 103, 110, 97, 119, 32, 207, 129, 206, 191, 206,
-    ///     186, 206, 177, 206, 189, 206, 175, 206, 182, 207,
-    ///     137, 32, 240, 170, 152, 130, 227, 130, 139];
+    /// let data = &[6, 11, 105, 130, 128, 128, 128, 128, 128, 128, 128,
+    ///     128, 0];
     /// let asn = yasna::parse_der(data, |reader| {
-    ///     reader.read_utf8string()
+    ///     reader.read_big_oid()
     /// }).unwrap();
-    /// assert_eq!(&asn, "gnaw ροκανίζω 𪘂る");
+    /// assert_eq!(asn.to_string(), "2.25.18446744073709551616");
     /// ```
-    pub fn read_utf8string(self) -> ASN1Result<String> {
-        self.read_tagged_implicit(TAG_UTF8STRING, |reader| {
-            let bytes = reader.read_bytes()?;
-            match String::from_utf8(bytes) {
-                Ok(string) => Ok(string),
-                Err(_) => Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
-            }
-        })
-    }
-
-    /// Reads an ASN.1 SEQUENCE value.
     ///
-    /// This function uses the loan pattern: `callback` is called back with
-    /// a [`BERReaderSeq`], from which the contents of the
-    /// SEQUENCE is read.
+    /// # Features
     ///
-    /// # Examples
+    /// This method is enabled by `num-bigint` feature.
     ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["num-bigint"] }
     /// ```
-    /// use yasna;
-    /// let data = &[48, 6, 2, 1, 10, 1, 1, 255];
-    /// let asn = yasna::parse_der(data, |reader| {
-    ///     reader.read_sequence(|reader| {
-    ///         let i = reader.next().read_i64()?;
-    ///         let b = reader.next().read_bool()?;
-    ///         return Ok((i, b));
-    ///     })
-    /// }).unwrap();
-    /// assert_eq!(asn, (10, true));
-    /// ```
-    pub fn read_sequence<T, F>(self, callback: F) -> ASN1Result<T>
-            where F: for<'c> FnOnce(
-                &mut BERReaderSeq<'a, 'c>) -> ASN1Result<T> {
-        self.read_general(TAG_SEQUENCE, |contents| {
-            let inner = match contents {
-                Contents::Primitive(_) => {
+    pub fn read_big_oid(self) -> ASN1Result<BigObjectIdentifier> {
+        self.read_general(TAG_OID, |contents| {
+            let buf = match contents {
+                Contents::Primitive(buf) => buf,
+                Contents::Constructed(_) => {
                     return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
                 },
-                Contents::Constructed(inner) => inner,
             };
-            return callback(&mut BERReaderSeq { inner, });
+            if buf.len() == 0 || buf[buf.len()-1] >= 128 {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            let mut components = Vec::new();
+            let mut digits = Vec::new();
+            for &b in buf.iter() {
+                // A subidentifier must not start with a redundant 0x80.
+                if digits.is_empty() && b == 128 {
+                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                }
+                digits.push(b & 127);
+                if (b & 128) == 0 {
+                    let subid = BigUint::from_radix_be(&digits, 128).unwrap();
+                    digits.clear();
+                    if components.len() == 0 {
+                        let id0 = if subid < BigUint::from(40u8) {
+                            0u8
+                        } else if subid < BigUint::from(80u8) {
+                            1
+                        } else {
+                            2
+                        };
+                        components.push(BigUint::from(id0));
+                        components.push(subid - 40u8 * id0);
+                    } else {
+                        components.push(subid);
+                    }
+                }
+            }
+            return Ok(BigObjectIdentifier::new(components));
         })
     }
 
-    /// Reads an ASN.1 SEQUENCE OF value.
-    ///
-    /// This function uses the loan pattern: `callback` is called back with
-    /// a [`BERReader`], from which the contents of the
-    /// SEQUENCE OF is read.
-    ///
-    /// This function doesn't return values. Instead, use mutable values to
-    /// maintain read values. `collect_set_of` can be an alternative.
+    /// Reads an ASN.1 RELATIVE-OID.
     ///
     /// # Examples
     ///
     /// ```
     /// use yasna;
-    /// let data = &[48, 7, 2, 1, 10, 2, 2, 255, 127];
+    /// let data = &[13, 4, 194, 123, 3, 2];
     /// let asn = yasna::parse_der(data, |reader| {
-    ///     let mut numbers = Vec::new();
-    ///     reader.read_sequence_of(|reader| {
-    ///         numbers.push(reader.read_i64()?);
-    ///         return Ok(());
+    ///     reader.read_relative_oid()
+    /// }).unwrap();
+    /// assert_eq!(&*asn.components(), &[8571, 3, 2]);
+    /// ```
+    pub fn read_relative_oid(self) -> ASN1Result<RelativeOid> {
+        use super::tags::TAG_RELATIVE_OID;
+        self.read_general(TAG_RELATIVE_OID, |contents| {
+            let buf = match contents {
+                Contents::Primitive(buf) => buf,
+                Contents::Constructed(_) => {
+                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                },
+            };
+            let mut components = Vec::new();
+            if buf.is_empty() || buf[buf.len()-1] >= 128 {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            let mut subid : u64 = 0;
+            for &b in buf.iter() {
+                if b == 128 && subid == 0 {
+                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                }
+                subid = subid.checked_mul(128)
+                    .ok_or(ASN1Error::new(
+                        ASN1ErrorKind::IntegerOverflow))? + ((b & 127) as u64);
+                if (b & 128) == 0 {
+                    components.push(subid);
+                    subid = 0;
+                }
+            }
+            return Ok(RelativeOid::new(components));
+        })
+    }
+
+    /// Reads an ASN.1 EMBEDDED PDV.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use yasna::models::{ObjectIdentifier, PdvIdentification};
+    /// let data = &[43, 11, 160, 4, 129, 2, 81, 1, 130, 3, 72, 105, 33];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_embedded_pdv()
+    /// }).unwrap();
+    /// assert_eq!(asn.identification, PdvIdentification::Syntax(
+    ///     ObjectIdentifier::from_slice(&[2, 1, 1])));
+    /// assert_eq!(&asn.data_value, b"Hi!");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Except parse errors, it raises an `UnexpectedTag` error for an
+    /// unknown `identification` alternative.
+    pub fn read_embedded_pdv(self) -> ASN1Result<EmbeddedPdv> {
+        use super::tags::TAG_EMBEDDED_PDV;
+        self.read_tagged_implicit(TAG_EMBEDDED_PDV, |reader| {
+            reader.read_sequence(|reader| {
+                let identification = reader.next().read_tagged(
+                        Tag::context(0), |reader| {
+                    let tag = reader.lookahead_tag()?;
+                    if tag == Tag::context(0) {
+                        reader.read_tagged_implicit(tag, |reader| {
+                            reader.read_sequence(|reader| {
+                                let abstract_syntax = reader.next()
+                                    .read_tagged_implicit(Tag::context(0),
+                                        |reader| reader.read_oid())?;
+                                let transfer_syntax = reader.next()
+                                    .read_tagged_implicit(Tag::context(1),
+                                        |reader| reader.read_oid())?;
+                                return Ok(PdvIdentification::Syntaxes {
+                                    abstract_syntax,
+                                    transfer_syntax,
+                                });
+                            })
+                        })
+                    } else if tag == Tag::context(1) {
+                        reader.read_tagged_implicit(tag, |reader| {
+                            reader.read_oid()
+                        }).map(PdvIdentification::Syntax)
+                    } else if tag == Tag::context(2) {
+                        reader.read_tagged_implicit(tag, |reader| {
+                            reader.read_i64()
+                        }).map(PdvIdentification::PresentationContextId)
+                    } else if tag == Tag::context(3) {
+                        reader.read_tagged_implicit(tag, |reader| {
+                            reader.read_sequence(|reader| {
+                                let presentation_context_id = reader.next()
+                                    .read_tagged_implicit(Tag::context(0),
+                                        |reader| reader.read_i64())?;
+                                let transfer_syntax = reader.next()
+                                    .read_tagged_implicit(Tag::context(1),
+                                        |reader| reader.read_oid())?;
+                                return Ok(
+                                    PdvIdentification::ContextNegotiation {
+                                        presentation_context_id,
+                                        transfer_syntax,
+                                    });
+                            })
+                        })
+                    } else if tag == Tag::context(4) {
+                        reader.read_tagged_implicit(tag, |reader| {
+                            reader.read_oid()
+                        }).map(PdvIdentification::TransferSyntax)
+                    } else if tag == Tag::context(5) {
+                        reader.read_tagged_implicit(tag, |reader| {
+                            reader.read_null()
+                        }).map(|_| PdvIdentification::Fixed)
+                    } else {
+                        Err(ASN1Error::new(ASN1ErrorKind::UnexpectedTag))
+                    }
+                })?;
+                let data_value = reader.next().read_tagged_implicit(
+                    Tag::context(2), |reader| reader.read_bytes())?;
+                return Ok(EmbeddedPdv {
+                    identification,
+                    data_value,
+                });
+            })
+        })
+    }
+
+    /// Reads an ASN.1 UTF8String.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[
+    ///     12, 29, 103, 110, 97, 119, 32, 207, 129, 206, 191, 206,
+    ///     186, 206, 177, 206, 189, 206, 175, 206, 182, 207,
+    ///     137, 32, 240, 170, 152, 130, 227, 130, 139];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_utf8string()
+    /// }).unwrap();
+    /// assert_eq!(&asn, "gnaw ροκανίζω 𪘂る");
+    /// ```
+    pub fn read_utf8string(self) -> ASN1Result<String> {
+        self.read_tagged_implicit(TAG_UTF8STRING, |reader| {
+            let bytes = reader.read_bytes()?;
+            match String::from_utf8(bytes) {
+                Ok(string) => Ok(string),
+                Err(_) => Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+            }
+        })
+    }
+
+    /// Reads an ASN.1 UTF8String, borrowing from the input when it is
+    /// encoded as a single primitive value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use yasna;
+    /// let data = &[12, 3, 72, 105, 33];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_utf8string_cow()
+    /// }).unwrap();
+    /// assert_eq!(asn, Cow::Borrowed("Hi!"));
+    /// ```
+    pub fn read_utf8string_cow(self) -> ASN1Result<Cow<'a, str>> {
+        self.read_tagged_implicit(TAG_UTF8STRING, |reader| {
+            bytes_to_str(reader.read_bytes_cow()?)
+        })
+    }
+
+    // Reads a restricted character string whose characters are single
+    // octets satisfying `valid`.
+    fn read_charstring_cow<P>(self, tag: Tag, valid: P)
+            -> ASN1Result<Cow<'a, str>>
+            where P: Fn(u8) -> bool {
+        let check = self.inner.options.check_charsets;
+        self.read_tagged_implicit(tag, |reader| {
+            let bytes = reader.read_bytes_cow()?;
+            if check && !bytes.iter().all(|&byte| valid(byte)) {
+                return Err(ASN1Error::new(ASN1ErrorKind::CharsetViolation));
+            }
+            return bytes_to_str(bytes);
+        })
+    }
+
+    /// Reads an ASN.1 UTF8String as a `str` borrowed from the input,
+    /// without copying.
+    ///
+    /// Only the primitive encoding can be borrowed; constructed (BER)
+    /// values are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[12, 3, 72, 105, 33];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_utf8string_ref()
+    /// }).unwrap();
+    /// assert_eq!(asn, "Hi!");
+    /// ```
+    pub fn read_utf8string_ref(self) -> ASN1Result<&'a str> {
+        self.read_tagged_implicit(TAG_UTF8STRING, |reader| {
+            let bytes = reader.read_bytes_ref()?;
+            match core::str::from_utf8(bytes) {
+                Ok(string) => Ok(string),
+                Err(_) => Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+            }
+        })
+    }
+
+    /// Reads an ASN.1 SEQUENCE value.
+    ///
+    /// This function uses the loan pattern: `callback` is called back with
+    /// a [`BERReaderSeq`], from which the contents of the
+    /// SEQUENCE is read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[48, 6, 2, 1, 10, 1, 1, 255];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_sequence(|reader| {
+    ///         let i = reader.next().read_i64()?;
+    ///         let b = reader.next().read_bool()?;
+    ///         return Ok((i, b));
+    ///     })
+    /// }).unwrap();
+    /// assert_eq!(asn, (10, true));
+    /// ```
+    pub fn read_sequence<T, F>(self, callback: F) -> ASN1Result<T>
+            where F: for<'c> FnOnce(
+                &mut BERReaderSeq<'a, 'c>) -> ASN1Result<T> {
+        self.read_general(TAG_SEQUENCE, |contents| {
+            let inner = match contents {
+                Contents::Primitive(_) => {
+                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                },
+                Contents::Constructed(inner) => inner,
+            };
+            return callback(&mut BERReaderSeq { inner, });
+        })
+    }
+
+    /// Reads an ASN.1 SEQUENCE OF value.
+    ///
+    /// This function uses the loan pattern: `callback` is called back with
+    /// a [`BERReader`], from which the contents of the
+    /// SEQUENCE OF is read.
+    ///
+    /// This function doesn't return values. Instead, use mutable values to
+    /// maintain read values. `collect_set_of` can be an alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[48, 7, 2, 1, 10, 2, 2, 255, 127];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     let mut numbers = Vec::new();
+    ///     reader.read_sequence_of(|reader| {
+    ///         numbers.push(reader.read_i64()?);
+    ///         return Ok(());
     ///     })?;
     ///     return Ok(numbers);
     /// }).unwrap();
@@ -1164,14 +2126,16 @@ impl<'a, 'b> BERReader<'a, 'b> {
                     break;
                 }
             }
-            if inner.mode == BERMode::Der {
+            if inner.options.mode == BERMode::Der {
+                // Elements must appear in ascending tag order, which
+                // also excludes duplicate tags.
                 for i in 1..elements.len() {
-                    if elements[i] <= elements[i-1] {
+                    if elements[i].0 <= elements[i-1].0 {
                         return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
                     }
                 }
             }
-            let mut new_impl = BERReaderImpl::new(&[], inner.mode);
+            let mut new_impl = BERReaderImpl::new(&[], inner.options);
             let result = callback(&mut BERReaderSet {
                 impl_ref: &mut new_impl,
                 elements: &mut elements,
@@ -1228,7 +2192,7 @@ impl<'a, 'b> BERReader<'a, 'b> {
                     })
             })? {
                 if let Some(last_buf) = last_buf {
-                    if inner.mode == BERMode::Der && buf < last_buf {
+                    if inner.options.mode == BERMode::Der && buf < last_buf {
                         return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
                     }
                 }
@@ -1284,15 +2248,13 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// assert_eq!(&asn, "128 256");
     /// ```
     pub fn read_numeric_string(self) -> ASN1Result<String> {
-        self.read_tagged_implicit(TAG_NUMERICSTRING, |reader| {
-            let bytes = reader.read_bytes()?;
-            for &byte in bytes.iter() {
-                if !(byte == b' ' || (b'0' <= byte && byte <= b'9')) {
-                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-                }
-            }
-            return Ok(String::from_utf8(bytes).unwrap());
-        })
+        self.read_numeric_string_cow().map(Cow::into_owned)
+    }
+
+    /// Reads an ASN.1 NumericString, borrowing from the input when it is
+    /// encoded as a single primitive value.
+    pub fn read_numeric_string_cow(self) -> ASN1Result<Cow<'a, str>> {
+        self.read_charstring_cow(TAG_NUMERICSTRING, is_numeric_char)
     }
 
     /// Reads an ASN.1 PrintableString.
@@ -1308,20 +2270,13 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// assert_eq!(&asn, "Co., Ltd.");
     /// ```
     pub fn read_printable_string(self) -> ASN1Result<String> {
-        self.read_tagged_implicit(TAG_PRINTABLESTRING, |reader| {
-            let bytes = reader.read_bytes()?;
-            for &byte in bytes.iter() {
-                if !(
-                    byte == b' ' ||
-                    (b'\'' <= byte && byte <= b':' && byte != b'*') ||
-                    byte == b'=' ||
-                    (b'A' <= byte && byte <= b'Z') ||
-                    (b'a' <= byte && byte <= b'z')) {
-                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-                }
-            }
-            return Ok(String::from_utf8(bytes).unwrap());
-        })
+        self.read_printable_string_cow().map(Cow::into_owned)
+    }
+
+    /// Reads an ASN.1 PrintableString, borrowing from the input when it is
+    /// encoded as a single primitive value.
+    pub fn read_printable_string_cow(self) -> ASN1Result<Cow<'a, str>> {
+        self.read_charstring_cow(TAG_PRINTABLESTRING, is_printable_char)
     }
 
     /// Reads an ASN.1 IA5String.
@@ -1337,19 +2292,13 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// assert_eq!(&asn, "ASCII plz");
     /// ```
     pub fn read_ia5_string(self) -> ASN1Result<String> {
-        self.read_tagged_implicit(TAG_IA5STRING, |reader| {
-            let bytes = reader.read_bytes()?;
+        self.read_ia5_string_cow().map(Cow::into_owned)
+    }
 
-            match String::from_utf8(bytes) {
-                Ok(string) => {
-                    if !string.is_ascii() {
-                        return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-                    }
-                    Ok(string)
-                }
-                Err(_) => Err(ASN1Error::new(ASN1ErrorKind::Invalid))
-            }
-        })
+    /// Reads an ASN.1 IA5String, borrowing from the input when it is
+    /// encoded as a single primitive value.
+    pub fn read_ia5_string_cow(self) -> ASN1Result<Cow<'a, str>> {
+        self.read_charstring_cow(TAG_IA5STRING, is_ia5_char)
     }
 
     /// Reads an ASN.1 BMPString.
@@ -1362,36 +2311,424 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// let asn = yasna::parse_der(data, |reader| {
     ///     reader.read_bmp_string()
     /// }).unwrap();
-    /// assert_eq!(&asn, "£π quux");
+    /// assert_eq!(&asn, "£π quux");
+    /// ```
+    pub fn read_bmp_string(self) -> ASN1Result<String> {
+        self.read_bmp_string_cow().map(Cow::into_owned)
+    }
+
+    /// Reads an ASN.1 BMPString as a [`Cow`].
+    ///
+    /// BMPString is encoded in UTF-16, so the result has to be transcoded
+    /// and is owned unless it is empty. This is the counterpart of the
+    /// other `_cow` string readers for code handling any string type.
+    pub fn read_bmp_string_cow(self) -> ASN1Result<Cow<'a, str>> {
+        self.read_tagged_implicit(TAG_BMPSTRING, |reader| {
+            let bytes = reader.read_bytes_cow()?;
+
+            if bytes.len() % 2 != 0 {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            if bytes.is_empty() {
+                return Ok(Cow::Borrowed(""));
+            }
+
+            let mut string = String::with_capacity(bytes.len());
+            for c in bytes.chunks(2) {
+                // BMPString only covers the Basic Multilingual Plane,
+                // so surrogate code units never appear in valid data.
+                match core::char::from_u32((c[0] as u32) * 256 + c[1] as u32) {
+                    Some(ch) => string.push(ch),
+                    None => return Err(
+                        ASN1Error::new(ASN1ErrorKind::CharsetViolation)),
+                }
+            }
+            return Ok(Cow::Owned(string));
+        })
+    }
+
+    #[cfg(feature = "time")]
+    /// Reads an ASN.1 UTCTime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[
+    ///     23, 15, 56, 50, 48, 49, 48, 50, 48,
+    ///     55, 48, 48, 45, 48, 53, 48, 48];
+    /// let asn = yasna::parse_ber(data, |reader| {
+    ///     reader.read_utctime()
+    /// }).unwrap();
+    /// assert_eq!(asn.datetime().unix_timestamp(), 378820800);
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn read_utctime(self) -> ASN1Result<UTCTime> {
+        use super::tags::TAG_UTCTIME;
+        let mode = self.inner.options.mode;
+        self.read_tagged_implicit(TAG_UTCTIME, |reader| {
+            let bytes = reader.read_bytes()?;
+            let datetime = UTCTime::parse(&bytes).ok_or_else(
+                || ASN1Error::new(ASN1ErrorKind::Invalid))?;
+            if mode == BERMode::Der && &datetime.to_bytes() != &bytes {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            return Ok(datetime);
+        })
+    }
+
+    #[cfg(feature = "time")]
+    /// Reads an ASN.1 GeneralizedTime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[
+    ///     24, 17, 49, 57, 56, 53, 49, 49, 48, 54,
+    ///     50, 49, 46, 49, 52, 49, 53, 57, 90];
+    /// let asn = yasna::parse_ber(data, |reader| {
+    ///     reader.read_generalized_time()
+    /// }).unwrap();
+    /// assert_eq!(asn.datetime().unix_timestamp(), 500159309);
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn read_generalized_time(self) -> ASN1Result<GeneralizedTime> {
+        use super::tags::TAG_GENERALIZEDTIME;
+        let mode = self.inner.options.mode;
+        self.read_tagged_implicit(TAG_GENERALIZEDTIME, |reader| {
+            let bytes = reader.read_bytes()?;
+            let datetime = GeneralizedTime::parse(&bytes).ok_or_else(
+                || ASN1Error::new(ASN1ErrorKind::Invalid))?;
+            if mode == BERMode::Der && &datetime.to_bytes() != &bytes {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            return Ok(datetime);
+        })
+    }
+
+    /// Reads an ASN.1 UTCTime into its fields, without converting it.
+    ///
+    /// In DER mode, it only accepts the form DER requires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = b"\x17\x0f8201020700-0500";
+    /// let asn = yasna::parse_ber(data, |reader| {
+    ///     reader.read_utctime_fields()
+    /// }).unwrap();
+    /// assert_eq!((asn.year(), asn.hour()), (1982, 7));
+    /// ```
+    pub fn read_utctime_fields(self) -> ASN1Result<UTCTimeFields> {
+        use super::tags::TAG_UTCTIME;
+        let mode = self.inner.options.mode;
+        self.read_tagged_implicit(TAG_UTCTIME, |reader| {
+            let bytes = reader.read_bytes()?;
+            let fields = UTCTimeFields::parse(&bytes).ok_or_else(
+                || ASN1Error::new(ASN1ErrorKind::Invalid))?;
+            if mode == BERMode::Der &&
+                    !(fields.is_der() && fields.to_bytes() == bytes) {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            return Ok(fields);
+        })
+    }
+
+    /// Reads an ASN.1 GeneralizedTime into its fields, without converting
+    /// it.
+    ///
+    /// In DER mode, it only accepts the form DER requires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = b"\x18\x101985110621.14159";
+    /// let asn = yasna::parse_ber(data, |reader| {
+    ///     reader.read_generalized_time_fields()
+    /// }).unwrap();
+    /// assert_eq!((asn.year(), asn.minute()), (1985, None));
+    /// assert_eq!(asn.fraction(), b"14159");
+    /// ```
+    pub fn read_generalized_time_fields(self)
+            -> ASN1Result<GeneralizedTimeFields> {
+        use super::tags::TAG_GENERALIZEDTIME;
+        let mode = self.inner.options.mode;
+        self.read_tagged_implicit(TAG_GENERALIZEDTIME, |reader| {
+            let bytes = reader.read_bytes()?;
+            let fields = GeneralizedTimeFields::parse(&bytes).ok_or_else(
+                || ASN1Error::new(ASN1ErrorKind::Invalid))?;
+            if mode == BERMode::Der &&
+                    !(fields.is_der() && fields.to_bytes() == bytes) {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            return Ok(fields);
+        })
+    }
+
+    #[cfg(feature = "time")]
+    /// Reads an ASN.1 UTCTime as an `OffsetDateTime` in UTC.
+    ///
+    /// This is a shorthand for [`read_utctime`](Self::read_utctime)
+    /// followed by [`UTCTime::datetime`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = b"\x17\x0f8201020700-0500";
+    /// let asn = yasna::parse_ber(data, |reader| {
+    ///     reader.read_utctime_datetime()
+    /// }).unwrap();
+    /// assert_eq!(asn.unix_timestamp(), 378820800);
+    /// assert_eq!(asn.offset(), time::UtcOffset::UTC);
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn read_utctime_datetime(self) -> ASN1Result<time::OffsetDateTime> {
+        return Ok(*self.read_utctime()?.datetime());
+    }
+
+    #[cfg(feature = "time")]
+    /// Reads an ASN.1 GeneralizedTime as an `OffsetDateTime` in UTC.
+    ///
+    /// This is a shorthand for
+    /// [`read_generalized_time`](Self::read_generalized_time) followed by
+    /// [`GeneralizedTime::datetime`], so leap seconds and sub-nanoseconds
+    /// digits are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = b"\x18\x1319851106210829.724Z";
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_generalized_time_datetime()
+    /// }).unwrap();
+    /// assert_eq!(asn.unix_timestamp(), 500159309);
+    /// assert_eq!(asn.millisecond(), 724);
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn read_generalized_time_datetime(self)
+            -> ASN1Result<time::OffsetDateTime> {
+        return Ok(*self.read_generalized_time()?.datetime());
+    }
+
+    #[cfg(feature = "std")]
+    /// Reads an ASN.1 UTCTime as a `SystemTime`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// let data = b"\x17\x0d820102120000Z";
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_utctime_system_time()
+    /// }).unwrap();
+    /// assert_eq!(asn, UNIX_EPOCH + Duration::from_secs(378820800));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Besides malformed values, it fails when `SystemTime` can't
+    /// represent the value on the platform.
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `std` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["std"] }
+    /// ```
+    pub fn read_utctime_system_time(self)
+            -> ASN1Result<std::time::SystemTime> {
+        return self.read_utctime_fields()?.to_system_time()
+            .ok_or_else(|| ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+
+    #[cfg(feature = "std")]
+    /// Reads an ASN.1 GeneralizedTime as a `SystemTime`.
+    ///
+    /// Digits of the fraction beyond nanoseconds are truncated, and a leap
+    /// second is taken as the first second of the next minute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// let data = b"\x18\x1319851106210829.724Z";
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_generalized_time_system_time()
+    /// }).unwrap();
+    /// assert_eq!(asn, UNIX_EPOCH + Duration::from_millis(500159309724));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Besides malformed values, it fails for the local time without
+    /// a time zone, and when `SystemTime` can't represent the value on
+    /// the platform.
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `std` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["std"] }
+    /// ```
+    pub fn read_generalized_time_system_time(self)
+            -> ASN1Result<std::time::SystemTime> {
+        return self.read_generalized_time_fields()?.to_system_time()
+            .ok_or_else(|| ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+
+    #[cfg(feature = "chrono")]
+    /// Reads an ASN.1 UTCTime as a `chrono::DateTime<Utc>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = b"\x17\x0f8201020700-0500";
+    /// let asn = yasna::parse_ber(data, |reader| {
+    ///     reader.read_utctime_chrono()
+    /// }).unwrap();
+    /// assert_eq!(asn.timestamp(), 378820800);
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `chrono` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["chrono"] }
+    /// ```
+    pub fn read_utctime_chrono(self)
+            -> ASN1Result<chrono::DateTime<chrono::Utc>> {
+        return Ok(self.read_utctime_fields()?.to_chrono());
+    }
+
+    #[cfg(feature = "chrono")]
+    /// Reads an ASN.1 GeneralizedTime as a `chrono::DateTime<Utc>`.
+    ///
+    /// Digits of the fraction beyond nanoseconds are truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = b"\x18\x1319851106210829.724Z";
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_generalized_time_chrono()
+    /// }).unwrap();
+    /// assert_eq!(asn.timestamp(), 500159309);
+    /// assert_eq!(asn.timestamp_subsec_millis(), 724);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Besides malformed values, it fails for the local time without
+    /// a time zone.
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `chrono` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["chrono"] }
+    /// ```
+    pub fn read_generalized_time_chrono(self)
+            -> ASN1Result<chrono::DateTime<chrono::Utc>> {
+        return self.read_generalized_time_fields()?.to_chrono()
+            .ok_or_else(|| ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+
+    #[cfg(feature = "time")]
+    /// Reads an ASN.1 DATE.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() {
+    /// use yasna;
+    /// let data = b"\x1f\x1f\x0820080401";
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_date()
+    /// }).unwrap();
+    /// assert_eq!(&asn.to_string(), "20080401");
+    /// # }
     /// ```
-    pub fn read_bmp_string(self) -> ASN1Result<String> {
-        self.read_tagged_implicit(TAG_BMPSTRING, |reader| {
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn read_date(self) -> ASN1Result<Date> {
+        use super::tags::TAG_DATE;
+        self.read_tagged_implicit(TAG_DATE, |reader| {
             let bytes = reader.read_bytes()?;
-
-            if bytes.len() % 2 != 0 {
-                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-            }
-
-            let utf16 : Vec<u16> = bytes.chunks(2).map(|c| (c[0] as u16) * 256 + c[1] as u16).collect();
-
-            Ok(String::from_utf16_lossy(&utf16))
+            return Date::parse(&bytes).ok_or_else(
+                || ASN1Error::new(ASN1ErrorKind::Invalid));
         })
     }
 
     #[cfg(feature = "time")]
-    /// Reads an ASN.1 UTCTime.
+    /// Reads an ASN.1 TIME-OF-DAY.
     ///
     /// # Examples
     ///
     /// ```
+    /// # fn main() {
     /// use yasna;
-    /// let data = &[
-    ///     23, 15, 56, 50, 48, 49, 48, 50, 48,
-    ///     55, 48, 48, 45, 48, 53, 48, 48];
-    /// let asn = yasna::parse_ber(data, |reader| {
-    ///     reader.read_utctime()
+    /// let data = b"\x1f\x20\x06123456";
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_time_of_day()
     /// }).unwrap();
-    /// assert_eq!(asn.datetime().unix_timestamp(), 378820800);
+    /// assert_eq!(&asn.to_string(), "123456");
+    /// # }
     /// ```
     ///
     /// # Features
@@ -1402,34 +2739,29 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// [dependencies]
     /// yasna = { version = "*", features = ["time"] }
     /// ```
-    pub fn read_utctime(self) -> ASN1Result<UTCTime> {
-        use super::tags::TAG_UTCTIME;
-        let mode = self.inner.mode;
-        self.read_tagged_implicit(TAG_UTCTIME, |reader| {
+    pub fn read_time_of_day(self) -> ASN1Result<TimeOfDay> {
+        use super::tags::TAG_TIME_OF_DAY;
+        self.read_tagged_implicit(TAG_TIME_OF_DAY, |reader| {
             let bytes = reader.read_bytes()?;
-            let datetime = UTCTime::parse(&bytes).ok_or_else(
-                || ASN1Error::new(ASN1ErrorKind::Invalid))?;
-            if mode == BERMode::Der && &datetime.to_bytes() != &bytes {
-                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-            }
-            return Ok(datetime);
+            return TimeOfDay::parse(&bytes).ok_or_else(
+                || ASN1Error::new(ASN1ErrorKind::Invalid));
         })
     }
 
     #[cfg(feature = "time")]
-    /// Reads an ASN.1 GeneralizedTime.
+    /// Reads an ASN.1 DATE-TIME.
     ///
     /// # Examples
     ///
     /// ```
+    /// # fn main() {
     /// use yasna;
-    /// let data = &[
-    ///     24, 17, 49, 57, 56, 53, 49, 49, 48, 54,
-    ///     50, 49, 46, 49, 52, 49, 53, 57, 90];
-    /// let asn = yasna::parse_ber(data, |reader| {
-    ///     reader.read_generalized_time()
+    /// let data = b"\x1f\x21\x0e20080401123456";
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_date_time()
     /// }).unwrap();
-    /// assert_eq!(asn.datetime().unix_timestamp(), 500159309);
+    /// assert_eq!(&asn.to_string(), "20080401123456");
+    /// # }
     /// ```
     ///
     /// # Features
@@ -1440,20 +2772,62 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// [dependencies]
     /// yasna = { version = "*", features = ["time"] }
     /// ```
-    pub fn read_generalized_time(self) -> ASN1Result<GeneralizedTime> {
-        use super::tags::TAG_GENERALIZEDTIME;
-        let mode = self.inner.mode;
-        self.read_tagged_implicit(TAG_GENERALIZEDTIME, |reader| {
+    pub fn read_date_time(self) -> ASN1Result<DateTime> {
+        use super::tags::TAG_DATE_TIME;
+        self.read_tagged_implicit(TAG_DATE_TIME, |reader| {
             let bytes = reader.read_bytes()?;
-            let datetime = GeneralizedTime::parse(&bytes).ok_or_else(
+            return DateTime::parse(&bytes).ok_or_else(
+                || ASN1Error::new(ASN1ErrorKind::Invalid));
+        })
+    }
+
+    /// Reads an ASN.1 DURATION.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = b"\x1f\x22\x07P1DT12H";
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_duration()
+    /// }).unwrap();
+    /// assert_eq!((asn.days, asn.hours), (1, 12));
+    /// ```
+    pub fn read_duration(self) -> ASN1Result<Duration> {
+        use super::tags::TAG_DURATION;
+        let mode = self.inner.options.mode;
+        self.read_tagged_implicit(TAG_DURATION, |reader| {
+            let bytes = reader.read_bytes()?;
+            let duration = Duration::parse(&bytes).ok_or_else(
                 || ASN1Error::new(ASN1ErrorKind::Invalid))?;
-            if mode == BERMode::Der && &datetime.to_bytes() != &bytes {
+            if mode == BERMode::Der && duration.to_bytes() != bytes {
                 return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
             }
-            return Ok(datetime);
+            return Ok(duration);
         })
     }
 
+    /// Reads an ASN.1 ObjectDescriptor.
+    ///
+    /// Only the ASCII graphic characters and space are accepted as its
+    /// GraphicString contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[7, 3, 72, 105, 33];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_object_descriptor()
+    /// }).unwrap();
+    /// assert_eq!(&asn, "Hi!");
+    /// ```
+    pub fn read_object_descriptor(self) -> ASN1Result<String> {
+        use super::tags::TAG_OBJECT_DESCRIPTOR;
+        self.read_charstring_cow(TAG_OBJECT_DESCRIPTOR, is_visible_char)
+            .map(Cow::into_owned)
+    }
+
     /// Reads an ASN.1 VisibleString.
     ///
     /// # Examples
@@ -1467,15 +2841,13 @@ impl<'a, 'b> BERReader<'a, 'b> {
     /// assert_eq!(&asn, "Hi!");
     /// ```
     pub fn read_visible_string(self) -> ASN1Result<String> {
-        self.read_tagged_implicit(TAG_VISIBLESTRING, |reader| {
-            let bytes = reader.read_bytes()?;
-            for &byte in bytes.iter() {
-                if !(b' ' <= byte && byte <= b'~') {
-                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
-                }
-            }
-            return Ok(String::from_utf8(bytes).unwrap());
-        })
+        self.read_visible_string_cow().map(Cow::into_owned)
+    }
+
+    /// Reads an ASN.1 VisibleString, borrowing from the input when it is
+    /// encoded as a single primitive value.
+    pub fn read_visible_string_cow(self) -> ASN1Result<Cow<'a, str>> {
+        self.read_charstring_cow(TAG_VISIBLESTRING, is_visible_char)
     }
 
     /// Reads a (explicitly) tagged value.
@@ -1505,6 +2877,52 @@ impl<'a, 'b> BERReader<'a, 'b> {
         })
     }
 
+    /// Reads a (explicitly) tagged value whose tag satisfies `pred`.
+    ///
+    /// Useful when several tags, or a whole tag class, are acceptable.
+    /// The actual tag is passed to the callback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna::{self,Tag,TagClass};
+    /// let data = &[99, 3, 2, 1, 10];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_tagged_if(|tag| {
+    ///         tag.tag_class == TagClass::Application ||
+    ///             tag.tag_class == TagClass::ContextSpecific
+    ///     }, |tag, reader| {
+    ///         Ok((tag, reader.read_i64()?))
+    ///     })
+    /// }).unwrap();
+    /// assert_eq!(asn, (Tag::application(3), 10));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Except parse errors, it raises an `UnexpectedTag` error
+    /// if the tag doesn't satisfy `pred`.
+    pub fn read_tagged_if<T, P, F>(self, pred: P, callback: F)
+            -> ASN1Result<T>
+            where P: FnOnce(Tag) -> bool,
+            F: for<'c> FnOnce(Tag, BERReader<'a, 'c>) -> ASN1Result<T> {
+        let tag = match self.implicit_tag {
+            Some(tag) => tag,
+            None => {
+                let inner = &self.inner;
+                inner.lookahead_tag().map_err(|e| {
+                    inner.locate_error(e, inner.buf, inner.pos)
+                })?
+            },
+        };
+        if !pred(tag) {
+            let e = ASN1Error::new(ASN1ErrorKind::UnexpectedTag);
+            return Err(self.inner.locate_error(e, self.inner.buf,
+                self.inner.pos));
+        }
+        self.read_tagged(tag, |reader| callback(tag, reader))
+    }
+
     /// Reads an implicitly tagged value.
     ///
     /// # Examples
@@ -1556,6 +2974,80 @@ impl<'a, 'b> BERReader<'a, 'b> {
         self.inner.lookahead_tag()
     }
 
+    /// Decodes the identifier and length octets of the next element
+    /// without consuming anything.
+    ///
+    /// The offset in the result is absolute in the input given to
+    /// the parse function. See [`ElementHeader`] for an example.
+    pub fn lookahead_header(&self) -> ASN1Result<ElementHeader> {
+        let inner = &self.inner;
+        return BERReaderImpl::with_pos(inner.buf, inner.pos, inner.options)
+            .read_header();
+    }
+
+    /// Attempts to read a value with `callback`, rewinding the reader
+    /// if it fails.
+    ///
+    /// On error, the reader is handed back along with the error, as if
+    /// nothing had been read, so that another alternative of a CHOICE
+    /// can be tried. On success, it is consumed like with the other
+    /// methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[12, 2, 49, 48];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     match reader.try_read(|reader| reader.read_i64()) {
+    ///         Ok(i) => Ok(i),
+    ///         Err((_, reader)) => reader.read_utf8string()?.parse()
+    ///             .map_err(|_| {
+    ///                 yasna::ASN1Error::new(yasna::ASN1ErrorKind::Invalid)
+    ///             }),
+    ///     }
+    /// }).unwrap();
+    /// assert_eq!(asn, 10);
+    /// ```
+    pub fn try_read<T, F>(self, callback: F)
+            -> Result<T, (ASN1Error, BERReader<'a, 'b>)>
+            where F: for<'c> FnOnce(BERReader<'a, 'c>) -> ASN1Result<T> {
+        let implicit_tag = self.implicit_tag;
+        let result = self.inner.try_read(|inner| {
+            callback(BERReader {
+                inner,
+                implicit_tag,
+            })
+        });
+        return result.map_err(|e| (e, self));
+    }
+
+    /// Reads a value with `callback` and also returns the exact bytes
+    /// it was encoded as, including its tag and length.
+    ///
+    /// This is typically used to verify a signature over the original
+    /// encoding of a part of a message (e.g. an X.509 tbsCertificate)
+    /// without re-serializing it. The returned slice borrows from the
+    /// input, so nothing is copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[48, 8, 48, 3, 2, 1, 10, 3, 1, 0];
+    /// let (tbs, signed, signature) = yasna::parse_der(data, |reader| {
+    ///     reader.read_sequence(|reader| {
+    ///         let (tbs, signed) = reader.next().read_with_buffer(|reader| {
+    ///             reader.read_sequence(|reader| reader.next().read_i64())
+    ///         })?;
+    ///         let signature = reader.next().read_bitvec_bytes()?;
+    ///         return Ok((tbs, signed, signature));
+    ///     })
+    /// }).unwrap();
+    /// assert_eq!(tbs, 10);
+    /// assert_eq!(signed, &[48, 3, 2, 1, 10]);
+    /// assert_eq!(signature, (vec![], 0));
+    /// ```
     pub fn read_with_buffer<T, F>(self, callback: F)
             -> ASN1Result<(T, &'a [u8])>
             where F: for<'c> FnOnce(BERReader<'a, 'c>) -> ASN1Result<T> {
@@ -1568,6 +3060,122 @@ impl<'a, 'b> BERReader<'a, 'b> {
         })
     }
 
+    /// Reads an arbitrary ASN.1 value into an [`ASN1Value`] tree.
+    ///
+    /// Constructed values are decoded recursively. Used for ANY
+    /// fields and other data whose schema is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use yasna::models::ASN1Value;
+    /// let data = &[49, 6, 1, 1, 255, 12, 1, 65];
+    /// let asn = yasna::parse_der(data, |reader| reader.read_any()).unwrap();
+    /// assert_eq!(asn, ASN1Value::Set(vec![
+    ///     ASN1Value::Boolean(true),
+    ///     ASN1Value::UTF8String("A".to_string()),
+    /// ]));
+    /// ```
+    pub fn read_any(self) -> ASN1Result<ASN1Value> {
+        let tag = match self.implicit_tag {
+            // The underlying type is unknown; keep the contents as is.
+            Some(tag) => return self.read_any_general(tag),
+            None => self.lookahead_tag()?,
+        };
+        return match tag {
+            TAG_EOC => {
+                // Left unconsumed so that enclosing loops stop here.
+                let inner = &self.inner;
+                let e = ASN1Error::new(ASN1ErrorKind::Invalid);
+                Err(inner.locate_error(e, inner.buf, inner.pos))
+            },
+            TAG_BOOLEAN => self.read_bool().map(ASN1Value::Boolean),
+            TAG_INTEGER => self.read_integer_bytes(TAG_INTEGER)
+                .map(|buf| ASN1Value::Integer(buf.to_vec())),
+            TAG_BITSTRING => self.read_bitvec_bytes()
+                .map(|(bytes, len)| ASN1Value::BitString(bytes, len)),
+            TAG_OCTETSTRING => self.read_bytes().map(ASN1Value::OctetString),
+            TAG_NULL => self.read_null().map(|_| ASN1Value::Null),
+            TAG_OID => self.read_oid().map(ASN1Value::ObjectIdentifier),
+            TAG_UTF8STRING =>
+                self.read_utf8string().map(ASN1Value::UTF8String),
+            TAG_NUMERICSTRING =>
+                self.read_numeric_string().map(ASN1Value::NumericString),
+            TAG_PRINTABLESTRING =>
+                self.read_printable_string().map(ASN1Value::PrintableString),
+            TAG_IA5STRING => self.read_ia5_string().map(ASN1Value::IA5String),
+            TAG_VISIBLESTRING =>
+                self.read_visible_string().map(ASN1Value::VisibleString),
+            TAG_BMPSTRING => self.read_bmp_string().map(ASN1Value::BMPString),
+            TAG_SEQUENCE => self.collect_sequence_of(|reader| {
+                reader.read_any()
+            }).map(ASN1Value::Sequence),
+            TAG_SET => self.read_any_set().map(ASN1Value::Set),
+            _ => self.read_any_general(tag),
+        };
+    }
+
+    /// Reads a SET whose type is unknown. In DER, the elements must be
+    /// sorted either by their tags, as in a SET (X.690 10.3), or by their
+    /// encodings, as in a SET OF (X.690 11.6).
+    fn read_any_set(self) -> ASN1Result<Vec<ASN1Value>> {
+        self.read_general(TAG_SET, |contents| {
+            let inner = match contents {
+                Contents::Primitive(_) => {
+                    return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                },
+                Contents::Constructed(inner) => inner,
+            };
+            let mut children = Vec::new();
+            let mut tag_order = true;
+            let mut encoding_order = true;
+            let mut last = None;
+            while let Some((tag, (child, buf))) = inner.read_optional(|inner| {
+                let tag = inner.lookahead_tag()?;
+                let child = inner.read_with_buffer(|inner| {
+                    BERReader::new(inner).read_any()
+                })?;
+                return Ok((tag, child));
+            })? {
+                if let Some((last_tag, last_buf)) = last {
+                    tag_order = tag_order && tag > last_tag;
+                    encoding_order = encoding_order && buf >= last_buf;
+                    if inner.options.mode == BERMode::Der &&
+                            !tag_order && !encoding_order {
+                        return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+                    }
+                }
+                last = Some((tag, buf));
+                children.push(child);
+            }
+            return Ok(children);
+        })
+    }
+
+    fn read_any_general(self, tag: Tag) -> ASN1Result<ASN1Value> {
+        self.read_general(tag, |contents| {
+            match contents {
+                Contents::Primitive(buf) => {
+                    return Ok(ASN1Value::Primitive(tag, buf.to_vec()));
+                },
+                Contents::Constructed(inner) => {
+                    let mut children = Vec::new();
+                    loop {
+                        let result = inner.read_optional(|inner| {
+                            BERReader::new(inner).read_any()
+                        })?;
+                        match result {
+                            Some(child) => children.push(child),
+                            None => { break; },
+                        }
+                    }
+                    return Ok(ASN1Value::Constructed(tag, children));
+                },
+            }
+        })
+    }
+
     /// Read an arbitrary (tag, value) pair as a TaggedDerValue.
     /// The length is not included in the returned payload. If the
     /// payload has indefinite-length encoding, the EOC bytes are
@@ -1591,6 +3199,29 @@ impl<'a, 'b> BERReader<'a, 'b> {
                 self.inner.buf[data_pos..self.inner.pos].to_vec()))
     }
 
+    /// Reads an arbitrary ASN.1 value and discards it.
+    ///
+    /// Constructed values, including indefinite-length ones in BER,
+    /// are skipped as a whole. Used to ignore uninteresting fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[48, 10, 48, 5, 5, 0, 1, 1, 255, 2, 1, 10];
+    /// let asn = yasna::parse_der(data, |reader| {
+    ///     reader.read_sequence(|reader| {
+    ///         reader.next().skip_value()?;
+    ///         reader.next().read_i64()
+    ///     })
+    /// }).unwrap();
+    /// assert_eq!(asn, 10);
+    /// ```
+    pub fn skip_value(self) -> ASN1Result<()> {
+        self.inner.skip_general()?;
+        return Ok(());
+    }
+
     /// Reads a DER object as raw bytes. Tag and length are included
     /// in the returned buffer. For indefinite length encoding, EOC bytes
     /// are included in the returned buffer as well.
@@ -1639,7 +3270,7 @@ pub struct BERReaderSeq<'a, 'b> where 'a: 'b {
 impl<'a, 'b> BERReaderSeq<'a, 'b> {
     /// Tells which format we are parsing, BER or DER.
     pub fn mode(&self) -> BERMode {
-        self.inner.mode
+        self.inner.options.mode
     }
 
     /// Generates a new [`BERReader`].
@@ -1706,7 +3337,7 @@ impl<'a, 'b> BERReaderSeq<'a, 'b> {
             T: Eq {
         match self.read_optional(callback)? {
             Some(result) => {
-                if self.inner.mode == BERMode::Der && result == default {
+                if self.inner.options.mode == BERMode::Der && result == default {
                     return Err(
                         ASN1Error::new(ASN1ErrorKind::Invalid));
                 }
@@ -1716,6 +3347,26 @@ impl<'a, 'b> BERReaderSeq<'a, 'b> {
         }
     }
 
+    /// Reads one or more elements with `callback` and also returns the
+    /// exact bytes they were encoded as.
+    ///
+    /// See [`BERReader::read_with_buffer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let data = &[48, 6, 2, 1, 10, 1, 1, 255];
+    /// let (_, buf) = yasna::parse_der(data, |reader| {
+    ///     reader.read_sequence(|reader| {
+    ///         reader.read_with_buffer(|reader| {
+    ///             reader.next().read_i64()?;
+    ///             reader.next().read_bool()
+    ///         })
+    ///     })
+    /// }).unwrap();
+    /// assert_eq!(buf, &[2, 1, 10, 1, 1, 255]);
+    /// ```
     pub fn read_with_buffer<T, F>(&mut self, callback: F)
             -> ASN1Result<(T, &'a [u8])>
             where F: for<'c> FnOnce(
@@ -1755,7 +3406,7 @@ pub struct BERReaderSet<'a, 'b> where 'a: 'b {
 impl<'a, 'b> BERReaderSet<'a, 'b> {
     /// Tells which format we are parsing, BER or DER.
     pub fn mode(&self) -> BERMode {
-        self.impl_ref.mode
+        self.impl_ref.options.mode
     }
 
     /// Generates a new [`BERReader`].
@@ -1768,10 +3419,10 @@ impl<'a, 'b> BERReaderSet<'a, 'b> {
         }) {
             let (_, buf, pos) = self.elements.remove(elem_pos);
             *self.impl_ref = BERReaderImpl::with_pos(
-                buf, pos, self.impl_ref.mode);
+                buf, pos, self.impl_ref.options);
             return Ok(BERReader::new(self.impl_ref))
         } else {
-            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            return Err(ASN1Error::new(ASN1ErrorKind::UnexpectedTag));
         }
     }
 
@@ -1806,7 +3457,7 @@ impl<'a, 'b> BERReaderSet<'a, 'b> {
         }) {
             let (_, buf, pos) = self.elements.remove(elem_pos);
             let mut reader_impl = BERReaderImpl::with_pos(
-                buf, pos, self.impl_ref.mode);
+                buf, pos, self.impl_ref.options);
             let result = callback(BERReader::new(&mut reader_impl))?;
             reader_impl.end_of_buf()?;
             return Ok(Some(result));
@@ -1845,7 +3496,7 @@ impl<'a, 'b> BERReaderSet<'a, 'b> {
             -> ASN1Result<T>
             where F: for<'c> FnOnce(BERReader<'a, 'c>) -> ASN1Result<T>,
             T: Eq {
-        let mode = self.impl_ref.mode;
+        let mode = self.impl_ref.options.mode;
         match self.read_optional(tag_hint, callback)? {
             Some(result) => {
                 if mode == BERMode::Der && result == default {
@@ -1859,6 +3510,240 @@ impl<'a, 'b> BERReaderSet<'a, 'b> {
     }
 }
 
+fn bytes_to_str(bytes: Cow<[u8]>) -> ASN1Result<Cow<str>> {
+    return match bytes {
+        Cow::Borrowed(bytes) => core::str::from_utf8(bytes).map(Cow::Borrowed)
+            .map_err(|_| ASN1Error::new(ASN1ErrorKind::Invalid)),
+        Cow::Owned(bytes) => String::from_utf8(bytes).map(Cow::Owned)
+            .map_err(|_| ASN1Error::new(ASN1ErrorKind::Invalid)),
+    };
+}
+
+fn is_numeric_char(byte: u8) -> bool {
+    byte == b' ' || byte.is_ascii_digit()
+}
+
+fn is_printable_char(byte: u8) -> bool {
+    match byte {
+        b' ' | b'\'' | b'(' | b')' | b'+' | b',' | b'-' | b'.' | b'/' |
+        b':' | b'=' => true,
+        _ => byte.is_ascii_alphanumeric(),
+    }
+}
+
+fn is_ia5_char(byte: u8) -> bool {
+    byte.is_ascii()
+}
+
+fn is_visible_char(byte: u8) -> bool {
+    byte == b' ' || byte.is_ascii_graphic()
+}
+
+/// Removes redundant leading `0x00`/`0xFF` octets from
+/// a two's complement integer encoding.
+fn strip_integer_padding(mut buf: &[u8]) -> &[u8] {
+    while buf.len() >= 2 &&
+            ((buf[0] == 0 && buf[1] < 128) ||
+            (buf[0] == 255 && buf[1] >= 128)) {
+        buf = &buf[1..];
+    }
+    return buf;
+}
+
+/// Folds the contents of a well-formed INTEGER into an integer of
+/// `width` bytes, returned as its two's complement bit pattern.
+fn fold_integer_bytes(buf: &[u8], signed: bool, width: usize)
+        -> ASN1Result<u128> {
+    let mut buf = buf;
+    let mut x = 0;
+    if signed {
+        if buf[0] >= 128 {
+            x = !0;
+        }
+    } else {
+        if buf[0] >= 128 {
+            return Err(ASN1Error::new(ASN1ErrorKind::IntegerOverflow));
+        }
+        if buf.len() == width + 1 && buf[0] == 0 {
+            buf = &buf[1..];
+        }
+    }
+    if buf.len() > width {
+        return Err(ASN1Error::new(ASN1ErrorKind::IntegerOverflow));
+    }
+    for &b in buf {
+        x = (x << 8) | (b as u128);
+    }
+    return Ok(x);
+}
+
+/// Decodes the contents of a binary-encoded REAL (X.690 8.5.7).
+fn decode_binary_real(buf: &[u8], mode: BERMode) -> ASN1Result<f64> {
+    let negative = buf[0] & 64 != 0;
+    let base_bits = match (buf[0] >> 4) & 3 {
+        0 => 1,
+        1 => 3,
+        2 => 4,
+        _ => return Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+    };
+    let scale = ((buf[0] >> 2) & 3) as i64;
+    let (exp_start, exp_len) = match buf[0] & 3 {
+        3 => {
+            if buf.len() < 2 || buf[1] == 0 {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            (2, buf[1] as usize)
+        },
+        e => (1, e as usize + 1),
+    };
+    if buf.len() <= exp_start + exp_len {
+        return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+    let exp_buf = &buf[exp_start..exp_start+exp_len];
+    let mantissa = &buf[exp_start+exp_len..];
+    if mode == BERMode::Der {
+        // X.690 11.3.1: base 2, no scaling, odd mantissa and
+        // the shortest exponent encoding.
+        if base_bits != 1 || scale != 0 ||
+            mantissa[0] == 0 || mantissa[mantissa.len()-1] & 1 == 0 {
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+        }
+        if exp_buf.len() > 1 {
+            let x = ((exp_buf[0] as i8 as i16) << 1) |
+                ((exp_buf[1] >> 7) as i16);
+            if x == 0 || x == -1 {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+        }
+        if buf[0] & 3 == 3 && exp_len <= 3 {
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+        }
+    }
+    // Strip redundant sign-extension octets.
+    let mut exp_buf = exp_buf;
+    while exp_buf.len() > 1 &&
+        ((exp_buf[0] == 0 && exp_buf[1] < 128) ||
+            (exp_buf[0] == 255 && exp_buf[1] >= 128)) {
+        exp_buf = &exp_buf[1..];
+    }
+    if exp_buf.len() > 8 {
+        return Err(ASN1Error::new(ASN1ErrorKind::IntegerOverflow));
+    }
+    let mut exponent = exp_buf[0] as i8 as i64;
+    for &b in exp_buf[1..].iter() {
+        exponent = (exponent << 8) | (b as i64);
+    }
+    // Keep the 64 most significant bits of the mantissa; the rest
+    // only affects rounding, so fold it into a sticky bit.
+    let mantissa = match mantissa.iter().position(|&b| b != 0) {
+        Some(i) => &mantissa[i..],
+        None => return Ok(if negative { -0.0 } else { 0.0 }),
+    };
+    let mut n : u64 = 0;
+    for &b in mantissa.iter().take(8) {
+        n = (n << 8) | (b as u64);
+    }
+    let mut shift = 0;
+    if mantissa.len() > 8 {
+        shift = 8 * (mantissa.len() - 8) as i64;
+        if mantissa[8..].iter().any(|&b| b != 0) {
+            n |= 1;
+        }
+    }
+    let exponent = exponent.checked_mul(base_bits)
+        .and_then(|e| e.checked_add(scale + shift))
+        .ok_or(ASN1Error::new(ASN1ErrorKind::IntegerOverflow))?;
+    let value = scale_by_power_of_two(n as f64, exponent);
+    return Ok(if negative { -value } else { value });
+}
+
+/// Computes `x * 2^exp` without intermediate overflow.
+fn scale_by_power_of_two(mut x: f64, mut exp: i64) -> f64 {
+    while exp > 1023 && x.is_finite() {
+        x *= f64::from_bits(2046 << 52);
+        exp -= 1023;
+    }
+    while exp < -1022 && x != 0.0 {
+        x *= f64::from_bits(1 << 52);
+        exp += 1022;
+    }
+    if !(-1022..=1023).contains(&exp) {
+        // x has already become infinity or zero.
+        return x;
+    }
+    return x * f64::from_bits(((exp + 1023) as u64) << 52);
+}
+
+/// Decodes the contents of a decimal-encoded REAL (X.690 8.5.8).
+fn decode_decimal_real(buf: &[u8], mode: BERMode) -> ASN1Result<f64> {
+    use core::str;
+    let form = buf[0] & 63;
+    if !(1..=3).contains(&form) || (mode == BERMode::Der && form != 3) {
+        return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+    let digits = &buf[1..];
+    if mode == BERMode::Der && !is_canonical_nr3(digits) {
+        return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+    let digits = match digits.iter().position(|&b| b != b' ') {
+        Some(i) => &digits[i..],
+        None => return Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+    };
+    let mut normalized = Vec::with_capacity(digits.len());
+    let mut has_digit = false;
+    for &b in digits.iter() {
+        match b {
+            b'0'..=b'9' => {
+                has_digit = true;
+                normalized.push(b);
+            },
+            b'+' | b'-' | b'.' => normalized.push(b),
+            b',' => normalized.push(b'.'),
+            b'e' | b'E' if form == 3 => normalized.push(b'E'),
+            _ => return Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+        }
+    }
+    if !has_digit || (form == 1 && normalized.contains(&b'.')) ||
+        (form == 2 && !normalized.contains(&b'.')) {
+        return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+    let string = str::from_utf8(&normalized).unwrap();
+    return string.parse::<f64>()
+        .map_err(|_| ASN1Error::new(ASN1ErrorKind::Invalid));
+}
+
+/// Checks the restrictions of X.690 11.3.2 on NR3 REAL encodings,
+/// e.g. `"15.E-1"` or `"-3.E+0"`.
+fn is_canonical_nr3(digits: &[u8]) -> bool {
+    let dot = match digits.iter().position(|&b| b == b'.') {
+        Some(dot) => dot,
+        None => return false,
+    };
+    let (mantissa, rest) = digits.split_at(dot);
+    let mantissa = if mantissa.first() == Some(&b'-') {
+        &mantissa[1..]
+    } else {
+        mantissa
+    };
+    if mantissa.is_empty() || !mantissa.iter().all(|b| b.is_ascii_digit()) ||
+        mantissa[0] == b'0' || mantissa[mantissa.len()-1] == b'0' {
+        return false;
+    }
+    if rest.len() < 3 || rest[1] != b'E' {
+        return false;
+    }
+    let exponent = &rest[2..];
+    if exponent == b"+0" {
+        return true;
+    }
+    let exponent = if exponent[0] == b'-' {
+        &exponent[1..]
+    } else {
+        exponent
+    };
+    return !exponent.is_empty() && exponent[0] != b'0' &&
+        exponent.iter().all(|b| b.is_ascii_digit());
+}
 
 #[cfg(test)]
 mod tests;
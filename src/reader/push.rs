@@ -0,0 +1,186 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::vec::Vec;
+
+use super::{parse_ber_with_options,BERMode,BERReader,BERReaderImpl};
+use super::{BERReaderOptions,ASN1Error,ASN1ErrorKind,ASN1Result};
+use super::super::PCBit;
+use super::super::tags::TAG_EOC;
+
+/// An incremental parser for BER/DER-encoded values arriving in chunks.
+///
+/// Input is handed over with [`feed`](BERPushParser::feed) as it
+/// becomes available, e.g. from a non-blocking socket, and complete
+/// top-level values are taken out with
+/// [`read_next`](BERPushParser::read_next) or
+/// [`next_raw`](BERPushParser::next_raw), which return `None` until
+/// enough bytes have arrived.
+///
+/// Use [`BERReaderOptions::max_total_length`] to bound the buffered
+/// data when reading from an untrusted source.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{BERMode,BERPushParser};
+/// let mut parser = BERPushParser::new(BERMode::Der);
+/// parser.feed(&[2, 1]);
+/// assert_eq!(parser.read_next(|reader| reader.read_i64()), Ok(None));
+/// parser.feed(&[10, 2, 1, 20]);
+/// assert_eq!(parser.read_next(|reader| reader.read_i64()), Ok(Some(10)));
+/// assert_eq!(parser.read_next(|reader| reader.read_i64()), Ok(Some(20)));
+/// assert_eq!(parser.read_next(|reader| reader.read_i64()), Ok(None));
+/// assert!(parser.is_empty());
+/// ```
+#[derive(Debug)]
+pub struct BERPushParser {
+    options: BERReaderOptions,
+    buf: Vec<u8>,
+    // The start of the pending value in `buf`. The consumed part before
+    // it is only dropped once it makes up half of the buffer.
+    start: usize,
+    scan: Scan,
+}
+
+/// How far the pending value has been scanned, kept between calls so that
+/// no byte is scanned twice.
+#[derive(Debug, Clone, Copy, Default)]
+struct Scan {
+    // The position of the next header, or the end of the value, relative
+    // to the start of the value. It may be beyond the buffered data.
+    pos: usize,
+    // The number of open indefinite-length values.
+    depth: usize,
+    // Whether the header of the value itself has been read.
+    started: bool,
+}
+
+impl BERPushParser {
+    /// Constructs a push parser with the default options for `mode`.
+    pub fn new(mode: BERMode) -> Self {
+        BERPushParser::with_options(BERReaderOptions::new(mode))
+    }
+
+    /// Constructs a push parser with the given options.
+    pub fn with_options(options: BERReaderOptions) -> Self {
+        return BERPushParser {
+            options,
+            buf: Vec::new(),
+            start: 0,
+            scan: Scan::default(),
+        };
+    }
+
+    /// Appends a chunk of input.
+    pub fn feed(&mut self, data: &[u8]) {
+        if self.start > 0 && self.start >= self.buf.len() / 2 {
+            self.buf.drain(..self.start);
+            self.start = 0;
+        }
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Tells whether no input is pending. When the input ends, a
+    /// `false` here means the last value was truncated.
+    pub fn is_empty(&self) -> bool {
+        self.buf.len() == self.start
+    }
+
+    /// Takes the next complete top-level value as raw bytes, including
+    /// its tag and length. Returns `None` if more input is needed.
+    ///
+    /// Only the framing is checked here. Errors are sticky: the
+    /// offending data stays in the buffer.
+    pub fn next_raw(&mut self) -> ASN1Result<Option<&[u8]>> {
+        let buf = &self.buf[self.start..];
+        loop {
+            let scan = &mut self.scan;
+            if scan.started && scan.depth == 0 {
+                if buf.len() < scan.pos {
+                    return Ok(None);
+                }
+                break;
+            }
+            if buf.len() <= scan.pos {
+                return Ok(None);
+            }
+            match Self::scan_header(buf, self.options, scan) {
+                Ok(()) => {},
+                Err(ref e) if e.kind() == ASN1ErrorKind::Eof => return Ok(None),
+                Err(e) => {
+                    // Scan the value again to locate the error, as
+                    // the reader does.
+                    let mut inner = BERReaderImpl::new(buf, self.options);
+                    return Err(match inner.skip_general() {
+                        Err(located) => {
+                            if located.kind() == e.kind() { located } else { e }
+                        },
+                        Ok(_) => e,
+                    });
+                },
+            }
+        }
+        let start = self.start;
+        self.start += self.scan.pos;
+        self.scan = Scan::default();
+        return Ok(Some(&self.buf[start..self.start]));
+    }
+
+    /// Reads the header at `scan.pos` and advances `scan` past it, and
+    /// past the contents if they have a definite length. `scan` is left
+    /// untouched on errors.
+    fn scan_header(buf: &[u8], options: BERReaderOptions, scan: &mut Scan)
+            -> ASN1Result<()> {
+        let mut inner = BERReaderImpl::with_pos(buf, scan.pos, options);
+        let (tag, pcbit) = inner.read_identifier()?;
+        if tag == TAG_EOC {
+            // EOC is a pair of zero bytes.
+            if scan.depth == 0 || inner.read_u8()? != 0 {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            inner.check_total_length(0, inner.pos)?;
+            scan.depth -= 1;
+            scan.pos = inner.pos;
+            return Ok(());
+        }
+        let (pos, depth) = match inner.read_length()? {
+            Some(length) => {
+                let pos = inner.pos.checked_add(length).ok_or_else(
+                    || ASN1Error::new(ASN1ErrorKind::LengthOverflow))?;
+                (pos, scan.depth)
+            },
+            None => {
+                if pcbit != PCBit::Constructed ||
+                        !options.allow_indefinite_length {
+                    return Err(ASN1Error::new(ASN1ErrorKind::InvalidLength));
+                }
+                (inner.pos, scan.depth + 1)
+            },
+        };
+        inner.check_total_length(0, pos)?;
+        scan.pos = pos;
+        scan.depth = depth;
+        scan.started = true;
+        return Ok(());
+    }
+
+    /// Takes and decodes the next complete top-level value.
+    /// Returns `None` if more input is needed.
+    ///
+    /// Error offsets are relative to the start of the value.
+    pub fn read_next<T, F>(&mut self, callback: F) -> ASN1Result<Option<T>>
+            where F: for<'a, 'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T> {
+        let options = self.options;
+        match self.next_raw()? {
+            Some(buf) => {
+                let result = parse_ber_with_options(buf, options, callback)?;
+                return Ok(Some(result));
+            },
+            None => return Ok(None),
+        }
+    }
+}
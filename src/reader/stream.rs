@@ -0,0 +1,243 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use alloc::vec::Vec;
+
+use super::{parse_ber_with_options,BERMode,BERReader,BERReaderOptions};
+use super::{ASN1Error,ASN1ErrorKind,ASN1Result,BER_READER_STACK_DEPTH};
+
+/// A reader front-end pulling BER/DER-encoded values from an
+/// [`io::Read`](std::io::Read).
+///
+/// Top-level values are read one at a time: the framing (tags and
+/// lengths) is parsed directly from the stream, so only the value
+/// currently being decoded is held in memory. Use
+/// [`BERReaderOptions::max_total_length`] to bound that memory when
+/// reading from an untrusted source.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{BERMode,BERStreamReader};
+/// let data : &[u8] = &[2, 1, 10, 2, 1, 20];
+/// let mut stream = BERStreamReader::new(data, BERMode::Der);
+/// let mut values = Vec::new();
+/// while let Some(i) = stream.read_next(|reader| reader.read_i64())? {
+///     values.push(i);
+/// }
+/// assert_eq!(values, vec![10, 20]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Features
+///
+/// This struct is enabled by `std` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["std"] }
+/// ```
+#[derive(Debug)]
+pub struct BERStreamReader<R> {
+    inner: BufReader<R>,
+    options: BERReaderOptions,
+    buf: Vec<u8>,
+}
+
+impl<R: Read> BERStreamReader<R> {
+    /// Constructs a stream reader with the default options for `mode`.
+    pub fn new(inner: R, mode: BERMode) -> Self {
+        BERStreamReader::with_options(inner, BERReaderOptions::new(mode))
+    }
+
+    /// Constructs a stream reader with the given options.
+    pub fn with_options(inner: R, options: BERReaderOptions) -> Self {
+        return BERStreamReader {
+            inner: BufReader::new(inner),
+            options,
+            buf: Vec::new(),
+        };
+    }
+
+    /// Unwraps the underlying reader. Data already buffered
+    /// internally is lost.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Reads the next top-level value as raw bytes, including its tag
+    /// and length. Returns `None` if the stream ends before the value.
+    pub fn next_raw(&mut self) -> io::Result<Option<&[u8]>> {
+        self.buf.clear();
+        if !self.read_first()? {
+            return Ok(None);
+        }
+        if self.read_element(0)? {
+            // A top-level end-of-contents marker.
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid).into());
+        }
+        return Ok(Some(&self.buf));
+    }
+
+    /// Reads and decodes the next top-level value. Returns `None`
+    /// if the stream ends before the value.
+    pub fn read_next<T, F>(&mut self, callback: F) -> io::Result<Option<T>>
+            where F: for<'a, 'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T> {
+        if self.next_raw()?.is_none() {
+            return Ok(None);
+        }
+        let result = parse_ber_with_options(&self.buf, self.options,
+            callback)?;
+        return Ok(Some(result));
+    }
+
+    // Reads the first octet of a top-level value, if any.
+    fn read_first(&mut self) -> io::Result<bool> {
+        let mut first = [0; 1];
+        loop {
+            match self.inner.read(&mut first) {
+                Ok(0) => return Ok(false),
+                Ok(_) => break,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+                Err(e) => return Err(e),
+            }
+        }
+        self.buf.push(first[0]);
+        return Ok(true);
+    }
+
+    fn read_u8(&mut self) -> io::Result<u8> {
+        self.check_total_length(1)?;
+        let mut b = [0; 1];
+        self.inner.read_exact(&mut b)?;
+        self.buf.push(b[0]);
+        return Ok(b[0]);
+    }
+
+    // Reads an element whose first octet is already in the buffer.
+    // Returns whether it was an end-of-contents marker.
+    fn read_element(&mut self, depth: usize) -> io::Result<bool> {
+        let (tagbyte, length) = match self.read_header(depth)? {
+            (_, None) => {
+                loop {
+                    self.read_u8()?;
+                    if self.read_element(depth + 1)? {
+                        return Ok(false);
+                    }
+                }
+            },
+            (tagbyte, Some(length)) => (tagbyte, length),
+        };
+        self.check_total_length(length)?;
+        // The buffer grows with the data actually read rather than
+        // with the declared length.
+        let read = (&mut self.inner).take(length)
+            .read_to_end(&mut self.buf)?;
+        if (read as u64) < length {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        return Ok(tagbyte == 0 && length == 0);
+    }
+
+    // Checks that `additional` more octets fit in the buffered value.
+    fn check_total_length(&self, additional: u64) -> io::Result<()> {
+        if let Some(max_total_length) = self.options.max_total_length {
+            if self.buf.len() as u64 + additional > max_total_length as u64 {
+                return Err(invalid_length());
+            }
+        }
+        return Ok(());
+    }
+
+    // Reads the identifier and length octets of an element whose first
+    // octet is the last one in the buffer.
+    fn read_header(&mut self, depth: usize)
+            -> io::Result<(u8, Option<u64>)> {
+        let tagbyte = self.buf[self.buf.len() - 1];
+        if tagbyte & 31 == 31 {
+            while self.read_u8()? & 128 != 0 {}
+        }
+        let lbyte = self.read_u8()?;
+        if lbyte == 128 {
+            if tagbyte & 32 == 0 || !self.options.allow_indefinite_length {
+                return Err(invalid_length());
+            }
+            if depth > BER_READER_STACK_DEPTH {
+                return Err(
+                    ASN1Error::new(ASN1ErrorKind::StackOverflow).into());
+            }
+            return Ok((tagbyte, None));
+        }
+        let length = if lbyte & 128 == 0 {
+            lbyte as u64
+        } else {
+            let mut length : u64 = 0;
+            for _ in 0..(lbyte & 127) {
+                let b = self.read_u8()? as u64;
+                length = length.checked_mul(256).ok_or_else(length_overflow)?
+                    + b;
+            }
+            length
+        };
+        if let Some(max_length) = self.options.max_length {
+            if length > max_length as u64 {
+                return Err(invalid_length());
+            }
+        }
+        return Ok((tagbyte, Some(length)));
+    }
+}
+
+impl<R: Read + Seek> BERStreamReader<R> {
+    /// Skips the next top-level value without reading its contents.
+    /// Returns `false` if the stream ends before the value.
+    ///
+    /// Definite-length contents are seeked over, which makes skipping
+    /// large uninteresting values cheap. As a consequence, truncation
+    /// inside the skipped contents is not detected.
+    pub fn skip_next(&mut self) -> io::Result<bool> {
+        self.buf.clear();
+        if !self.read_first()? {
+            return Ok(false);
+        }
+        let is_eoc = self.skip_element(0)?;
+        self.buf.clear();
+        if is_eoc {
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid).into());
+        }
+        return Ok(true);
+    }
+
+    fn skip_element(&mut self, depth: usize) -> io::Result<bool> {
+        match self.read_header(depth)? {
+            (_, None) => {
+                loop {
+                    self.buf.clear();
+                    self.read_u8()?;
+                    if self.skip_element(depth + 1)? {
+                        return Ok(false);
+                    }
+                }
+            },
+            (tagbyte, Some(length)) => {
+                if length >= 1 << 63 {
+                    return Err(length_overflow());
+                }
+                self.inner.seek(SeekFrom::Current(length as i64))?;
+                return Ok(tagbyte == 0 && length == 0);
+            },
+        }
+    }
+}
+
+fn invalid_length() -> io::Error {
+    ASN1Error::new(ASN1ErrorKind::InvalidLength).into()
+}
+
+fn length_overflow() -> io::Error {
+    ASN1Error::new(ASN1ErrorKind::LengthOverflow).into()
+}
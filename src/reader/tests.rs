@@ -10,8 +10,8 @@
 #[cfg(feature = "num-bigint")]
 use num_bigint::{BigUint, BigInt};
 
-use super::super::Tag;
-use super::super::tags::TAG_SEQUENCE;
+use super::super::{PCBit,Tag,TagClass};
+use super::super::tags::{TAG_NULL,TAG_SEQUENCE};
 use super::*;
 
 #[test]
@@ -177,6 +177,12 @@ fn test_general_read_u64_ok(mode: BERMode) {
             reader.read_u64()
         }).unwrap();
         assert_eq!(value, evalue);
+        let mut enum_data = Vec::from(&[10][..]);
+        enum_data.extend_from_slice(&data[1..]);
+        let value = parse_ber_general(&enum_data, mode, |reader| {
+            reader.read_enum_u64()
+        }).unwrap();
+        assert_eq!(value, evalue);
     }
 }
 
@@ -196,6 +202,182 @@ fn test_general_read_u64_err(mode: BERMode) {
         parse_ber_general(data, mode, |reader| {
             reader.read_u64()
         }).unwrap_err();
+        parse_ber_general(data, mode, |reader| {
+            reader.read_enum_u64()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_der_read_i128_ok() {
+    test_general_read_i128_ok(BERMode::Der);
+}
+
+#[test]
+fn test_der_read_i128_err() {
+    test_general_read_i128_err(BERMode::Der);
+}
+
+#[test]
+fn test_ber_read_i128_ok() {
+    test_general_read_i128_ok(BERMode::Ber);
+}
+
+#[test]
+fn test_ber_read_i128_err() {
+    test_general_read_i128_err(BERMode::Ber);
+}
+
+#[test]
+fn test_der_read_u128_ok() {
+    test_general_read_u128_ok(BERMode::Der);
+}
+
+#[test]
+fn test_der_read_u128_err() {
+    test_general_read_u128_err(BERMode::Der);
+}
+
+#[test]
+fn test_ber_read_u128_ok() {
+    test_general_read_u128_ok(BERMode::Ber);
+}
+
+#[test]
+fn test_ber_read_u128_err() {
+    test_general_read_u128_err(BERMode::Ber);
+}
+
+#[test]
+fn test_der_read_int_bytes_ok() {
+    test_general_read_int_bytes_ok(BERMode::Der);
+}
+
+#[test]
+fn test_der_read_int_bytes_err() {
+    test_general_read_int_bytes_err(BERMode::Der);
+}
+
+#[test]
+fn test_ber_read_int_bytes_ok() {
+    test_general_read_int_bytes_ok(BERMode::Ber);
+}
+
+#[test]
+fn test_ber_read_int_bytes_err() {
+    test_general_read_int_bytes_err(BERMode::Ber);
+}
+
+fn test_general_read_int_bytes_ok(mode: BERMode) {
+    let tests : &[(&[u8], &[u8])] = &[
+        (&[0], &[2, 1, 0]),
+        (&[127], &[2, 1, 127]),
+        (&[0, 128], &[2, 2, 0, 128]),
+        (&[255], &[2, 1, 255]),
+        (&[128, 0], &[2, 2, 128, 0]),
+        (&[1, 0, 0, 0, 0, 0, 0, 0, 0], &[2, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_ber_general(data, mode, |reader| {
+            reader.read_int_bytes()
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+}
+
+fn test_general_read_int_bytes_err(mode: BERMode) {
+    let tests : &[&[u8]] = &[
+        &[], &[2], &[2, 0], &[2, 1], &[2, 2, 0, 127], &[2, 2, 255, 128],
+        &[34, 1, 0], &[3, 1, 0],
+    ];
+    for &data in tests {
+        parse_ber_general(data, mode, |reader| {
+            reader.read_int_bytes()
+        }).unwrap_err();
+    }
+}
+
+fn test_general_read_i128_ok(mode: BERMode) {
+    let tests : &[(i128, &[u8])] = &[
+        (-170141183460469231731687303715884105728,
+            &[2, 16, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (-18446744073709551616, &[2, 9, 255, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (-9223372036854775809,
+            &[2, 9, 255, 127, 255, 255, 255, 255, 255, 255, 255]),
+        (-129, &[2, 2, 255, 127]),
+        (-128, &[2, 1, 128]),
+        (-1, &[2, 1, 255]),
+        (0, &[2, 1, 0]),
+        (1, &[2, 1, 1]),
+        (127, &[2, 1, 127]),
+        (128, &[2, 2, 0, 128]),
+        (9223372036854775808, &[2, 9, 0, 128, 0, 0, 0, 0, 0, 0, 0]),
+        (18446744073709551616, &[2, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (170141183460469231731687303715884105727,
+            &[2, 16, 127, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_ber_general(data, mode, |reader| {
+            reader.read_i128()
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+}
+
+fn test_general_read_i128_err(mode: BERMode) {
+    let tests : &[&[u8]] = &[
+        &[], &[2], &[2, 0], &[2, 2, 0], &[2, 2, 255, 128], &[2, 2, 0, 127],
+        &[2, 17, 0, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        &[2, 17, 255, 127, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255],
+        &[4, 1, 0],
+    ];
+    for &data in tests {
+        parse_ber_general(data, mode, |reader| {
+            reader.read_i128()
+        }).unwrap_err();
+    }
+}
+
+fn test_general_read_u128_ok(mode: BERMode) {
+    let tests : &[(u128, &[u8])] = &[
+        (0, &[2, 1, 0]),
+        (1, &[2, 1, 1]),
+        (127, &[2, 1, 127]),
+        (128, &[2, 2, 0, 128]),
+        (18446744073709551615,
+            &[2, 9, 0, 255, 255, 255, 255, 255, 255, 255, 255]),
+        (18446744073709551616, &[2, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (170141183460469231731687303715884105727,
+            &[2, 16, 127, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255]),
+        (170141183460469231731687303715884105728,
+            &[2, 17, 0, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (340282366920938463463374607431768211455,
+            &[2, 17, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_ber_general(data, mode, |reader| {
+            reader.read_u128()
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+}
+
+fn test_general_read_u128_err(mode: BERMode) {
+    let tests : &[&[u8]] = &[
+        &[], &[2], &[2, 0], &[2, 2, 0], &[2, 2, 0, 127], &[2, 1, 128],
+        &[2, 1, 255],
+        &[2, 17, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        &[2, 18, 0, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        &[4, 1, 0],
+    ];
+    for &data in tests {
+        parse_ber_general(data, mode, |reader| {
+            reader.read_u128()
+        }).unwrap_err();
     }
 }
 
@@ -712,6 +894,146 @@ fn test_general_read_biguint_err(mode: BERMode) {
     }
 }
 
+#[test]
+fn test_der_read_bitvec_bytes_ok() {
+    let tests : &[((&[u8], usize), &[u8])] = &[
+        ((&[], 0), &[3, 1, 0]),
+        ((&[128], 1), &[3, 2, 7, 128]),
+        ((&[255], 8), &[3, 2, 0, 255]),
+        ((&[117, 13, 64], 18), &[3, 4, 6, 117, 13, 64]),
+    ];
+    for &((ebytes, elen), data) in tests {
+        let (bytes, len) = parse_der(data, |reader| {
+            reader.read_bitvec_bytes()
+        }).unwrap();
+        assert_eq!((&bytes[..], len), (ebytes, elen));
+    }
+}
+
+#[test]
+fn test_der_read_bitvec_bytes_err() {
+    let tests : &[&[u8]] = &[
+        &[], &[3], &[3, 0], &[3, 1, 1], &[3, 1, 8], &[3, 2, 8, 0],
+        &[3, 4, 6, 117, 13, 65], &[4, 2, 0, 255],
+        &[35, 128, 3, 2, 0, 117, 0, 0],
+        &[35, 4, 3, 2, 0, 117],
+    ];
+    for &data in tests {
+        parse_der(data, |reader| {
+            reader.read_bitvec_bytes()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_ber_read_bitvec_bytes_ok() {
+    let tests : &[((&[u8], usize), &[u8])] = &[
+        ((&[], 0), &[3, 1, 0]),
+        ((&[117, 13, 64], 18), &[3, 4, 6, 117, 13, 64]),
+        ((&[117, 13, 65], 18), &[3, 4, 6, 117, 13, 65]),
+        ((&[117, 13, 64], 18), &[35, 9, 3, 3, 0, 117, 13, 3, 2, 6, 64]),
+        ((&[117, 13, 64], 18),
+            &[35, 128, 3, 2, 0, 117, 3, 3, 6, 13, 64, 0, 0]),
+    ];
+    for &((ebytes, elen), data) in tests {
+        let (bytes, len) = parse_ber(data, |reader| {
+            reader.read_bitvec_bytes()
+        }).unwrap();
+        assert_eq!((&bytes[..], len), (ebytes, elen));
+    }
+}
+
+#[test]
+fn test_ber_read_bitvec_bytes_err() {
+    let tests : &[&[u8]] = &[
+        &[], &[3], &[3, 0], &[3, 1, 1], &[3, 1, 8], &[3, 2, 8, 0],
+        &[35, 8, 3, 2, 6, 117, 3, 2, 0, 13],
+        &[35, 128, 3, 2, 6, 117, 3, 2, 0, 13, 0, 0],
+    ];
+    for &data in tests {
+        parse_ber(data, |reader| {
+            reader.read_bitvec_bytes()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_ber_read_bitstring() {
+    use super::super::models::BitString;
+    let tests : &[((&[u8], usize), &[u8])] = &[
+        ((&[], 0), &[3, 1, 0]),
+        ((&[117, 13, 64], 18), &[3, 4, 6, 117, 13, 64]),
+        ((&[117, 13, 64], 18), &[3, 4, 6, 117, 13, 65]),
+        ((&[117, 13, 64], 18),
+            &[35, 128, 3, 2, 0, 117, 3, 3, 6, 13, 64, 0, 0]),
+    ];
+    for &((ebytes, elen), data) in tests {
+        let bits = parse_ber(data, |reader| {
+            reader.read_bitstring()
+        }).unwrap();
+        assert_eq!((bits.as_bytes(), bits.len()), (ebytes, elen));
+        let der = super::super::encode_der(&bits);
+        let expected = BitString::from_bytes_and_len(ebytes.to_vec(), elen);
+        assert_eq!(parse_der(&der, |reader| reader.read_bitstring()).unwrap(),
+            expected);
+    }
+}
+
+#[test]
+fn test_der_read_bitstring_named_bits() {
+    // KeyUsage ::= BIT STRING { digitalSignature (0), nonRepudiation (1),
+    //     keyEncipherment (2), ..., keyCertSign (5), cRLSign (6), ... }
+    let bits = parse_der(&[3, 2, 1, 134], |reader| {
+        reader.read_bitstring()
+    }).unwrap();
+    assert_eq!(bits.len(), 7);
+    assert_eq!(bits.get(0), Some(true));
+    assert_eq!(bits.get(1), Some(false));
+    assert_eq!(bits.get(5), Some(true));
+    assert_eq!(bits.get(6), Some(true));
+    assert_eq!(bits.get(7), None);
+    for &data in &[&[3, 2, 8, 134][..], &[3, 2, 1, 135][..]] {
+        parse_der(data, |reader| reader.read_bitstring()).unwrap_err();
+    }
+}
+
+#[test]
+fn test_read_time_fields() {
+    let tests : &[(&[u8], bool)] = &[
+        (b"\x17\x0d820102120034Z", true),
+        (b"\x17\x0b8201021200Z", false),
+        (b"\x17\x0f8201021200-0500", false),
+    ];
+    for &(data, der) in tests {
+        let time = parse_ber(data, |reader| {
+            reader.read_utctime_fields()
+        }).unwrap();
+        assert_eq!(time.to_bytes(), &data[2..]);
+        assert_eq!(parse_der(data, |reader| {
+            reader.read_utctime_fields()
+        }).is_ok(), der);
+    }
+    let tests : &[(&[u8], bool)] = &[
+        (b"\x18\x1119851106210627.3Z", true),
+        (b"\x18\x1219851106210627.30Z", false),
+        (b"\x18\x1119851106210627,3Z", false),
+        (b"\x18\x0d198511062106Z", false),
+        (b"\x18\x0e19851106210627", false),
+    ];
+    for &(data, der) in tests {
+        let time = parse_ber(data, |reader| {
+            reader.read_generalized_time_fields()
+        }).unwrap();
+        assert_eq!(time.to_bytes().len(), data.len() - 2);
+        assert_eq!(parse_der(data, |reader| {
+            reader.read_generalized_time_fields()
+        }).is_ok(), der);
+    }
+    parse_ber(b"\x17\x0b8213021200Z", |reader| {
+        reader.read_utctime_fields()
+    }).unwrap_err();
+}
+
 #[test]
 fn test_der_read_bytes_ok() {
     let tests : &[(&[u8], &[u8])] = &[
@@ -789,6 +1111,31 @@ fn test_ber_read_bytes_err() {
     }
 }
 
+#[test]
+fn test_read_bytes_long_length() {
+    let contents = [7u8; 256];
+    let tests : &[(&[u8], usize, bool)] = &[
+        (&[4, 129, 128], 128, true),
+        (&[4, 130, 1, 0], 256, true),
+        (&[4, 129, 5], 5, false),
+        (&[4, 130, 0, 128], 128, false),
+        (&[4, 131, 0, 1, 0], 256, false),
+        (&[4, 132, 0, 0, 0, 5], 5, false),
+    ];
+    for &(header, len, der_ok) in tests {
+        let mut data = header.to_vec();
+        data.extend_from_slice(&contents[..len]);
+        let value = parse_ber(&data, |reader| {
+            reader.read_bytes()
+        }).unwrap();
+        assert_eq!(value, &contents[..len]);
+        let result = parse_der(&data, |reader| {
+            reader.read_bytes()
+        });
+        assert_eq!(result.is_ok(), der_ok);
+    }
+}
+
 #[test]
 fn test_der_read_null_ok() {
     let value = parse_der(&[5, 0], |reader| {
@@ -798,37 +1145,453 @@ fn test_der_read_null_ok() {
 }
 
 #[test]
-fn test_der_read_null_err() {
+fn test_der_read_null_err() {
+    let tests : &[&[u8]] = &[
+        &[], &[5], &[0, 0], &[0, 1, 0], &[2, 1, 0], &[37, 0], &[69, 0],
+        &[5, 128, 0], &[37, 128, 0], &[5, 1, 0], &[5, 2, 0, 0],
+        &[5, 1], &[5, 0, 1],
+    ];
+    for &data in tests {
+        parse_der(data, |reader| {
+            reader.read_null()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_ber_read_null_ok() {
+    let value = parse_ber(&[5, 0], |reader| {
+        reader.read_null()
+    }).unwrap();
+    assert_eq!(value, ());
+}
+
+#[test]
+fn test_ber_read_null_err() {
+    let tests : &[&[u8]] = &[
+        &[], &[5], &[0, 0], &[0, 1, 0], &[2, 1, 0], &[37, 0], &[69, 0],
+        &[5, 128, 0], &[37, 128, 0], &[5, 1, 0], &[5, 2, 0, 0],
+        &[5, 1], &[5, 0, 1],
+    ];
+    for &data in tests {
+        parse_ber(data, |reader| {
+            reader.read_null()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_der_read_relative_oid() {
+    let tests : &[(&[u64], &[u8])] = &[
+        (&[0], &[13, 1, 0]),
+        (&[8571, 3, 2], &[13, 4, 194, 123, 3, 2]),
+        (&[16384], &[13, 3, 129, 128, 0]),
+        (&[18446744073709551615],
+            &[13, 10, 129, 255, 255, 255, 255, 255, 255, 255, 255, 127]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_der(data, |reader| {
+            reader.read_relative_oid()
+        }).unwrap();
+        assert_eq!(&value.components()[..], evalue);
+    }
+    let tests : &[&[u8]] = &[
+        &[13, 0], &[13, 1, 128], &[13, 2, 128, 1], &[13, 2, 1, 129],
+        &[45, 1, 1], &[13, 11, 130, 128, 128, 128, 128, 128, 128, 128, 128,
+            128, 0],
+    ];
+    for &data in tests {
+        parse_der(data, |reader| {
+            reader.read_relative_oid()
+        }).unwrap_err();
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn test_der_read_big_oid() {
+    use alloc::string::ToString;
+    let tests : &[(&str, &[u8])] = &[
+        ("0.0", &[6, 1, 0]),
+        ("1.39", &[6, 1, 79]),
+        ("2.0", &[6, 1, 80]),
+        ("1.2.840.113549.1.1", &[6, 8, 42, 134, 72, 134, 247, 13, 1, 1]),
+        ("1.2.16384", &[6, 4, 42, 129, 128, 0]),
+        ("2.25.329800735698586629295641978511506172918",
+            &[6, 20, 105, 131, 240, 157, 167, 235, 207, 222, 224, 199, 161,
+            167, 178, 192, 148, 140, 200, 249, 215, 118]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_der(data, |reader| {
+            reader.read_big_oid()
+        }).unwrap();
+        assert_eq!(value.to_string(), evalue);
+    }
+    let tests : &[&[u8]] = &[
+        &[6, 0], &[6, 1, 128], &[6, 2, 128, 1], &[6, 2, 1, 129],
+        &[6, 3, 42, 128, 1], &[38, 1, 1],
+    ];
+    for &data in tests {
+        parse_der(data, |reader| {
+            reader.read_big_oid()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_der_read_embedded_pdv() {
+    use super::super::models::{ObjectIdentifier,PdvIdentification};
+    let tests : Vec<(PdvIdentification, &[u8])> = alloc::vec![
+        (PdvIdentification::Syntaxes {
+            abstract_syntax: ObjectIdentifier::from_slice(&[2, 1, 1]),
+            transfer_syntax: ObjectIdentifier::from_slice(&[1, 0, 8571]),
+        }, &[43, 16, 160, 11, 160, 9, 128, 2, 81, 1, 129, 3, 40, 194, 123,
+            130, 1, 65]),
+        (PdvIdentification::Syntax(ObjectIdentifier::from_slice(&[2, 1, 1])),
+            &[43, 9, 160, 4, 129, 2, 81, 1, 130, 1, 65]),
+        (PdvIdentification::PresentationContextId(3),
+            &[43, 8, 160, 3, 130, 1, 3, 130, 1, 65]),
+        (PdvIdentification::ContextNegotiation {
+            presentation_context_id: 3,
+            transfer_syntax: ObjectIdentifier::from_slice(&[2, 1, 1]),
+        }, &[43, 14, 160, 9, 163, 7, 128, 1, 3, 129, 2, 81, 1, 130, 1, 65]),
+        (PdvIdentification::TransferSyntax(
+            ObjectIdentifier::from_slice(&[2, 1, 1])),
+            &[43, 9, 160, 4, 132, 2, 81, 1, 130, 1, 65]),
+        (PdvIdentification::Fixed, &[43, 7, 160, 2, 133, 0, 130, 1, 65]),
+    ];
+    for (eidentification, data) in tests {
+        let value = parse_der(data, |reader| {
+            reader.read_embedded_pdv()
+        }).unwrap();
+        assert_eq!(value.identification, eidentification);
+        assert_eq!(value.data_value, b"A");
+    }
+    let tests : &[&[u8]] = &[
+        &[43, 7, 160, 2, 134, 0, 130, 1, 65],
+        &[43, 4, 160, 2, 133, 0],
+        &[43, 7, 128, 2, 133, 0, 130, 1, 65],
+        &[48, 7, 160, 2, 133, 0, 130, 1, 65],
+    ];
+    for &data in tests {
+        parse_der(data, |reader| {
+            reader.read_embedded_pdv()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_read_duration() {
+    let value = parse_der(b"\x1f\x22\x0eP1Y2M3DT4H5M6S", |reader| {
+        reader.read_duration()
+    }).unwrap();
+    assert_eq!((value.years, value.months, value.days), (1, 2, 3));
+    assert_eq!((value.hours, value.minutes, value.seconds), (4, 5, 6));
+    let value = parse_ber(b"\x1f\x22\x04PT0S", |reader| {
+        reader.read_duration()
+    }).unwrap();
+    assert_eq!(value, Default::default());
+    parse_der(b"\x1f\x22\x04PT0S", |reader| {
+        reader.read_duration()
+    }).unwrap_err();
+    parse_der(b"\x1f\x22\x03P2W", |reader| {
+        reader.read_duration()
+    }).unwrap_err();
+}
+
+#[test]
+fn test_der_read_real_ok() {
+    let tests : &[(f64, &[u8])] = &[
+        (0.0, &[9, 0]),
+        (1.0, &[9, 3, 128, 0, 1]),
+        (-1.0, &[9, 3, 192, 0, 1]),
+        (1.5, &[9, 3, 128, 255, 3]),
+        (-0.375, &[9, 3, 192, 253, 3]),
+        (1.0e300, &[9, 10, 129, 3, 178, 5, 249, 15, 34, 0, 29, 103]),
+        (f64::from_bits(1), &[9, 4, 129, 251, 206, 1]),
+        (f64::from_bits(764 << 52), &[9, 4, 129, 254, 253, 1]),
+        (core::f64::INFINITY, &[9, 4, 129, 4, 0, 1]),
+        (1.5, &[9, 7, 3, 49, 53, 46, 69, 45, 49]),
+        (-3.0, &[9, 7, 3, 45, 51, 46, 69, 43, 48]),
+        (core::f64::INFINITY, &[9, 1, 64]),
+        (core::f64::NEG_INFINITY, &[9, 1, 65]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_der(data, |reader| {
+            reader.read_real()
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+    let value = parse_der(&[9, 1, 66], |reader| {
+        reader.read_real()
+    }).unwrap();
+    assert!(value.is_nan());
+    let value = parse_der(&[9, 1, 67], |reader| {
+        reader.read_real()
+    }).unwrap();
+    assert!(value == 0.0 && value.is_sign_negative());
+}
+
+#[test]
+fn test_der_read_real_err() {
+    let tests : &[&[u8]] = &[
+        &[], &[9], &[41, 0], &[9, 1, 128], &[9, 2, 128, 0],
+        &[9, 1, 68], &[9, 2, 64, 0], &[9, 3, 176, 0, 1],
+        &[9, 3, 128, 0, 0], &[9, 3, 128, 0, 2], &[9, 4, 128, 0, 0, 1],
+        &[9, 3, 144, 1, 1], &[9, 3, 132, 0, 1], &[9, 4, 129, 0, 1, 1],
+        &[9, 4, 131, 1, 0, 1], &[9, 4, 129, 255, 255, 1],
+        &[9, 4, 1, 49, 50, 51], &[9, 4, 2, 49, 46, 53],
+        &[9, 6, 3, 49, 53, 69, 45, 49], &[9, 8, 3, 49, 53, 46, 69, 45, 48, 49],
+        &[9, 8, 3, 49, 53, 48, 46, 69, 45, 50], &[9, 7, 3, 48, 49, 46, 69, 43, 48],
+        &[9, 7, 3, 49, 46, 69, 43, 49],
+    ];
+    for &data in tests {
+        parse_der(data, |reader| {
+            reader.read_real()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_ber_read_real_ok() {
+    let tests : &[(f64, &[u8])] = &[
+        (0.0, &[9, 0]),
+        (1.5, &[9, 3, 128, 255, 3]),
+        (6.0, &[9, 3, 128, 1, 3]),
+        (8.0, &[9, 3, 144, 1, 1]),
+        (32.0, &[9, 3, 164, 1, 1]),
+        (1.0, &[9, 4, 128, 0, 0, 1]),
+        (1.0, &[9, 4, 129, 0, 0, 1]),
+        (1.0, &[9, 4, 131, 1, 0, 1]),
+        (1.0, &[9, 13, 131, 10, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 251, 32]),
+        (123.0, &[9, 6, 1, 32, 32, 49, 50, 51]),
+        (-1.5, &[9, 5, 2, 45, 49, 44, 53]),
+        (1.5, &[9, 7, 3, 48, 46, 49, 53, 101, 49]),
+        (1.5, &[9, 7, 3, 49, 53, 46, 69, 45, 49]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_ber(data, |reader| {
+            reader.read_real()
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+    let value = parse_ber(&[9, 3, 192, 0, 0], |reader| {
+        reader.read_real()
+    }).unwrap();
+    assert!(value == 0.0 && value.is_sign_negative());
+}
+
+#[test]
+fn test_ber_read_real_err() {
+    let tests : &[&[u8]] = &[
+        &[], &[9], &[41, 0], &[9, 1, 128], &[9, 2, 128, 0],
+        &[9, 1, 68], &[9, 2, 64, 0], &[9, 3, 176, 0, 1],
+        &[9, 2, 131, 0], &[9, 3, 131, 2, 0],
+        &[9, 12, 131, 9, 127, 255, 255, 255, 255, 255, 255, 255, 255, 1],
+        &[9, 4, 0, 49, 50, 51], &[9, 4, 4, 49, 50, 51],
+        &[9, 4, 1, 49, 46, 53], &[9, 3, 2, 49, 53], &[9, 3, 1, 32, 32],
+        &[9, 4, 2, 49, 69, 53], &[9, 4, 3, 49, 120, 53],
+    ];
+    for &data in tests {
+        parse_ber(data, |reader| {
+            reader.read_real()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_read_strings_ok() {
+    let value = parse_der(&[12, 3, 226, 130, 172], |reader| {
+        reader.read_utf8string()
+    }).unwrap();
+    assert_eq!(value, "\u{20ac}");
+    let value = parse_der(&[18, 3, 49, 32, 50], |reader| {
+        reader.read_numeric_string()
+    }).unwrap();
+    assert_eq!(value, "1 2");
+    let value = parse_der(&[19, 5, 65, 39, 40, 61, 122], |reader| {
+        reader.read_printable_string()
+    }).unwrap();
+    assert_eq!(value, "A'(=z");
+    let value = parse_der(&[22, 3, 0, 64, 127], |reader| {
+        reader.read_ia5_string()
+    }).unwrap();
+    assert_eq!(value, "\u{0}@\u{7f}");
+    let value = parse_der(&[26, 3, 32, 65, 126], |reader| {
+        reader.read_visible_string()
+    }).unwrap();
+    assert_eq!(value, " A~");
+    let value = parse_der(&[7, 3, 32, 65, 126], |reader| {
+        reader.read_object_descriptor()
+    }).unwrap();
+    assert_eq!(value, " A~");
+    let value = parse_der(&[30, 4, 0, 65, 255, 253], |reader| {
+        reader.read_bmp_string()
+    }).unwrap();
+    assert_eq!(value, "A\u{fffd}");
+    let value = parse_ber(&[44, 128, 4, 1, 72, 4, 1, 105, 0, 0], |reader| {
+        reader.read_utf8string()
+    }).unwrap();
+    assert_eq!(value, "Hi");
+}
+
+#[test]
+fn test_read_strings_err() {
+    parse_der(&[12, 2, 192, 128], |reader| {
+        reader.read_utf8string()
+    }).unwrap_err();
+    parse_der(&[18, 1, 65], |reader| {
+        reader.read_numeric_string()
+    }).unwrap_err();
+    parse_der(&[19, 1, 42], |reader| {
+        reader.read_printable_string()
+    }).unwrap_err();
+    parse_der(&[22, 1, 128], |reader| {
+        reader.read_ia5_string()
+    }).unwrap_err();
+    parse_der(&[26, 1, 127], |reader| {
+        reader.read_visible_string()
+    }).unwrap_err();
+    parse_der(&[7, 1, 10], |reader| {
+        reader.read_object_descriptor()
+    }).unwrap_err();
+    parse_der(&[30, 3, 0, 65, 0], |reader| {
+        reader.read_bmp_string()
+    }).unwrap_err();
+    parse_der(&[30, 4, 216, 61, 222, 0], |reader| {
+        reader.read_bmp_string()
+    }).unwrap_err();
+    parse_der(&[44, 128, 4, 1, 72, 4, 1, 105, 0, 0], |reader| {
+        reader.read_utf8string()
+    }).unwrap_err();
+}
+
+#[test]
+fn test_ber_read_constructed_strings() {
+    // A UTF-8 sequence split across segments is validated as a whole.
+    let value = parse_ber(&[44, 128, 4, 1, 226, 4, 2, 130, 172, 0, 0],
+        |reader| reader.read_utf8string()).unwrap();
+    assert_eq!(value, "\u{20ac}");
+    let value = parse_ber(&[54, 9, 4, 1, 65, 36, 4, 4, 2, 66, 67], |reader| {
+        reader.read_ia5_string()
+    }).unwrap();
+    assert_eq!(value, "ABC");
+    let value = parse_ber(&[62, 128, 4, 1, 0, 4, 1, 65, 0, 0], |reader| {
+        reader.read_bmp_string()
+    }).unwrap();
+    assert_eq!(value, "A");
+    parse_ber(&[54, 128, 4, 1, 65, 4, 1, 128, 0, 0], |reader| {
+        reader.read_ia5_string()
+    }).unwrap_err();
+    parse_ber(&[44, 128, 4, 1, 226, 0, 0], |reader| {
+        reader.read_utf8string()
+    }).unwrap_err();
+    parse_der(&[54, 3, 4, 1, 65], |reader| {
+        reader.read_ia5_string()
+    }).unwrap_err();
+}
+
+#[test]
+fn test_der_skip_value_ok() {
+    let tests : &[(bool, &[u8])] = &[
+        (true, &[48, 5, 5, 0, 1, 1, 255]),
+        (false, &[48, 6, 2, 1, 10, 1, 1, 0]),
+        (true, &[48, 9, 48, 4, 5, 0, 5, 0, 1, 1, 255]),
+        (true, &[48, 6, 191, 31, 0, 1, 1, 255]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                reader.next().skip_value()?;
+                reader.next().read_bool()
+            })
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+}
+
+#[test]
+fn test_der_skip_value_err() {
+    let tests : &[&[u8]] = &[
+        &[48, 0], &[48, 1, 5], &[48, 2, 0, 0], &[48, 3, 4, 5, 0],
+        &[48, 9, 48, 128, 5, 0, 0, 0, 1, 1, 255],
+    ];
+    for &data in tests {
+        parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                reader.next().skip_value()?;
+                reader.next().read_bool()
+            })
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_ber_skip_value_ok() {
+    let tests : &[(bool, &[u8])] = &[
+        (true, &[48, 5, 5, 0, 1, 1, 255]),
+        (true, &[48, 9, 48, 128, 5, 0, 0, 0, 1, 1, 255]),
+        (true, &[48, 128, 48, 128, 48, 128, 0, 0, 4, 0, 0, 0,
+            1, 1, 255, 0, 0]),
+        (true, &[48, 128, 36, 128, 4, 1, 1, 0, 0, 1, 1, 255, 0, 0]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_ber(data, |reader| {
+            reader.read_sequence(|reader| {
+                reader.next().skip_value()?;
+                reader.next().read_bool()
+            })
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+}
+
+#[test]
+fn test_ber_skip_value_err() {
     let tests : &[&[u8]] = &[
-        &[], &[5], &[0, 0], &[0, 1, 0], &[2, 1, 0], &[37, 0], &[69, 0],
-        &[5, 128, 0], &[37, 128, 0], &[5, 1, 0], &[5, 2, 0, 0],
-        &[5, 1], &[5, 0, 1],
+        &[48, 0], &[48, 1, 5], &[48, 2, 0, 0], &[48, 3, 4, 5, 0],
+        &[48, 7, 5, 128, 0, 0, 1, 1, 255],
+        &[48, 128, 48, 128, 5, 0, 0, 0],
     ];
     for &data in tests {
-        parse_der(data, |reader| {
-            reader.read_null()
+        parse_ber(data, |reader| {
+            reader.read_sequence(|reader| {
+                reader.next().skip_value()?;
+                reader.next().read_bool()
+            })
         }).unwrap_err();
     }
 }
 
 #[test]
-fn test_ber_read_null_ok() {
-    let value = parse_ber(&[5, 0], |reader| {
-        reader.read_null()
-    }).unwrap();
-    assert_eq!(value, ());
+fn test_der_huge_length() {
+    test_general_huge_length(BERMode::Der);
 }
 
 #[test]
-fn test_ber_read_null_err() {
+fn test_ber_huge_length() {
+    test_general_huge_length(BERMode::Ber);
+}
+
+fn test_general_huge_length(mode: BERMode) {
     let tests : &[&[u8]] = &[
-        &[], &[5], &[0, 0], &[0, 1, 0], &[2, 1, 0], &[37, 0], &[69, 0],
-        &[5, 128, 0], &[37, 128, 0], &[5, 1, 0], &[5, 2, 0, 0],
-        &[5, 1], &[5, 0, 1],
+        &[4, 132, 255, 255, 255, 255],
+        &[4, 136, 127, 255, 255, 255, 255, 255, 255, 255],
+        &[4, 136, 255, 255, 255, 255, 255, 255, 255, 255],
+        &[4, 137, 1, 0, 0, 0, 0, 0, 0, 0, 0],
+        &[48, 132, 255, 255, 255, 255, 5, 0],
+        &[48, 136, 255, 255, 255, 255, 255, 255, 255, 255, 5, 0],
     ];
     for &data in tests {
-        parse_ber(data, |reader| {
-            reader.read_null()
+        parse_ber_general(data, mode, |reader| {
+            reader.read_bytes()
+        }).unwrap_err();
+        parse_ber_general(data, mode, |reader| {
+            reader.skip_value()
+        }).unwrap_err();
+        parse_ber_general(data, mode, |reader| {
+            reader.read_der()
         }).unwrap_err();
     }
 }
@@ -1192,6 +1955,23 @@ fn test_ber_read_set_err() {
     }
 }
 
+#[test]
+fn test_read_set_duplicate_tags() {
+    use super::super::tags::TAG_INTEGER;
+    let data = &[49, 6, 2, 1, 10, 2, 1, 20];
+    let read = |mode| {
+        parse_ber_general(data, mode, |reader| {
+            reader.read_set(|reader| {
+                let a = reader.next(&[TAG_INTEGER])?.read_i64()?;
+                let b = reader.next(&[TAG_INTEGER])?.read_i64()?;
+                return Ok((a, b));
+            })
+        })
+    };
+    assert_eq!(read(BERMode::Ber).unwrap(), (10, 20));
+    read(BERMode::Der).unwrap_err();
+}
+
 #[test]
 fn test_der_read_set_of_ok() {
     use std::collections::HashSet;
@@ -1662,5 +2442,1003 @@ fn test_ber_read_overflow() {
 
     let err = r.unwrap_err();
 
-    assert_eq!(err.kind(), ASN1ErrorKind::IntegerOverflow);
+    assert_eq!(err.kind(), ASN1ErrorKind::LengthOverflow);
+}
+
+#[test]
+fn test_read_with_options() {
+    let der = BERReaderOptions::new(BERMode::Der);
+    let ber = BERReaderOptions::new(BERMode::Ber);
+    assert_eq!(BERReaderOptions::from(BERMode::Der), der);
+
+    let data = &[48, 128, 2, 1, 10, 0, 0];
+    let read_seq = |reader: BERReader| {
+        reader.read_sequence(|reader| reader.next().read_i64())
+    };
+    parse_ber_with_options(data, der, read_seq).unwrap_err();
+    assert_eq!(parse_ber_with_options(data,
+        der.allow_indefinite_length(true), read_seq).unwrap(), 10);
+    parse_ber_with_options(data,
+        ber.allow_indefinite_length(false), read_seq).unwrap_err();
+
+    let tests : &[(i64, &[u8])] = &[
+        (10, &[2, 2, 0, 10]),
+        (-1, &[2, 3, 255, 255, 255]),
+        (128, &[2, 3, 0, 0, 128]),
+    ];
+    for &(evalue, data) in tests {
+        parse_ber_with_options(data, ber, |reader| {
+            reader.read_i64()
+        }).unwrap_err();
+        let value = parse_ber_with_options(data,
+            der.allow_non_minimal_integers(true), |reader| {
+            reader.read_i64()
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+    let value = parse_ber_with_options(&[2, 3, 0, 0, 128],
+        ber.allow_non_minimal_integers(true), |reader| {
+        reader.read_u64()
+    }).unwrap();
+    assert_eq!(value, 128);
+
+    let data = &[36, 128, 4, 1, 1, 4, 1, 2, 0, 0];
+    parse_ber_with_options(data,
+        ber.allow_constructed_strings(false), |reader| {
+        reader.read_bytes()
+    }).unwrap_err();
+    let value = parse_ber_with_options(data,
+        der.allow_indefinite_length(true).allow_constructed_strings(true),
+        |reader| reader.read_bytes()).unwrap();
+    assert_eq!(value, [1, 2]);
+
+    let data = &[5, 0, 5, 0];
+    parse_ber_with_options(data, der, |reader| {
+        reader.read_null()
+    }).unwrap_err();
+    parse_ber_with_options(data, der.allow_trailing_data(true), |reader| {
+        reader.read_null()
+    }).unwrap();
+
+    let data = &[19, 2, 65, 42];
+    parse_ber_with_options(data, der, |reader| {
+        reader.read_printable_string()
+    }).unwrap_err();
+    let value = parse_ber_with_options(data, der.check_charsets(false),
+        |reader| reader.read_printable_string()).unwrap();
+    assert_eq!(value, "A*");
+    parse_ber_with_options(&[22, 1, 255], der.check_charsets(false),
+        |reader| reader.read_ia5_string()).unwrap_err();
+
+    let data = &[4, 3, 1, 2, 3];
+    parse_ber_with_options(data, der.max_length(2), |reader| {
+        reader.read_bytes()
+    }).unwrap_err();
+    parse_ber_with_options(data, der.max_length(3), |reader| {
+        reader.read_bytes()
+    }).unwrap();
+    let err = parse_ber_with_options(data, der.max_total_length(4), |reader| {
+        reader.read_bytes()
+    }).unwrap_err();
+    assert_eq!(err.kind(), ASN1ErrorKind::InvalidLength);
+    parse_ber_with_options(data, der.max_total_length(5), |reader| {
+        reader.read_bytes()
+    }).unwrap();
+    // Declared lengths are checked before the input is known to be short.
+    let err = parse_ber_with_options(&[4, 130, 1, 0], der.max_total_length(5),
+        |reader| reader.read_bytes()).unwrap_err();
+    assert_eq!(err.kind(), ASN1ErrorKind::InvalidLength);
+
+    let ber = BERReaderOptions::new(BERMode::Ber);
+    let data = &[48, 128, 5, 0, 5, 0, 5, 0, 0, 0];
+    let read = |reader: BERReader| {
+        reader.read_sequence_of(|reader| reader.read_null())
+    };
+    let err = parse_ber_with_options(data, ber.max_total_length(9), read)
+        .unwrap_err();
+    assert_eq!(err.kind(), ASN1ErrorKind::InvalidLength);
+    parse_ber_with_options(data, ber.max_total_length(10), read).unwrap();
+
+    // Any AsRef<[u8]> input, which the values read may borrow from.
+    struct Input(Vec<u8>);
+    impl AsRef<[u8]> for Input {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+    let input = Input(Vec::from(&[4, 2, 1, 2][..]));
+    let value = parse_ber_with_options(&input, der, |reader| {
+        reader.read_bytes_ref()
+    }).unwrap();
+    assert_eq!(value, &[1, 2]);
+    let err = parse_ber_with_options(data, ber.max_total_length(9),
+        |reader| reader.read_der()).unwrap_err();
+    assert_eq!(err.kind(), ASN1ErrorKind::InvalidLength);
+}
+
+#[test]
+fn test_error_position() {
+    let tests : &[(&[u8], ASN1ErrorKind, usize, usize)] = &[
+        (&[48, 6, 2, 1, 10, 1, 1, 5], ASN1ErrorKind::Invalid, 5, 3),
+        (&[48, 6, 2, 1, 10, 4, 1, 5], ASN1ErrorKind::UnexpectedTag, 5, 3),
+        (&[48, 6, 2, 2, 0, 10, 1, 1, 255], ASN1ErrorKind::Invalid, 2, 4),
+        (&[48, 6, 2, 1, 10, 1, 5, 255], ASN1ErrorKind::Eof, 5, 7),
+        (&[48, 6, 2, 1, 10, 1, 1, 255, 0], ASN1ErrorKind::Extra, 8, 1),
+        (&[48, 4, 2, 1, 10], ASN1ErrorKind::Eof, 0, 6),
+    ];
+    for &(data, kind, offset, length) in tests {
+        let err = parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                let i = reader.next().read_i64()?;
+                let b = reader.next().read_bool()?;
+                return Ok((i, b));
+            })
+        }).unwrap_err();
+        assert_eq!(err.kind(), kind);
+        assert_eq!(err.offset(), Some(offset));
+        assert_eq!(err.length(), Some(length));
+    }
+}
+
+#[test]
+fn test_error_kinds() {
+    let tests : &[(&[u8], ASN1ErrorKind)] = &[
+        (&[4, 1], ASN1ErrorKind::Eof),
+        (&[2, 1, 0], ASN1ErrorKind::UnexpectedTag),
+        (&[4, 255, 0], ASN1ErrorKind::InvalidLength),
+        (&[4, 129, 1, 0], ASN1ErrorKind::InvalidLength),
+        (&[4, 128, 0, 0], ASN1ErrorKind::InvalidLength),
+        (&[36, 128, 0, 0], ASN1ErrorKind::InvalidLength),
+        (&[4, 1, 0, 0], ASN1ErrorKind::Extra),
+    ];
+    for &(data, kind) in tests {
+        let err = parse_der(data, |reader| {
+            reader.read_bytes()
+        }).unwrap_err();
+        assert_eq!(err.kind(), kind);
+    }
+    let err = parse_der(&[19, 1, 42], |reader| {
+        reader.read_printable_string()
+    }).unwrap_err();
+    assert_eq!(err.kind(), ASN1ErrorKind::CharsetViolation);
+    let err = parse_der(&[49, 3, 2, 1, 0], |reader| {
+        reader.read_set(|reader| reader.next(&[TAG_BOOLEAN])?.read_bool())
+    }).unwrap_err();
+    assert_eq!(err.kind(), ASN1ErrorKind::UnexpectedTag);
+}
+
+#[test]
+fn test_error_context() {
+    use alloc::string::ToString;
+    let tests : &[(&[u8], &str)] = &[
+        (&[48, 8, 49, 3, 2, 1, 10, 1, 1, 5], "SEQUENCE[1] → BOOLEAN"),
+        (&[48, 9, 49, 4, 2, 2, 0, 10, 1, 1, 255],
+            "SEQUENCE[0] → SET[0] → INTEGER"),
+        (&[48, 7, 49, 2, 5, 0, 1, 1, 255], "SEQUENCE[0] → SET"),
+        (&[48, 7, 4, 2, 5, 0, 1, 1, 255], "SEQUENCE[0] → OCTET STRING"),
+        (&[48, 8, 49, 3, 2, 1, 10, 1, 1, 255, 0], ""),
+    ];
+    for &(data, econtext) in tests {
+        let err = parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                let i = reader.next().read_set(|reader| {
+                    reader.next(&[TAG_INTEGER])?.read_i64()
+                })?;
+                let b = reader.next().read_bool()?;
+                return Ok((i, b));
+            })
+        }).unwrap_err();
+        assert_eq!(err.context().to_string(), econtext);
+    }
+    let err = parse_der(&[48, 6, 2, 1, 10, 1, 1, 5], |reader| {
+        reader.read_sequence(|reader| {
+            reader.next().read_i64()?;
+            reader.next().read_bool()
+        })
+    }).unwrap_err();
+    assert_eq!(err.to_string(),
+        "Invalid at offset 5 in SEQUENCE[1] → BOOLEAN");
+    let frames : Vec<(Tag, Option<usize>)> = err.context().iter().collect();
+    assert_eq!(frames, [(TAG_SEQUENCE, Some(1)), (TAG_BOOLEAN, None)]);
+
+    let mut data = Vec::from(&[1u8, 1, 5][..]);
+    for _ in 0..10 {
+        data.splice(0..0, [48, data.len() as u8].iter().cloned());
+    }
+    fn read_nested(reader: BERReader, depth: usize) -> ASN1Result<bool> {
+        if depth == 0 {
+            return reader.read_bool();
+        }
+        reader.read_sequence(|reader| read_nested(reader.next(), depth - 1))
+    }
+    let err = parse_der(&data, |reader| read_nested(reader, 10))
+        .unwrap_err();
+    assert_eq!(err.context().to_string(), "… → SEQUENCE[0] → SEQUENCE[0] → \
+        SEQUENCE[0] → SEQUENCE[0] → SEQUENCE[0] → BOOLEAN");
+}
+
+#[test]
+fn test_error_compact() {
+    assert!(core::mem::size_of::<ASN1Error>() <=
+        2 * core::mem::size_of::<usize>());
+    let err = parse_der(&[48, 6, 2, 1, 10, 1, 1, 5], |reader| {
+        reader.read_sequence(|reader| {
+            let i = reader.read_optional(|reader| reader.read_bool())?;
+            reader.next().read_i64()?;
+            reader.next().read_bool()?;
+            return Ok(i);
+        })
+    }).unwrap_err();
+    assert_eq!(err.offset(), Some(5));
+    // Only the kinds are compared.
+    assert_eq!(err, ASN1Error::new(ASN1ErrorKind::Invalid));
+    assert_ne!(err, ASN1Error::new(ASN1ErrorKind::Eof));
+    assert!(ASN1Error::new(ASN1ErrorKind::Eof).context().is_empty());
+}
+
+#[test]
+fn test_parse_partial() {
+    let tests : &[(i64, usize, &[u8])] = &[
+        (10, 3, &[2, 1, 10]),
+        (10, 3, &[2, 1, 10, 5, 0]),
+        (266, 4, &[2, 2, 1, 10, 0, 0, 0]),
+    ];
+    for &(evalue, elen, data) in tests {
+        let value = parse_der_partial(data, |reader| {
+            reader.read_i64()
+        }).unwrap();
+        assert_eq!(value, (evalue, elen));
+        let value = parse_ber_partial(data, |reader| {
+            reader.read_i64()
+        }).unwrap();
+        assert_eq!(value, (evalue, elen));
+    }
+
+    let data = &[48, 128, 2, 1, 10, 0, 0, 1, 1, 255];
+    parse_der_partial(data, |reader| {
+        reader.read_sequence(|reader| reader.next().read_i64())
+    }).unwrap_err();
+    let value = parse_ber_partial(data, |reader| {
+        reader.read_sequence(|reader| reader.next().read_i64())
+    }).unwrap();
+    assert_eq!(value, (10, 7));
+
+    let tests : &[&[u8]] = &[&[], &[2], &[2, 2, 10], &[5, 0]];
+    for &data in tests {
+        parse_der_partial(data, |reader| {
+            reader.read_i64()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_parse_multi() {
+    let tests : &[(&[i64], &[u8])] = &[
+        (&[], &[]),
+        (&[10], &[2, 1, 10]),
+        (&[10, 266, -1], &[2, 1, 10, 2, 2, 1, 10, 2, 1, 255]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_der_multi(data, |reader| {
+            reader.read_i64()
+        }).unwrap();
+        assert_eq!(value, evalue);
+        let docs = split_ber(data, BERMode::Der)
+            .collect::<ASN1Result<Vec<_>>>().unwrap();
+        assert_eq!(docs.len(), evalue.len());
+        let mut joined = Vec::new();
+        for doc in &docs {
+            joined.extend_from_slice(doc);
+        }
+        assert_eq!(joined, data);
+    }
+
+    let data = &[48, 128, 2, 1, 10, 0, 0, 48, 3, 2, 1, 20];
+    parse_der_multi(data, |reader| {
+        reader.read_sequence(|reader| reader.next().read_i64())
+    }).unwrap_err();
+    let value = parse_ber_multi(data, |reader| {
+        reader.read_sequence(|reader| reader.next().read_i64())
+    }).unwrap();
+    assert_eq!(value, [10, 20]);
+    let docs = split_ber(data, BERMode::Ber)
+        .collect::<ASN1Result<Vec<_>>>().unwrap();
+    assert_eq!(docs, [&data[..7], &data[7..]]);
+
+    let tests : &[&[u8]] = &[&[2, 1, 10, 2], &[2, 1, 10, 2, 2, 0], &[0, 0]];
+    for &data in tests {
+        parse_der_multi(data, |reader| {
+            reader.read_i64()
+        }).unwrap_err();
+        let mut docs = split_ber(data, BERMode::Der);
+        assert!(docs.by_ref().any(|doc| doc.is_err()));
+        assert!(docs.next().is_none());
+    }
+
+    parse_der_multi(&[5, 0], |_| Ok(())).unwrap_err();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_stream_reader() {
+    let data : &[u8] = &[
+        2, 1, 10, 48, 128, 4, 2, 1, 2, 48, 128, 0, 0, 0, 0, 191, 31, 1, 7];
+    let mut stream = BERStreamReader::new(data, BERMode::Ber);
+    assert_eq!(stream.next_raw().unwrap().unwrap(), &data[..3]);
+    assert_eq!(stream.next_raw().unwrap().unwrap(), &data[3..15]);
+    assert_eq!(stream.next_raw().unwrap().unwrap(), &data[15..]);
+    assert!(stream.next_raw().unwrap().is_none());
+
+    let data : &[u8] = &[2, 1, 10, 2, 2, 1, 10, 1, 1, 255];
+    let mut stream = BERStreamReader::new(data, BERMode::Der);
+    assert_eq!(stream.read_next(|reader| reader.read_i64()).unwrap(),
+        Some(10));
+    assert_eq!(stream.read_next(|reader| reader.read_i64()).unwrap(),
+        Some(266));
+    stream.read_next(|reader| reader.read_i64()).unwrap_err();
+    assert!(stream.read_next(|reader| reader.read_i64()).unwrap().is_none());
+
+    let tests : &[&[u8]] = &[
+        &[2], &[2, 2, 1], &[0, 0], &[4, 128, 0, 0], &[48, 128, 0, 0],
+        &[4, 136, 255, 255, 255, 255, 255, 255, 255, 255],
+        &[4, 137, 1, 0, 0, 0, 0, 0, 0, 0, 0],
+    ];
+    for &data in tests {
+        let mut stream = BERStreamReader::new(data, BERMode::Der);
+        stream.next_raw().unwrap_err();
+    }
+    let options = BERReaderOptions::new(BERMode::Der).max_length(2);
+    let mut stream = BERStreamReader::with_options(
+        &[4, 3, 1, 2, 3][..], options);
+    stream.next_raw().unwrap_err();
+
+    let data : &[u8] = &[48, 128, 5, 0, 5, 0, 5, 0, 0, 0, 5, 0];
+    let options = BERReaderOptions::new(BERMode::Ber).max_total_length(9);
+    let mut stream = BERStreamReader::with_options(data, options);
+    stream.next_raw().unwrap_err();
+    let options = BERReaderOptions::new(BERMode::Ber).max_total_length(10);
+    let mut stream = BERStreamReader::with_options(data, options);
+    assert_eq!(stream.next_raw().unwrap().unwrap().len(), 10);
+    assert_eq!(stream.next_raw().unwrap().unwrap(), &[5, 0]);
+    let options = BERReaderOptions::new(BERMode::Der).max_total_length(4);
+    let mut stream = BERStreamReader::with_options(
+        &[4, 3, 1, 2, 3][..], options);
+    stream.next_raw().unwrap_err();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_stream_reader_skip() {
+    use std::io::Cursor;
+    let data : &[u8] = &[
+        4, 3, 1, 2, 3, 48, 128, 4, 1, 1, 36, 128, 0, 0, 0, 0, 2, 1, 10];
+    let mut stream = BERStreamReader::new(Cursor::new(data), BERMode::Ber);
+    assert!(stream.skip_next().unwrap());
+    assert!(stream.skip_next().unwrap());
+    assert_eq!(stream.read_next(|reader| reader.read_i64()).unwrap(),
+        Some(10));
+    assert!(!stream.skip_next().unwrap());
+
+    let tests : &[&[u8]] = &[&[0, 0], &[4], &[48, 128, 4, 0]];
+    for &data in tests {
+        let mut stream = BERStreamReader::new(Cursor::new(data),
+            BERMode::Ber);
+        stream.skip_next().unwrap_err();
+    }
+}
+
+#[test]
+fn test_read_refs() {
+    let tests : &[(&[u8], &[u8])] = &[
+        (&[], &[4, 0]),
+        (&[1, 0, 100, 255], &[4, 4, 1, 0, 100, 255]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_ber(data, |reader| {
+            reader.read_bytes_ref()
+        }).unwrap();
+        assert_eq!(value, evalue);
+        assert_eq!(value.as_ptr(), data[2..].as_ptr());
+    }
+    let tests : &[&[u8]] = &[
+        &[36, 128, 4, 1, 1, 0, 0], &[36, 3, 4, 1, 1], &[12, 0], &[4, 1],
+    ];
+    for &data in tests {
+        parse_ber(data, |reader| {
+            reader.read_bytes_ref()
+        }).unwrap_err();
+    }
+
+    let data = &[12, 5, 206, 177, 206, 178, 33];
+    let value = parse_der(data, |reader| {
+        reader.read_utf8string_ref()
+    }).unwrap();
+    assert_eq!(value, "αβ!");
+    let tests : &[&[u8]] = &[
+        &[12, 1, 255], &[44, 3, 4, 1, 65], &[4, 1, 65],
+    ];
+    for &data in tests {
+        parse_ber(data, |reader| {
+            reader.read_utf8string_ref()
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_read_cows() {
+    use alloc::borrow::{Cow,ToOwned};
+    fn is_borrowed<T: ?Sized + ToOwned>(value: Cow<T>) -> bool {
+        match value {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        }
+    }
+    let data = &[4, 3, 1, 2, 3];
+    let value = parse_der(data, |reader| reader.read_bytes_cow()).unwrap();
+    assert!(is_borrowed(value.clone()));
+    assert_eq!(value, &[1, 2, 3][..]);
+    let data = &[36, 128, 4, 1, 1, 4, 2, 2, 3, 0, 0];
+    let value = parse_ber(data, |reader| reader.read_bytes_cow()).unwrap();
+    assert!(!is_borrowed(value.clone()));
+    assert_eq!(value, &[1, 2, 3][..]);
+    parse_der(data, |reader| reader.read_bytes_cow()).unwrap_err();
+
+    let tests : &[(&str, bool, &[u8])] = &[
+        ("Hi", true, &[12, 2, 72, 105]),
+        ("Hi", false, &[44, 128, 4, 1, 72, 4, 1, 105, 0, 0]),
+    ];
+    for &(evalue, borrowed, data) in tests {
+        let value = parse_ber(data, |reader| {
+            reader.read_utf8string_cow()
+        }).unwrap();
+        assert_eq!(value, evalue);
+        assert_eq!(is_borrowed(value.clone()), borrowed);
+    }
+
+    let value = parse_der(&[19, 2, 65, 66], |reader| {
+        reader.read_printable_string_cow()
+    }).unwrap();
+    assert!(is_borrowed(value.clone()));
+    assert_eq!(value, "AB");
+    let value = parse_ber(&[54, 128, 4, 1, 65, 4, 1, 66, 0, 0], |reader| {
+        reader.read_ia5_string_cow()
+    }).unwrap();
+    assert!(!is_borrowed(value.clone()));
+    assert_eq!(value, "AB");
+    let value = parse_der(&[18, 2, 49, 32], |reader| {
+        reader.read_numeric_string_cow()
+    }).unwrap();
+    assert_eq!(value, "1 ");
+    let value = parse_der(&[26, 1, 126], |reader| {
+        reader.read_visible_string_cow()
+    }).unwrap();
+    assert_eq!(value, "~");
+    let value = parse_der(&[30, 0], |reader| {
+        reader.read_bmp_string_cow()
+    }).unwrap();
+    assert!(is_borrowed(value.clone()));
+    assert_eq!(value, "");
+    let value = parse_der(&[30, 4, 0, 65, 3, 192], |reader| {
+        reader.read_bmp_string_cow()
+    }).unwrap();
+    assert!(!is_borrowed(value.clone()));
+    assert_eq!(value, "A\u{3C0}");
+    let value = parse_ber(&[62, 128, 4, 2, 0, 65, 4, 2, 0, 66, 0, 0],
+        |reader| reader.read_bmp_string_cow()).unwrap();
+    assert!(!is_borrowed(value.clone()));
+    assert_eq!(value, "AB");
+    parse_der(&[30, 4, 216, 61, 222, 0], |reader| {
+        reader.read_bmp_string_cow()
+    }).unwrap_err();
+
+    let tests : &[&[u8]] = &[&[12, 1, 255], &[19, 1, 42], &[4, 1, 65]];
+    for &data in tests {
+        parse_der(data, |reader| {
+            if reader.lookahead_tag()? == TAG_UTF8STRING {
+                reader.read_utf8string_cow()
+            } else {
+                reader.read_printable_string_cow()
+            }
+        }).unwrap_err();
+    }
+}
+
+#[test]
+fn test_read_bytes_into() {
+    let tests : &[(&[u8], &[u8])] = &[
+        (&[], &[4, 0]),
+        (&[1, 0, 100, 255], &[4, 4, 1, 0, 100, 255]),
+        (&[12, 34, 56], &[36, 128, 36, 128, 4, 3, 12, 34, 56, 0, 0, 0, 0]),
+        (&[2, 3, 1], &[36, 128, 4, 1, 2, 36, 128, 4, 2, 3, 1, 0, 0, 0, 0]),
+    ];
+    for &(evalue, data) in tests {
+        let mut buf = [0; 4];
+        let len = parse_ber(data, |reader| {
+            reader.read_bytes_into(&mut buf)
+        }).unwrap();
+        assert_eq!(&buf[..len], evalue);
+        let mut buf = [0; 2];
+        let result = parse_ber(data, |reader| {
+            reader.read_bytes_into(&mut buf)
+        });
+        if evalue.len() > 2 {
+            assert_eq!(result.unwrap_err().kind(),
+                ASN1ErrorKind::InvalidLength);
+        } else {
+            assert_eq!(result.unwrap(), evalue.len());
+        }
+    }
+    let mut buf = [0; 4];
+    parse_der(&[36, 128, 4, 1, 1, 0, 0], |reader| {
+        reader.read_bytes_into(&mut buf)
+    }).unwrap_err();
+}
+
+#[test]
+fn test_try_read() {
+    let data = &[48, 6, 2, 1, 10, 1, 1, 255];
+    let value = parse_der(data, |mut reader| {
+        // Failing deep inside the sequence, many more times than the
+        // nesting limit, must leave the reader intact.
+        for _ in 0..200 {
+            reader = reader.try_read(|reader| {
+                reader.read_sequence(|reader| {
+                    let i = reader.next().read_i64()?;
+                    let j = reader.next().read_i64()?;
+                    return Ok((i, j != 0));
+                })
+            }).unwrap_err().1;
+        }
+        reader.try_read(|reader| {
+            reader.read_sequence(|reader| {
+                let i = reader.next().read_i64()?;
+                let b = reader.next().read_bool()?;
+                return Ok((i, b));
+            })
+        }).map_err(|(e, _)| e)
+    }).unwrap();
+    assert_eq!(value, (10, true));
+    let value = parse_der(&[48, 3, 1, 1, 0], |reader| {
+        reader.read_sequence(|reader| {
+            match reader.next().try_read(|reader| reader.read_i64()) {
+                Ok(i) => Ok(Some(i)),
+                Err((_, reader)) => reader.read_bool().map(|_| None),
+            }
+        })
+    }).unwrap();
+    assert_eq!(value, None);
+    // An implicit tag still applies after a failed attempt.
+    let value = parse_der(&[128, 1, 255], |reader| {
+        reader.read_tagged_implicit(Tag::context(0), |reader| {
+            let (e, reader) = reader.try_read(|reader| reader.read_null())
+                .unwrap_err();
+            assert_eq!(e.kind(), ASN1ErrorKind::Invalid);
+            reader.read_bool()
+        })
+    }).unwrap();
+    assert!(value);
+}
+
+#[test]
+fn test_tlv_iter() {
+    let data = &[48, 128, 2, 1, 10, 36, 128, 4, 1, 1, 0, 0, 0, 0, 5, 0];
+    let tlvs = TlvIter::new(data, BERMode::Ber)
+        .collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(tlvs.len(), 2);
+    assert_eq!(tlvs[0].tag(), TAG_SEQUENCE);
+    assert_eq!(tlvs[0].pcbit(), PCBit::Constructed);
+    assert_eq!((tlvs[0].offset(), tlvs[0].header_len()), (0, 2));
+    assert_eq!(tlvs[0].contents(), &data[2..12]);
+    assert_eq!(tlvs[0].raw(), &data[..14]);
+    assert_eq!(tlvs[1].tag(), TAG_NULL);
+    assert_eq!((tlvs[1].offset(), tlvs[1].header_len()), (14, 2));
+    assert_eq!(tlvs[1].raw(), &[5, 0]);
+    let children = tlvs[0].children()
+        .collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0].pcbit(), PCBit::Primitive);
+    assert_eq!(children[0].contents(), &[10]);
+    assert_eq!(children[1].offset(), 5);
+    let segments = children[1].children()
+        .collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(segments.len(), 1);
+    assert_eq!((segments[0].offset(), segments[0].contents()), (7, &[1][..]));
+
+    let tests : &[&[u8]] = &[
+        &[0, 0], &[2, 2, 10], &[2, 128, 0, 0], &[48, 128, 0],
+    ];
+    for &data in tests {
+        let mut iter = TlvIter::new(data, BERMode::Ber);
+        iter.next().unwrap().unwrap_err();
+        assert!(iter.next().is_none());
+    }
+    TlvIter::new(&[48, 128, 0, 0], BERMode::Der).next().unwrap()
+        .unwrap_err();
+}
+
+#[test]
+fn test_lookahead_header() {
+    let mut data = Vec::from(&[48, 128, 159, 31, 129, 128][..]);
+    data.extend_from_slice(&[0; 128]);
+    data.extend_from_slice(&[0, 0]);
+    let (outer, inner) = parse_ber(&data, |reader| {
+        let outer = reader.lookahead_header()?;
+        let inner = reader.read_sequence(|reader| {
+            let reader = reader.next();
+            let header = reader.lookahead_header()?;
+            let reader = reader.try_read(|reader| reader.read_bool())
+                .unwrap_err().1;
+            reader.skip_value()?;
+            return Ok(header);
+        })?;
+        return Ok((outer, inner));
+    }).unwrap();
+    assert_eq!(outer.tag(), TAG_SEQUENCE);
+    assert_eq!(outer.pcbit(), PCBit::Constructed);
+    assert_eq!(outer.length(), None);
+    assert_eq!((outer.offset(), outer.header_len()), (0, 2));
+    assert_eq!(inner.tag(), Tag::context(31));
+    assert_eq!(inner.tag_class(), TagClass::ContextSpecific);
+    assert_eq!(inner.length(), Some(128));
+    assert_eq!((inner.offset(), inner.header_len()), (2, 4));
+    let tlv = TlvIter::new(&data, BERMode::Ber).next().unwrap().unwrap();
+    assert_eq!(tlv.header(), outer);
+    parse_der(&[2, 129, 1, 0], |reader| {
+        reader.lookahead_header()
+    }).unwrap_err();
+}
+
+#[test]
+fn test_read_tagged_if() {
+    fn is_app_or_context(tag: Tag) -> bool {
+        tag.tag_class == TagClass::Application ||
+            tag.tag_class == TagClass::ContextSpecific
+    }
+    let tests : &[((Tag, i64), &[u8])] = &[
+        ((Tag::application(1), 10), &[97, 3, 2, 1, 10]),
+        ((Tag::context(31), -1), &[191, 31, 3, 2, 1, 255]),
+    ];
+    for &(evalue, data) in tests {
+        let value = parse_der(data, |reader| {
+            reader.read_tagged_if(is_app_or_context, |tag, reader| {
+                Ok((tag, reader.read_i64()?))
+            })
+        }).unwrap();
+        assert_eq!(value, evalue);
+    }
+    let tests : &[(&[u8], ASN1ErrorKind)] = &[
+        (&[], ASN1ErrorKind::Eof),
+        (&[48, 3, 2, 1, 10], ASN1ErrorKind::UnexpectedTag),
+        (&[225, 3, 2, 1, 10], ASN1ErrorKind::UnexpectedTag),
+        (&[129, 1, 10], ASN1ErrorKind::Invalid),
+    ];
+    for &(data, kind) in tests {
+        let e = parse_der(data, |reader| {
+            reader.read_tagged_if(is_app_or_context, |_, reader| {
+                reader.read_i64()
+            })
+        }).unwrap_err();
+        assert_eq!(e.kind(), kind);
+    }
+    let value = parse_der(&[164, 3, 2, 1, 10], |reader| {
+        reader.read_tagged_implicit(Tag::context(4), |reader| {
+            reader.read_tagged_if(|tag| tag == Tag::context(4), |_, reader| {
+                reader.read_i64()
+            })
+        })
+    }).unwrap();
+    assert_eq!(value, 10);
+}
+
+#[test]
+fn test_read_length_overflow() {
+    // Lengths exceeding usize on the target must not be truncated.
+    let mut data = Vec::from(&[4, 128 + 9, 1][..]);
+    data.extend_from_slice(&[0; 8]);
+    if core::mem::size_of::<usize>() <= 4 {
+        data[1] = 128 + 5;
+        data.truncate(7);
+    }
+    let e = parse_ber(&data, |reader| reader.read_bytes()).unwrap_err();
+    assert_eq!(e.kind(), ASN1ErrorKind::LengthOverflow);
+    let e = parse_ber(&data, |reader| reader.skip_value()).unwrap_err();
+    assert_eq!(e.kind(), ASN1ErrorKind::LengthOverflow);
+    let e = TlvIter::new(&data, BERMode::Ber).next().unwrap().unwrap_err();
+    assert_eq!(e.kind(), ASN1ErrorKind::LengthOverflow);
+}
+
+#[test]
+fn test_read_non_minimal_tag_number() {
+    let tests : &[(u64, &[u8])] = &[
+        (31, &[159, 128, 31, 1, 10]),
+        (200, &[159, 128, 129, 72, 1, 10]),
+    ];
+    for &(tag_number, data) in tests {
+        // Leading zero bits are forbidden in BER as well as in DER.
+        for &mode in &[BERMode::Ber, BERMode::Der] {
+            parse_ber_general(data, mode, |reader| {
+                reader.lookahead_tag()
+            }).unwrap_err();
+            parse_ber_general(data, mode, |reader| {
+                reader.read_tagged_implicit(Tag::context(tag_number),
+                    |reader| reader.read_i64())
+            }).unwrap_err();
+            parse_ber_general(data, mode, |reader| reader.skip_value())
+                .unwrap_err();
+        }
+    }
+    // The low-tag-number form must be used for tag numbers up to 30.
+    parse_ber(&[159, 30, 1, 10], |reader| reader.skip_value()).unwrap_err();
+    parse_der(&[159, 31, 1, 10], |reader| reader.skip_value()).unwrap();
+}
+
+#[test]
+fn test_push_parser() {
+    let mut data = Vec::from(&[48, 128, 2, 1, 10, 4, 130, 1, 0][..]);
+    data.extend_from_slice(&[7; 256]);
+    data.extend_from_slice(&[0, 0, 1, 1, 255]);
+    // Feed byte by byte: nothing is yielded before a value is complete.
+    let mut parser = BERPushParser::new(BERMode::Ber);
+    let mut values = Vec::new();
+    for (i, &b) in data.iter().enumerate() {
+        parser.feed(&[b]);
+        if let Some(raw) = parser.next_raw().unwrap() {
+            values.push((i, raw.len()));
+        }
+    }
+    assert_eq!(values, [(266, 267), (269, 3)]);
+    assert!(parser.is_empty());
+
+    let mut parser = BERPushParser::new(BERMode::Ber);
+    parser.feed(&data[..100]);
+    assert_eq!(parser.read_next(|reader| reader.read_bool()), Ok(None));
+    parser.feed(&data[100..]);
+    let value = parser.read_next(|reader| {
+        reader.read_sequence(|reader| {
+            let i = reader.next().read_i64()?;
+            let bytes = reader.next().read_bytes()?;
+            return Ok((i, bytes.len()));
+        })
+    }).unwrap();
+    assert_eq!(value, Some((10, 256)));
+    parser.feed(&[1]);
+    assert_eq!(parser.read_next(|reader| reader.read_bool()), Ok(Some(true)));
+    assert!(!parser.is_empty());
+
+    // Many values in one chunk, and values split across chunks while
+    // the consumed data is being dropped.
+    let value = &[48, 128, 4, 3, 1, 2, 3, 48, 3, 1, 1, 255, 0, 0];
+    let data : Vec<u8> = value.iter().cloned().cycle()
+        .take(value.len() * 5000).collect();
+    let mut parser = BERPushParser::new(BERMode::Ber);
+    parser.feed(&data);
+    let mut count = 0;
+    while let Some(raw) = parser.next_raw().unwrap() {
+        assert_eq!(raw, value);
+        count += 1;
+    }
+    assert_eq!(count, 5000);
+    assert!(parser.is_empty());
+    for chunk in data.chunks(5) {
+        parser.feed(chunk);
+        while let Some(raw) = parser.next_raw().unwrap() {
+            assert_eq!(raw, value);
+            count += 1;
+        }
+    }
+    assert_eq!(count, 10000);
+    assert!(parser.is_empty());
+
+    let mut parser = BERPushParser::new(BERMode::Ber);
+    parser.feed(&[2, 1, 10, 48, 128, 1, 1, 255, 0]);
+    assert!(parser.next_raw().unwrap().is_some());
+    assert_eq!(parser.next_raw().unwrap(), None);
+    parser.feed(&[1]);
+    assert_eq!(parser.next_raw().unwrap_err().kind(), ASN1ErrorKind::Invalid);
+
+    let mut parser = BERPushParser::new(BERMode::Der);
+    parser.feed(&[48, 128]);
+    parser.next_raw().unwrap_err();
+    parser.next_raw().unwrap_err();
+    let mut parser = BERPushParser::with_options(
+        BERReaderOptions::new(BERMode::Der).max_length(16));
+    parser.feed(&[4, 129, 200]);
+    assert_eq!(parser.next_raw().unwrap_err().kind(),
+        ASN1ErrorKind::InvalidLength);
+
+    let mut parser = BERPushParser::with_options(
+        BERReaderOptions::new(BERMode::Ber).max_total_length(9));
+    parser.feed(&[48, 128, 5, 0, 5, 0, 5, 0]);
+    assert_eq!(parser.next_raw().unwrap(), None);
+    parser.feed(&[0, 0]);
+    assert_eq!(parser.next_raw().unwrap_err().kind(),
+        ASN1ErrorKind::InvalidLength);
+    let mut parser = BERPushParser::with_options(
+        BERReaderOptions::new(BERMode::Ber).max_total_length(9));
+    parser.feed(&[48, 128, 4, 8]);
+    assert_eq!(parser.next_raw().unwrap_err().kind(),
+        ASN1ErrorKind::InvalidLength);
+}
+
+#[test]
+fn test_read_any() {
+    use super::super::models::ASN1Value::*;
+    let data = &[
+        48, 128,
+            2, 9, 0, 255, 255, 255, 255, 255, 255, 255, 255,
+            3, 2, 6, 64,
+            36, 128, 4, 1, 1, 4, 1, 2, 0, 0,
+            5, 0,
+            6, 3, 42, 3, 4,
+            19, 1, 65, 22, 1, 66, 26, 1, 67, 18, 1, 49, 30, 2, 0, 68,
+            49, 3, 1, 1, 255,
+            161, 128, 2, 1, 10, 0, 0,
+            128, 2, 1, 2,
+            10, 1, 3,
+        0, 0];
+    let value = parse_ber(data, |reader| reader.read_any()).unwrap();
+    assert_eq!(value, Sequence(Vec::from(&[
+        Integer(Vec::from(&[0, 255, 255, 255, 255, 255, 255, 255, 255][..])),
+        BitString(Vec::from(&[64][..]), 2),
+        OctetString(Vec::from(&[1, 2][..])),
+        Null,
+        ObjectIdentifier(
+            super::super::models::ObjectIdentifier::from_slice(&[1, 2, 3, 4])),
+        PrintableString("A".into()),
+        IA5String("B".into()),
+        VisibleString("C".into()),
+        NumericString("1".into()),
+        BMPString("D".into()),
+        Set(Vec::from(&[Boolean(true)][..])),
+        Constructed(Tag::context(1),
+            Vec::from(&[Integer(Vec::from(&[10][..]))][..])),
+        Primitive(Tag::context(0), Vec::from(&[1, 2][..])),
+        Primitive(TAG_ENUM, Vec::from(&[3][..])),
+    ][..])));
+    let value = parse_der(&[129, 1, 5], |reader| {
+        reader.read_tagged_implicit(Tag::context(1), |reader| reader.read_any())
+    }).unwrap();
+    assert_eq!(value, Primitive(Tag::context(1), Vec::from(&[5][..])));
+
+    // A DER SET is sorted by tags, and a DER SET OF by encodings.
+    let tagged = Set(Vec::from(&[
+        Constructed(Tag::context(0), Vec::from(&[Null][..])),
+        Primitive(Tag::context(1), Vec::from(&[1][..])),
+    ][..]));
+    let value = parse_der(&[49, 7, 160, 2, 5, 0, 129, 1, 1], |reader| {
+        reader.read_any()
+    }).unwrap();
+    assert_eq!(value, tagged);
+    let value = parse_der(&[49, 6, 2, 1, 1, 2, 1, 1], |reader| {
+        reader.read_any()
+    }).unwrap();
+    assert_eq!(value, Set(Vec::from(&[
+        Integer(Vec::from(&[1][..])), Integer(Vec::from(&[1][..])),
+    ][..])));
+    let data = &[49, 10, 129, 1, 1, 160, 2, 5, 0, 128, 1, 0];
+    parse_der(data, |reader| reader.read_any()).unwrap_err();
+    parse_ber(data, |reader| reader.read_any()).unwrap();
+
+    let tests : &[&[u8]] = &[
+        &[0, 0], &[2, 0], &[2, 2, 0, 1], &[1, 1, 1],
+        &[48, 128, 2, 1, 0, 0, 0], &[161, 3, 2, 1], &[161, 2, 0, 0],
+        &[12, 1, 255], &[49, 6, 1, 1, 255, 1, 1, 0],
+    ];
+    for &data in tests {
+        parse_der(data, |reader| reader.read_any()).unwrap_err();
+    }
+}
+
+#[test]
+fn test_read_with_buffer() {
+    let data = &[48, 128, 48, 128, 2, 1, 10, 0, 0, 5, 0, 0, 0];
+    let (value, buf) = parse_ber(data, |reader| {
+        reader.read_sequence(|reader| {
+            let (value, buf) = reader.next().read_with_buffer(|reader| {
+                reader.read_sequence(|reader| reader.next().read_i64())
+            })?;
+            reader.next().read_null()?;
+            return Ok((value, buf));
+        })
+    }).unwrap();
+    assert_eq!(value, 10);
+    assert_eq!(buf, &[48, 128, 2, 1, 10, 0, 0]);
+    // Errors from the callback are passed through.
+    parse_der(&[2, 1, 10], |reader| {
+        reader.read_with_buffer(|reader| reader.read_bool())
+    }).unwrap_err();
+}
+
+#[test]
+fn test_query() {
+    // SEQUENCE { INTEGER 10, [0] { NULL, BOOLEAN TRUE }, NULL }
+    let data = &[48, 11, 2, 1, 10, 160, 6, 5, 0, 1, 1, 255, 5, 0];
+    let tests : &[(&[QueryStep], usize, &[u8])] = &[
+        (&[], 0, &data[2..13]),
+        (&[QueryStep::Child(0)], 2, &[10]),
+        (&[QueryStep::Child(1), QueryStep::Child(1)], 9, &[255]),
+        (&[QueryStep::Tagged(Tag::context(0)), QueryStep::Tagged(TAG_NULL)],
+            7, &[]),
+    ];
+    for &(path, offset, contents) in tests {
+        let tlv = der_query(data, path).unwrap().unwrap();
+        assert_eq!((tlv.offset(), tlv.contents()), (offset, contents));
+    }
+    let tests : &[&[QueryStep]] = &[
+        &[QueryStep::Child(3)],
+        &[QueryStep::Tagged(TAG_SEQUENCE)],
+        &[QueryStep::Child(1), QueryStep::Child(0), QueryStep::Child(0)],
+    ];
+    for &path in tests {
+        assert!(der_query(data, path).unwrap().is_none());
+    }
+    // Primitive contents are looked into as encapsulated data.
+    der_query(data, &[QueryStep::Child(0), QueryStep::Child(0)])
+        .unwrap_err();
+    let tlv = der_query(&[4, 3, 2, 1, 7], &[QueryStep::Child(0)])
+        .unwrap().unwrap();
+    assert_eq!(tlv.contents(), &[7]);
+    // Only the elements on the way are examined.
+    let data = &[48, 7, 2, 1, 10, 48, 2, 4, 9];
+    assert!(der_query(data, &[QueryStep::Child(0)]).unwrap().is_some());
+    der_query(data, &[QueryStep::Child(1), QueryStep::Child(0)])
+        .unwrap_err();
+    der_query(&[], &[]).unwrap_err();
+    der_query(&[48, 128, 0, 0], &[]).unwrap_err();
+    assert!(ber_query(&[48, 128, 5, 0, 0, 0], &[QueryStep::Child(0)])
+        .unwrap().is_some());
+}
+
+#[test]
+fn test_lint_der() {
+    use alloc::string::ToString;
+    fn findings(data: &[u8]) -> Vec<(usize, ASN1ErrorKind)> {
+        lint_der(data).iter().map(|f| (f.offset(), f.kind())).collect()
+    }
+    assert_eq!(findings(&[48, 6, 2, 1, 10, 1, 1, 255]), []);
+    assert_eq!(findings(&[49, 6, 1, 1, 255, 2, 1, 10]), []);
+    assert_eq!(findings(&[49, 6, 4, 1, 2, 4, 1, 1]),
+        [(5, ASN1ErrorKind::Invalid)]);
+    assert_eq!(findings(&[49, 6, 2, 1, 10, 1, 1, 255]),
+        [(5, ASN1ErrorKind::Invalid)]);
+    // Time types are checked without the `time` feature too.
+    assert_eq!(findings(b"\x17\x0d820102120034Z"), []);
+    assert_eq!(findings(b"\x17\x0b8201021200Z"),
+        [(0, ASN1ErrorKind::Invalid)]);
+    assert_eq!(findings(b"\x18\x0f19851106210627Z"), []);
+    assert_eq!(findings(b"\x18\x1119851106210627.0Z"),
+        [(0, ASN1ErrorKind::Invalid)]);
+    // Either tag order or encoding order is fine, but not a mix.
+    assert_eq!(findings(&[49, 7, 160, 2, 5, 0, 129, 1, 1]), []);
+    assert_eq!(findings(&[49, 7, 129, 1, 1, 160, 2, 5, 0]), []);
+    assert_eq!(findings(&[49, 13, 129, 1, 1, 160, 2, 5, 0, 128, 1, 0,
+        128, 1, 1]), [(9, ASN1ErrorKind::Invalid)]);
+    // Findings in several elements, including nested ones.
+    let data = &[
+        48, 129, 26,
+            2, 2, 0, 10,
+            4, 129, 1, 7,
+            36, 128, 4, 1, 1, 0, 0,
+            160, 5, 48, 3, 1, 1, 1,
+            191, 128, 31, 0,
+        5, 0];
+    assert_eq!(findings(data), [
+        (0, ASN1ErrorKind::InvalidLength),
+        (3, ASN1ErrorKind::Invalid),
+        (7, ASN1ErrorKind::InvalidLength),
+        (11, ASN1ErrorKind::InvalidLength),
+        (11, ASN1ErrorKind::Invalid),
+        (22, ASN1ErrorKind::Invalid),
+        (25, ASN1ErrorKind::Invalid),
+        (29, ASN1ErrorKind::Extra),
+    ]);
+    // A framing error stops the walk of the enclosing element only.
+    assert_eq!(findings(&[48, 8, 48, 3, 2, 5, 10, 2, 1, 10]),
+        [(4, ASN1ErrorKind::Eof)]);
+    assert_eq!(findings(&[48, 5, 2, 3, 0, 10]),
+        [(0, ASN1ErrorKind::Eof)]);
+    assert_eq!(findings(&[]), [(0, ASN1ErrorKind::Eof)]);
+    let finding = lint_der(&[5, 1, 0])[0];
+    assert_eq!(finding.description(), "non-canonical contents");
+    assert_eq!(finding.to_string(), "non-canonical contents at offset 0");
 }
@@ -0,0 +1,305 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{BERMode,BERReaderImpl,BERReaderOptions};
+use super::{ASN1Error,ASN1ErrorKind,ASN1Result};
+use super::super::{PCBit,Tag,TagClass};
+use super::super::tags::TAG_EOC;
+
+/// An iterator over raw BER/DER-encoded elements
+/// (identifier, length and contents triples).
+///
+/// Elements are not interpreted beyond their framing, which makes
+/// this suitable for structure-agnostic tools such as dumpers.
+/// Nested elements are reached through [`Tlv::children`].
+/// After an error is returned, the iteration stops.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{BERMode,TlvIter};
+/// use yasna::tags::{TAG_SEQUENCE,TAG_INTEGER,TAG_BOOLEAN};
+/// let data = &[48, 6, 2, 1, 10, 1, 1, 255];
+/// let seq = TlvIter::new(data, BERMode::Der).next().unwrap().unwrap();
+/// assert_eq!(seq.tag(), TAG_SEQUENCE);
+/// assert_eq!(seq.header_len(), 2);
+/// let tags = seq.children().map(|tlv| tlv.map(|tlv| tlv.tag()))
+///     .collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(tags, vec![TAG_INTEGER, TAG_BOOLEAN]);
+/// ```
+#[derive(Debug)]
+pub struct TlvIter<'a> {
+    inner: BERReaderImpl<'a>,
+    failed: bool,
+}
+
+impl<'a> TlvIter<'a> {
+    /// Constructs an iterator over the elements in `buf`
+    /// with the default options for `mode`.
+    pub fn new(buf: &'a [u8], mode: BERMode) -> Self {
+        TlvIter::with_options(buf, BERReaderOptions::new(mode))
+    }
+
+    /// Constructs an iterator over the elements in `buf`
+    /// with the given options.
+    pub fn with_options(buf: &'a [u8], options: BERReaderOptions) -> Self {
+        return TlvIter {
+            inner: BERReaderImpl::new(buf, options),
+            failed: false,
+        };
+    }
+
+    fn read_tlv(&mut self) -> ASN1Result<Tlv<'a>> {
+        let inner = &mut self.inner;
+        let offset = inner.pos;
+        let header = inner.read_header()?;
+        if header.tag == TAG_EOC {
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+        }
+        let data_pos = inner.pos;
+        let contents_end = match header.length {
+            Some(length) => {
+                let limit = data_pos.checked_add(length).ok_or(
+                    ASN1Error::new(ASN1ErrorKind::LengthOverflow))?;
+                if inner.buf.len() < limit {
+                    return Err(ASN1Error::new(ASN1ErrorKind::Eof));
+                }
+                inner.pos = limit;
+                limit
+            },
+            None => {
+                inner.pos = offset;
+                inner.skip_general_impl()?;
+                // Exclude the end-of-contents octets.
+                inner.pos - 2
+            },
+        };
+        return Ok(Tlv {
+            buf: inner.buf,
+            options: inner.options,
+            header,
+            contents_end,
+            end: inner.pos,
+        });
+    }
+}
+
+impl<'a> Iterator for TlvIter<'a> {
+    type Item = ASN1Result<Tlv<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.inner.pos >= self.inner.buf.len() {
+            return None;
+        }
+        let old_pos = self.inner.pos;
+        match self.read_tlv() {
+            Ok(tlv) => Some(Ok(tlv)),
+            Err(e) => {
+                self.failed = true;
+                let buf = self.inner.buf;
+                Some(Err(self.inner.locate_error(e, buf, old_pos)))
+            },
+        }
+    }
+}
+
+/// A step of a path given to [`der_query`] or [`ber_query`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum QueryStep {
+    /// Selects the child at the given (zero-based) index.
+    Child(usize),
+    /// Selects the first child with the given tag.
+    Tagged(Tag),
+}
+
+/// Locates a nested element in DER-encoded data.
+///
+/// The path is applied starting from the first element in `buf`;
+/// an empty path returns that element itself. Only the elements on
+/// the path and their preceding siblings are looked at, so the rest of
+/// the document is neither decoded nor validated.
+/// Returns `None` if the path doesn't exist.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{self,QueryStep};
+/// use yasna::tags::TAG_BOOLEAN;
+/// let data = &[48, 11, 2, 1, 10, 160, 6, 5, 0, 1, 1, 255, 5, 0];
+/// let path = &[QueryStep::Child(1), QueryStep::Tagged(TAG_BOOLEAN)];
+/// let tlv = yasna::der_query(data, path).unwrap().unwrap();
+/// assert_eq!(tlv.contents(), &[255]);
+/// assert_eq!(tlv.offset(), 9);
+/// ```
+pub fn der_query<'a>(buf: &'a [u8], path: &[QueryStep])
+        -> ASN1Result<Option<Tlv<'a>>> {
+    query_general(buf, BERMode::Der, path)
+}
+
+/// Locates a nested element in BER-encoded data.
+///
+/// See [`der_query`] for details.
+pub fn ber_query<'a>(buf: &'a [u8], path: &[QueryStep])
+        -> ASN1Result<Option<Tlv<'a>>> {
+    query_general(buf, BERMode::Ber, path)
+}
+
+fn query_general<'a>(buf: &'a [u8], mode: BERMode, path: &[QueryStep])
+        -> ASN1Result<Option<Tlv<'a>>> {
+    let mut tlv = match TlvIter::new(buf, mode).next() {
+        Some(tlv) => tlv?,
+        None => return Err(ASN1Error::new(ASN1ErrorKind::Eof)),
+    };
+    for step in path {
+        let mut children = tlv.children();
+        let found = match *step {
+            QueryStep::Child(index) => children.nth(index),
+            QueryStep::Tagged(tag) => children.find(|child| {
+                match *child {
+                    Ok(ref child) => child.tag() == tag,
+                    Err(_) => true,
+                }
+            }),
+        };
+        tlv = match found {
+            Some(child) => child?,
+            None => return Ok(None),
+        };
+    }
+    return Ok(Some(tlv));
+}
+
+/// The identifier and length octets of an element, decoded.
+///
+/// Obtained from [`BERReader::lookahead_header`] or [`Tlv::header`].
+///
+/// [`BERReader::lookahead_header`]: crate::BERReader::lookahead_header
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{self,PCBit};
+/// use yasna::tags::TAG_SEQUENCE;
+/// let data = &[48, 3, 2, 1, 10];
+/// let header = yasna::parse_der(data, |reader| {
+///     let header = reader.lookahead_header()?;
+///     reader.skip_value()?;
+///     return Ok(header);
+/// }).unwrap();
+/// assert_eq!(header.tag(), TAG_SEQUENCE);
+/// assert_eq!(header.pcbit(), PCBit::Constructed);
+/// assert_eq!(header.length(), Some(3));
+/// assert_eq!((header.offset(), header.header_len()), (0, 2));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ElementHeader {
+    pub(super) tag: Tag,
+    pub(super) pcbit: PCBit,
+    pub(super) offset: usize,
+    pub(super) header_len: usize,
+    pub(super) length: Option<usize>,
+}
+
+impl ElementHeader {
+    /// Returns the tag of the element.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the class of the tag of the element.
+    pub fn tag_class(&self) -> TagClass {
+        self.tag.tag_class
+    }
+
+    /// Returns whether the element is primitive or constructed.
+    pub fn pcbit(&self) -> PCBit {
+        self.pcbit
+    }
+
+    /// Returns the declared length of the contents,
+    /// or `None` for the indefinite-length form.
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+
+    /// Returns the length of the identifier and length octets.
+    pub fn header_len(&self) -> usize {
+        self.header_len
+    }
+
+    /// Returns the absolute offset of the identifier octets
+    /// in the input.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// A raw BER/DER-encoded element, yielded by [`TlvIter`].
+#[derive(Debug, Clone, Copy)]
+pub struct Tlv<'a> {
+    buf: &'a [u8],
+    options: BERReaderOptions,
+    header: ElementHeader,
+    contents_end: usize,
+    end: usize,
+}
+
+impl<'a> Tlv<'a> {
+    /// Returns the decoded identifier and length octets.
+    pub fn header(&self) -> ElementHeader {
+        self.header
+    }
+
+    /// Returns the tag of the element.
+    pub fn tag(&self) -> Tag {
+        self.header.tag
+    }
+
+    /// Returns whether the element is primitive or constructed.
+    pub fn pcbit(&self) -> PCBit {
+        self.header.pcbit
+    }
+
+    /// Returns the offset of the identifier octets, relative to the
+    /// start of the buffer given to [`TlvIter`].
+    pub fn offset(&self) -> usize {
+        self.header.offset
+    }
+
+    /// Returns the length of the identifier and length octets.
+    pub fn header_len(&self) -> usize {
+        self.header.header_len
+    }
+
+    /// Returns the contents octets. For the indefinite-length form,
+    /// the end-of-contents octets are not included.
+    pub fn contents(&self) -> &'a [u8] {
+        &self.buf[self.data_pos()..self.contents_end]
+    }
+
+    /// Returns the whole encoding of the element, including its
+    /// header and end-of-contents octets, if any.
+    pub fn raw(&self) -> &'a [u8] {
+        &self.buf[self.header.offset..self.end]
+    }
+
+    /// Returns an iterator over the elements in the contents.
+    ///
+    /// This is meaningful for constructed elements, but is allowed
+    /// for primitive ones as well to look into encapsulated data.
+    /// Offsets are still relative to the original buffer.
+    pub fn children(&self) -> TlvIter<'a> {
+        return TlvIter {
+            inner: BERReaderImpl::with_pos(&self.buf[..self.contents_end],
+                self.data_pos(), self.options),
+            failed: false,
+        };
+    }
+
+    fn data_pos(&self) -> usize {
+        self.header.offset + self.header.header_len
+    }
+}
@@ -17,7 +17,9 @@ use num_bigint::{BigInt,BigUint};
 use bit_vec::BitVec;
 
 use super::{DERWriter,construct_der};
-use super::models::ObjectIdentifier;
+use super::models::{BitString,ObjectIdentifier};
+#[cfg(feature = "num-bigint")]
+use super::models::BigObjectIdentifier;
 #[cfg(feature = "time")]
 use super::models::{UTCTime,GeneralizedTime};
 
@@ -45,12 +47,13 @@ use super::models::{UTCTime,GeneralizedTime};
 /// - `()` as NULL encoder.
 /// - Tuples (except `()`) as SEQUENCE encoder.
 /// - `Vec<u8>`/`[u8]` as OCTETSTRING encoder.
-/// - `BitVec` as BITSTRING encoder.
+/// - `BitVec`/`BitString` as BITSTRING encoder.
 /// - `String`/`str` as UTF8String encoder.
-/// - `i64`, `u64`, `i32`, `u32`, `i16`, `u16`, `BigInt`, `BigUint`
-///   as INTEGER encoder. (`u8` is avoided because of confliction.)
+/// - `i128`, `u128`, `i64`, `u64`, `i32`, `u32`, `i16`, `u16`, `BigInt`,
+///   `BigUint` as INTEGER encoder. (`u8` is avoided because of confliction.)
 /// - `bool` as BOOLEAN encoder.
 /// - `ObjectIdentifier` as OBJECTT IDENTIFIER encoder.
+/// - `BigObjectIdentifier` as OBJECT IDENTIFIER encoder.
 /// - `UTCTime`/`GeneralizedTime` as UTCTime/GeneralizedTime encoder.
 pub trait DEREncodable {
     /// Writes the value as an DER-encoded ASN.1 value.
@@ -123,6 +126,18 @@ impl DEREncodable for u64 {
     }
 }
 
+impl DEREncodable for i128 {
+    fn encode_der(&self, writer: DERWriter) {
+        writer.write_i128(*self)
+    }
+}
+
+impl DEREncodable for u128 {
+    fn encode_der(&self, writer: DERWriter) {
+        writer.write_u128(*self)
+    }
+}
+
 impl DEREncodable for i32 {
     fn encode_der(&self, writer: DERWriter) {
         writer.write_i32(*self)
@@ -167,6 +182,12 @@ impl DEREncodable for bool {
     }
 }
 
+impl DEREncodable for f64 {
+    fn encode_der(&self, writer: DERWriter) {
+        writer.write_real(*self)
+    }
+}
+
 #[cfg(feature = "bit-vec")]
 impl DEREncodable for BitVec {
     fn encode_der(&self, writer: DERWriter) {
@@ -204,6 +225,19 @@ impl DEREncodable for ObjectIdentifier {
     }
 }
 
+impl DEREncodable for BitString {
+    fn encode_der(&self, writer: DERWriter) {
+        writer.write_bitstring(self)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl DEREncodable for BigObjectIdentifier {
+    fn encode_der(&self, writer: DERWriter) {
+        writer.write_big_oid(self)
+    }
+}
+
 #[cfg(feature = "time")]
 impl DEREncodable for UTCTime {
     fn encode_der(&self, writer: DERWriter) {
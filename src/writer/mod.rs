@@ -9,20 +9,50 @@
 
 #![forbid(missing_docs)]
 
+use alloc::vec;
 use alloc::vec::Vec;
+use core::fmt::{self, Display};
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::io;
 
 #[cfg(feature = "num-bigint")]
 use num_bigint::{BigUint, BigInt};
+#[cfg(feature = "num-bigint")]
+use super::models::BigObjectIdentifier;
 #[cfg(feature = "bit-vec")]
 use bit_vec::BitVec;
+#[cfg(feature = "bitvec")]
+use bitvec::{order::BitOrder, slice::BitSlice, store::BitStore};
 
 use super::{PCBit, Tag};
 use super::tags::{TAG_BOOLEAN,TAG_INTEGER,TAG_OCTETSTRING};
 use super::tags::{TAG_NULL,TAG_OID,TAG_UTF8STRING,TAG_SEQUENCE,TAG_SET,TAG_ENUM,TAG_IA5STRING,TAG_BMPSTRING};
 use super::tags::{TAG_NUMERICSTRING,TAG_PRINTABLESTRING,TAG_VISIBLESTRING};
-use super::models::{ObjectIdentifier,TaggedDerValue};
+use super::models::{ObjectIdentifier,RelativeOid,TaggedDerValue};
+use super::models::{BitString,EmbeddedPdv,PdvIdentification};
 #[cfg(feature = "time")]
-use super::models::{UTCTime,GeneralizedTime};
+use super::models::{UTCTime,GeneralizedTime,Date,TimeOfDay,DateTime};
+use super::models::Duration;
+use super::models::{UTCTimeFields,GeneralizedTimeFields};
+
+mod plan;
+mod scratch;
+mod sink;
+
+/// The number of length octets reserved for a constructed value
+/// by default, enough for contents shorter than 64 KiB.
+const DEFAULT_LENGTH_OCTETS: usize = 3;
+
+use self::plan::{Length, Plan, TwoPass};
+use self::scratch::Scratch;
+use self::sink::{Discard, SliceSink};
+pub use self::sink::{DERSink, Tee};
+#[cfg(feature = "std")]
+pub use self::sink::IoSink;
+#[cfg(feature = "digest")]
+pub use self::sink::DigestSink;
 
 /// Constructs DER-encoded data as `Vec<u8>`.
 ///
@@ -43,14 +73,56 @@ use super::models::{UTCTime,GeneralizedTime};
 /// ```
 pub fn construct_der<F>(callback: F) -> Vec<u8>
         where F: FnOnce(DERWriter) {
-    let mut buf = Vec::new();
+    let mut buf = Scratch::new();
+    {
+        let mut writer = DERWriterSeq {
+            buf: &mut buf.0,
+            plan: None,
+            length_octets: DEFAULT_LENGTH_OCTETS,
+        };
+        callback(writer.next());
+    }
+    return buf.take();
+}
+
+/// Constructs DER-encoded data as `Vec<u8>`, reserving `capacity` bytes.
+///
+/// Same as [`construct_der`], only that the output buffer is allocated
+/// with the given capacity in advance. If the approximate size of the
+/// output is known, e.g. when re-encoding parsed data, this avoids
+/// growing the buffer repeatedly.
+///
+/// With `zeroize` feature, the work buffers used while encoding are
+/// zeroized on drop, and so is the output if the callback panics. A
+/// buffer that has to grow is copied to a new allocation and the old
+/// one is wiped, so reserving enough here also saves those copies.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let der = yasna::construct_der_with_capacity(64, |writer| {
+///     writer.write_sequence(|writer| {
+///         writer.next().write_i64(10);
+///         writer.next().write_bool(true);
+///     })
+/// });
+/// assert_eq!(der, vec![48, 6, 2, 1, 10, 1, 1, 255]);
+/// assert!(der.capacity() >= 64);
+/// ```
+pub fn construct_der_with_capacity<F>(capacity: usize, callback: F)
+        -> Vec<u8>
+        where F: FnOnce(DERWriter) {
+    let mut buf = Scratch::with_capacity(capacity);
     {
         let mut writer = DERWriterSeq {
-            buf: &mut buf,
+            buf: &mut buf.0,
+            plan: None,
+            length_octets: DEFAULT_LENGTH_OCTETS,
         };
         callback(writer.next());
     }
-    return buf;
+    return buf.take();
 }
 
 /// Tries to construct DER-encoded data as `Vec<u8>`.
@@ -85,14 +157,16 @@ pub fn construct_der<F>(callback: F) -> Vec<u8>
 /// ```
 pub fn try_construct_der<F, E>(callback: F) -> Result<Vec<u8>, E>
         where F: FnOnce(DERWriter) -> Result<(), E> {
-    let mut buf = Vec::new();
+    let mut buf = Scratch::new();
     {
         let mut writer = DERWriterSeq {
-            buf: &mut buf,
+            buf: &mut buf.0,
+            plan: None,
+            length_octets: DEFAULT_LENGTH_OCTETS,
         };
         callback(writer.next())?;
     }
-    return Ok(buf);
+    return Ok(buf.take());
 }
 
 /// Constructs DER-encoded sequence of data as `Vec<u8>`.
@@ -115,14 +189,16 @@ pub fn try_construct_der<F, E>(callback: F) -> Result<Vec<u8>, E>
 /// ```
 pub fn construct_der_seq<F>(callback: F) -> Vec<u8>
         where F: FnOnce(&mut DERWriterSeq) {
-    let mut buf = Vec::new();
+    let mut buf = Scratch::new();
     {
         let mut writer = DERWriterSeq {
-            buf: &mut buf,
+            buf: &mut buf.0,
+            plan: None,
+            length_octets: DEFAULT_LENGTH_OCTETS,
         };
         callback(&mut writer);
     }
-    return buf;
+    return buf.take();
 }
 
 /// Tries to construct a DER-encoded sequence of data as `Vec<u8>`.
@@ -150,14 +226,329 @@ pub fn construct_der_seq<F>(callback: F) -> Vec<u8>
 /// ```
 pub fn try_construct_der_seq<F, E>(callback: F) -> Result<Vec<u8> , E>
         where F: FnOnce(&mut DERWriterSeq) -> Result<(), E> {
-    let mut buf = Vec::new();
+    let mut buf = Scratch::new();
     {
         let mut writer = DERWriterSeq {
-            buf: &mut buf,
+            buf: &mut buf.0,
+            plan: None,
+            length_octets: DEFAULT_LENGTH_OCTETS,
         };
         callback(&mut writer)?;
     }
-    return Ok(buf);
+    return Ok(buf.take());
+}
+
+/// Constructs DER-encoded data as `Vec<u8>`, computing lengths in advance.
+///
+/// The result is the same as [`construct_der`]. `construct_der` reserves
+/// 3 length octets for each constructed value and shifts the contents
+/// when the guess is wrong, which costs time proportional to the nesting
+/// depth. This function instead calls `callback` twice: the first call
+/// only measures the lengths of constructed values, and the second one
+/// writes the data once, into a buffer allocated to the exact size. The
+/// callback must write the same values in both calls.
+///
+/// It pays off for deeply nested data; for shallow data, running the
+/// callback twice is usually slower.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let der = yasna::construct_der_two_pass(|writer| {
+///     writer.write_sequence(|writer| {
+///         writer.next().write_i64(10);
+///         writer.next().write_bool(true);
+///     })
+/// });
+/// assert_eq!(der, vec![48, 6, 2, 1, 10, 1, 1, 255]);
+/// ```
+///
+/// # Panics
+///
+/// Panics when the two calls of `callback` write different values.
+pub fn construct_der_two_pass<F>(mut callback: F) -> Vec<u8>
+        where F: FnMut(DERWriter) {
+    let mut plan = TwoPass::new(Vec::new());
+    let mut buf = Scratch::new();
+    callback(DERWriter::from_buf(&mut buf.0, Some(&mut plan)));
+    let length = plan.finish_pass(&mut buf.0);
+    plan.sink.reserve_exact(length);
+    callback(DERWriter::from_buf(&mut buf.0, Some(&mut plan)));
+    plan.finish_pass(&mut buf.0);
+    return plan.sink;
+}
+
+/// Constructs DER-encoded data into the given slice.
+///
+/// Returns the length of the data, which is written at the start of
+/// `buf`. `callback` is called twice: the first call measures the data,
+/// and the second one writes it, unless it doesn't fit in `buf`. The
+/// callback must write the same values in both calls.
+///
+/// This is not an allocation-free API. Only the output is never
+/// allocated: the writer still uses a small working buffer, a `usize`
+/// per constructed value, and room for the largest primitive value or
+/// SET, all of which are on the heap, as yasna always requires an
+/// allocator. It suits output that must land in a given buffer, such as
+/// a fixed-size frame or a memory-mapped region.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let mut buf = [0; 16];
+/// let len = yasna::construct_der_in(&mut buf, |writer| {
+///     writer.write_sequence(|writer| {
+///         writer.next().write_i64(10);
+///         writer.next().write_bool(true);
+///     })
+/// }).unwrap();
+/// assert_eq!(&buf[..len], &[48, 6, 2, 1, 10, 1, 1, 255]);
+///
+/// let mut buf = [0; 4];
+/// let err = yasna::construct_der_in(&mut buf, |writer| {
+///     writer.write_bytes(b"Hello!")
+/// }).unwrap_err();
+/// assert_eq!(err.required(), 8);
+/// ```
+///
+/// # Errors
+///
+/// It returns [`BufferTooSmall`] if the data doesn't fit in `buf`,
+/// in which case `buf` is left untouched.
+///
+/// # Panics
+///
+/// Panics when the two calls of `callback` write different values.
+pub fn construct_der_in<F>(buf: &mut [u8], mut callback: F)
+        -> Result<usize, BufferTooSmall>
+        where F: FnMut(DERWriter) {
+    let mut plan = TwoPass::new(SliceSink {
+        buf,
+        pos: 0,
+    });
+    let mut work = Scratch::new();
+    callback(DERWriter::from_buf(&mut work.0, Some(&mut plan)));
+    let length = plan.finish_pass(&mut work.0);
+    if length > plan.sink.buf.len() {
+        return Err(BufferTooSmall {
+            required: length,
+        });
+    }
+    callback(DERWriter::from_buf(&mut work.0, Some(&mut plan)));
+    plan.finish_pass(&mut work.0);
+    return Ok(length);
+}
+
+/// An error indicating that the data doesn't fit in the slice passed to
+/// [`construct_der_in`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BufferTooSmall {
+    required: usize,
+}
+
+impl BufferTooSmall {
+    /// Returns the length of the data, that is, the required buffer size.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for BufferTooSmall {}
+
+impl Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Buffer too small: {} bytes required", self.required)
+    }
+}
+
+/// An error indicating that an element of a SET or SET OF was left empty,
+/// returned by [`DERWriter::try_write_set`] and
+/// [`DERWriter::try_write_set_of`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct EmptySetElement {
+    index: usize,
+}
+
+impl EmptySetElement {
+    /// Returns the index of the empty element, in the order the elements
+    /// were written.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for EmptySetElement {}
+
+impl Display for EmptySetElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Empty SET element at index {}", self.index)
+    }
+}
+
+/// Constructs DER-encoded data into a [`DERSink`].
+///
+/// Unlike [`construct_der`], the output is fed to `sink` piece by piece
+/// instead of being held in memory as a whole. Since DER needs the
+/// length of a value before its contents, `callback` is called twice:
+/// the first call only measures the lengths of constructed values, and
+/// the second one writes the data. The callback must write the same
+/// values in both calls.
+///
+/// The memory use is bounded by a few kilobytes of buffer, a `usize`
+/// per constructed value, and the largest primitive value or SET
+/// (whose elements need sorting).
+///
+/// [`Tee`] feeds the output into several sinks at once.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::{self,Tee};
+/// let mut first = Vec::new();
+/// let mut second = Vec::new();
+/// yasna::construct_der_to_sink(&mut Tee::new(&mut first, &mut second),
+///     |writer| {
+///         writer.write_sequence(|writer| {
+///             writer.next().write_i64(10);
+///             writer.next().write_bool(true);
+///         })
+///     });
+/// assert_eq!(first, vec![48, 6, 2, 1, 10, 1, 1, 255]);
+/// assert_eq!(second, first);
+/// ```
+///
+/// # Panics
+///
+/// Panics when the two calls of `callback` write different values.
+pub fn construct_der_to_sink<S, F>(sink: &mut S, mut callback: F)
+        where S: DERSink + ?Sized, F: FnMut(DERWriter) {
+    let mut plan = TwoPass::new(sink);
+    let mut buf = Scratch::new();
+    for _ in 0..2 {
+        callback(DERWriter::from_buf(&mut buf.0, Some(&mut plan)));
+        plan.finish_pass(&mut buf.0);
+    }
+}
+
+/// Feeds DER-encoded data into a hash function.
+///
+/// This computes the same digest as hashing the result of
+/// [`construct_der`], without holding the data in memory as a whole.
+/// As with [`construct_der_to_sink`], `callback` is called twice and
+/// must write the same values in both calls.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// // Any `digest::Update`, such as `sha2::Sha256`, can be fed.
+/// struct Collect(Vec<u8>);
+/// impl digest::Update for Collect {
+///     fn update(&mut self, data: &[u8]) {
+///         self.0.extend_from_slice(data);
+///     }
+/// }
+/// let mut hasher = Collect(Vec::new());
+/// yasna::construct_der_to_digest(&mut hasher, |writer| {
+///     writer.write_sequence(|writer| {
+///         writer.next().write_i64(10);
+///         writer.next().write_bool(true);
+///     })
+/// });
+/// assert_eq!(hasher.0, &[48, 6, 2, 1, 10, 1, 1, 255]);
+/// ```
+///
+/// # Panics
+///
+/// Panics when the two calls of `callback` write different values.
+///
+/// # Features
+///
+/// This function is enabled by `digest` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["digest"] }
+/// ```
+#[cfg(feature = "digest")]
+pub fn construct_der_to_digest<D, F>(digest: &mut D, callback: F)
+        where D: digest::Update + ?Sized, F: FnMut(DERWriter) {
+    construct_der_to_sink(&mut DigestSink::new(digest), callback);
+}
+
+/// Computes the length of the DER-encoded data written by `callback`,
+/// without producing the data.
+///
+/// The result is the same as `construct_der(callback).len()`, but the
+/// output isn't kept: only a bounded buffer, a `usize` per constructed
+/// value, and the largest primitive value or SET are held in memory.
+/// It can be used to preallocate buffers or to enforce size limits.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let len = yasna::encoded_len(|writer| {
+///     writer.write_sequence(|writer| {
+///         writer.next().write_i64(10);
+///         writer.next().write_bytes(&[0; 200]);
+///     })
+/// });
+/// assert_eq!(len, 209);
+/// ```
+pub fn encoded_len<F>(callback: F) -> usize
+        where F: FnOnce(DERWriter) {
+    let mut plan = TwoPass::new(Discard);
+    let mut buf = Scratch::new();
+    callback(DERWriter::from_buf(&mut buf.0, Some(&mut plan)));
+    return plan.finish_pass(&mut buf.0);
+}
+
+/// Constructs DER-encoded data into an [`io::Write`](std::io::Write).
+///
+/// This is a shorthand of [`construct_der_to_sink`] with an [`IoSink`]:
+/// the output is written out piece by piece, and `callback` is called
+/// twice and must write the same values in both calls.
+///
+/// # Examples
+///
+/// ```
+/// use yasna;
+/// let mut der = Vec::new();
+/// yasna::construct_der_to_writer(&mut der, |writer| {
+///     writer.write_sequence(|writer| {
+///         writer.next().write_i64(10);
+///         writer.next().write_bool(true);
+///     })
+/// })?;
+/// assert_eq!(der, vec![48, 6, 2, 1, 10, 1, 1, 255]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Panics
+///
+/// Panics when the two calls of `callback` write different values.
+///
+/// # Features
+///
+/// This function is enabled by `std` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["std"] }
+/// ```
+#[cfg(feature = "std")]
+pub fn construct_der_to_writer<W, F>(writer: W, callback: F)
+        -> io::Result<()>
+        where W: io::Write, F: FnMut(DERWriter) {
+    let mut sink = IoSink::new(writer);
+    construct_der_to_sink(&mut sink, callback);
+    sink.finish()?;
+    return Ok(());
 }
 
 /// A writer object that accepts an ASN.1 value.
@@ -181,15 +572,32 @@ pub fn try_construct_der_seq<F, E>(callback: F) -> Result<Vec<u8> , E>
 pub struct DERWriter<'a> {
     buf: &'a mut Vec<u8>,
     implicit_tag: Option<Tag>,
+    plan: Option<&'a mut (dyn Plan + 'a)>,
+    length_octets: usize,
 }
 
 impl<'a> DERWriter<'a> {
-    fn from_buf(buf: &'a mut Vec<u8>) -> Self {
+    fn from_buf(buf: &'a mut Vec<u8>,
+            plan: Option<&'a mut (dyn Plan + 'a)>) -> Self {
         return DERWriter {
             buf,
             implicit_tag: None,
+            plan,
+            length_octets: DEFAULT_LENGTH_OCTETS,
         }
     }
+    /// Appends a byte to the output.
+    fn push(&mut self, byte: u8) {
+        scratch::reserve(self.buf, 1);
+        self.buf.push(byte);
+    }
+
+    /// Appends bytes to the output.
+    fn extend(&mut self, bytes: &[u8]) {
+        scratch::reserve(self.buf, bytes.len());
+        self.buf.extend_from_slice(bytes);
+    }
+
     /// Writes BER identifier (tag + primitive/constructed) octets.
     fn write_identifier(&mut self, tag: Tag, pc: PCBit) {
         let tag = if let Some(tag) = self.implicit_tag { tag } else { tag };
@@ -197,36 +605,36 @@ impl<'a> DERWriter<'a> {
         let classid = tag.tag_class as u8;
         let pcid = pc as u8;
         if tag.tag_number < 31 {
-            self.buf.push(
+            self.push(
                 (classid << 6) | (pcid << 5) | (tag.tag_number as u8));
             return;
         }
-        self.buf.push((classid << 6) | (pcid << 5) | 31);
+        self.push((classid << 6) | (pcid << 5) | 31);
         let mut shiftnum = 63; // ceil(64 / 7) * 7 - 7
         while (tag.tag_number >> shiftnum) == 0 {
             shiftnum -= 7;
         }
         while shiftnum > 0 {
-            self.buf.push(128 | (((tag.tag_number >> shiftnum) & 127) as u8));
+            self.push(128 | (((tag.tag_number >> shiftnum) & 127) as u8));
             shiftnum -= 7;
         }
-        self.buf.push((tag.tag_number & 127) as u8);
+        self.push((tag.tag_number & 127) as u8);
     }
 
     /// Writes BER length octets.
     fn write_length(&mut self, length: usize) {
         let length = length as u64;
         if length < 128 {
-            self.buf.push(length as u8);
+            self.push(length as u8);
             return;
         }
         let mut shiftnum = 56; // ceil(64 / 8) * 8 - 8
         while (length >> shiftnum) == 0 {
             shiftnum -= 8;
         }
-        self.buf.push(128 | ((shiftnum / 8 + 1) as u8));
+        self.push(128 | ((shiftnum / 8 + 1) as u8));
         loop {
-            self.buf.push((length >> shiftnum) as u8);
+            self.push((length >> shiftnum) as u8);
             if shiftnum == 0 {
                 break;
             }
@@ -236,14 +644,17 @@ impl<'a> DERWriter<'a> {
 
     /// Deals with unknown length procedures.
     /// This function first marks the current position and
-    /// allocates 3 bytes. Then it calls back `callback`.
+    /// allocates `length_octets` bytes. Then it calls back `callback`.
     /// It then calculates the length and moves the written data
     /// to the actual position. Finally, it writes the length.
     fn with_length<T, F>(&mut self, callback: F) -> T
         where F: FnOnce(&mut Self) -> T {
-        let expected_length_length = 3;
-        for _ in 0..3 {
-            self.buf.push(255);
+        if self.plan.is_some() {
+            return self.with_planned_length(callback);
+        }
+        let expected_length_length = self.length_octets;
+        for _ in 0..expected_length_length {
+            self.push(255);
         }
         let start_pos = self.buf.len();
         let result = callback(self);
@@ -266,7 +677,9 @@ impl<'a> DERWriter<'a> {
         } else if length_length > expected_length_length {
             let diff = length_length - expected_length_length;
             new_start_pos = start_pos + diff;
-            for _ in 0..diff { self.buf.insert(start_pos, 0); }
+            scratch::reserve(self.buf, diff);
+            self.buf.splice(start_pos .. start_pos,
+                core::iter::repeat(0).take(diff));
         } else {
             new_start_pos = start_pos;
         }
@@ -288,6 +701,73 @@ impl<'a> DERWriter<'a> {
         return result;
     }
 
+    /// Same as `with_length`, but in a two-pass construction.
+    /// The measuring pass records the length of the contents, and
+    /// the emitting pass writes the recorded length before them.
+    fn with_planned_length<T, F>(&mut self, callback: F) -> T
+        where F: FnOnce(&mut Self) -> T {
+        let length = self.plan.as_mut().unwrap().open();
+        if let Length::Known(length) = length {
+            self.write_length(length);
+        }
+        plan::flush_if_full(&mut self.plan, self.buf);
+        let start_pos = self.position();
+        let result = callback(self);
+        let actual_length = self.position() - start_pos;
+        match length {
+            Length::Unknown(index) => {
+                self.plan.as_mut().unwrap().close(index, actual_length);
+                // Only the number of the length octets matters here.
+                self.write_length(actual_length);
+            },
+            Length::Known(length) => {
+                assert!(actual_length == length,
+                    "The callback wrote different values in the two passes");
+            },
+        }
+        plan::flush_if_full(&mut self.plan, self.buf);
+        return result;
+    }
+
+    /// Returns the number of bytes written so far, including the ones
+    /// already flushed in a two-pass construction.
+    fn position(&self) -> usize {
+        let flushed = self.plan.as_ref().map_or(0, |plan| plan.flushed());
+        return flushed + self.buf.len();
+    }
+
+    /// Sets the number of length octets reserved for constructed values
+    /// written with this writer, including nested ones.
+    ///
+    /// The length of a constructed value is only known after its contents
+    /// are written. The writer reserves room for the length octets in
+    /// advance, and moves the contents when the guess was wrong. The
+    /// default is 3, which fits contents shorter than 64 KiB. For values
+    /// known to be shorter than 128 bytes, 1 avoids moving the contents.
+    /// The output is the same regardless of the hint.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `count` is not between 1 and 9.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.length_octets_hint(1).write_sequence(|writer| {
+    ///         writer.next().write_i64(10);
+    ///         writer.next().write_bool(true);
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![48, 6, 2, 1, 10, 1, 1, 255]);
+    /// ```
+    pub fn length_octets_hint(mut self, count: usize) -> Self {
+        assert!((1..=9).contains(&count), "Invalid number of length octets");
+        self.length_octets = count;
+        return self;
+    }
+
     /// Writes `bool` as an ASN.1 BOOLEAN value.
     ///
     /// # Examples
@@ -302,7 +782,7 @@ impl<'a> DERWriter<'a> {
     pub fn write_bool(mut self, val: bool) {
         self.write_identifier(TAG_BOOLEAN, PCBit::Primitive);
         self.write_length(1);
-        self.buf.push(if val { 255 } else { 0 });
+        self.push(if val { 255 } else { 0 });
     }
 
     fn write_integer(mut self, tag: Tag, val: i64) {
@@ -314,7 +794,7 @@ impl<'a> DERWriter<'a> {
         self.write_identifier(tag, PCBit::Primitive);
         self.write_length(shiftnum / 8 + 1);
         loop {
-            self.buf.push((val >> shiftnum) as u8);
+            self.push((val >> shiftnum) as u8);
             if shiftnum == 0 {
                 break;
             }
@@ -322,6 +802,29 @@ impl<'a> DERWriter<'a> {
         }
     }
 
+    /// Writes the content of a REAL value that has a special encoding
+    /// (zero, infinities, NaN and minus zero), including its length.
+    /// Returns `false` without writing anything for other values.
+    fn write_real_special(&mut self, val: f64) -> bool {
+        let octet = if val.is_nan() {
+            66
+        } else if val == core::f64::INFINITY {
+            64
+        } else if val == core::f64::NEG_INFINITY {
+            65
+        } else if val == 0.0 && val.is_sign_negative() {
+            67
+        } else if val == 0.0 {
+            self.write_length(0);
+            return true;
+        } else {
+            return false;
+        };
+        self.write_length(1);
+        self.push(octet);
+        return true;
+    }
+
     /// Writes `i64` as an ASN.1 ENUMERATED value.
     ///
     /// # Examples
@@ -352,20 +855,39 @@ impl<'a> DERWriter<'a> {
         self.write_integer(TAG_INTEGER, val);
     }
 
+    /// Writes `u64` as an ASN.1 ENUMERATED value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_enum_u64(128)
+    /// });
+    /// assert_eq!(der, vec![10, 2, 0, 128]);
+    /// ```
+    pub fn write_enum_u64(self, val: u64) {
+        self.write_uinteger(TAG_ENUM, val);
+    }
+
     /// Writes `u64` as an ASN.1 INTEGER value.
-    pub fn write_u64(mut self, val: u64) {
+    pub fn write_u64(self, val: u64) {
+        self.write_uinteger(TAG_INTEGER, val);
+    }
+
+    fn write_uinteger(mut self, tag: Tag, val: u64) {
         let mut shiftnum = 64;
         while shiftnum > 0 && val >> (shiftnum-1) == 0 {
             shiftnum -= 8;
         }
-        self.write_identifier(TAG_INTEGER, PCBit::Primitive);
+        self.write_identifier(tag, PCBit::Primitive);
         self.write_length(shiftnum / 8 + 1);
         if shiftnum == 64 {
-            self.buf.push(0);
+            self.push(0);
             shiftnum -= 8;
         }
         loop {
-            self.buf.push((val >> shiftnum) as u8);
+            self.push((val >> shiftnum) as u8);
             if shiftnum == 0 {
                 break;
             }
@@ -373,103 +895,306 @@ impl<'a> DERWriter<'a> {
         }
     }
 
-    /// Writes `i32` as an ASN.1 INTEGER value.
-    pub fn write_i32(self, val: i32) {
-        self.write_i64(val as i64)
-    }
-
-    /// Writes `u32` as an ASN.1 INTEGER value.
-    pub fn write_u32(self, val: u32) {
-        self.write_i64(val as i64)
-    }
-
-    /// Writes `i16` as an ASN.1 INTEGER value.
-    pub fn write_i16(self, val: i16) {
-        self.write_i64(val as i64)
-    }
-
-    /// Writes `u16` as an ASN.1 INTEGER value.
-    pub fn write_u16(self, val: u16) {
-        self.write_i64(val as i64)
-    }
-
-    /// Writes `i8` as an ASN.1 INTEGER value.
-    pub fn write_i8(self, val: i8) {
-        self.write_i64(val as i64)
-    }
-
-    /// Writes `u8` as an ASN.1 INTEGER value.
-    pub fn write_u8(self, val: u8) {
-        self.write_i64(val as i64)
-    }
-
-    #[cfg(feature = "num-bigint")]
-    /// Writes `BigInt` as an ASN.1 INTEGER value.
+    /// Writes `i128` as an ASN.1 INTEGER value.
     ///
     /// # Examples
     ///
     /// ```
-    /// # fn main() {
     /// use yasna;
-    /// use num_bigint::BigInt;
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_bigint(
-    ///         &BigInt::parse_bytes(b"1234567890", 10).unwrap())
+    ///     writer.write_i128(-18446744073709551616)
     /// });
-    /// assert_eq!(der, vec![2, 4, 73, 150, 2, 210]);
-    /// # }
-    /// ```
-    ///
-    /// # Features
-    ///
-    /// This method is enabled by `num` feature.
-    ///
-    /// ```toml
-    /// [dependencies]
-    /// yasna = { version = "*", features = ["num-bigint"] }
+    /// assert_eq!(der, vec![2, 9, 255, 0, 0, 0, 0, 0, 0, 0, 0]);
     /// ```
-    pub fn write_bigint(mut self, val: &BigInt) {
-        use num_bigint::Sign;
-        self.write_identifier(TAG_INTEGER, PCBit::Primitive);
-        let (sign, mut bytes) = val.to_bytes_le();
-        match sign {
-            Sign::NoSign => {
-                self.write_length(1);
-                self.buf.push(0);
-            },
-            Sign::Plus => {
-                let byteslen = bytes.len();
-                debug_assert!(bytes[byteslen-1] != 0);
-                if bytes[byteslen-1] >= 128 {
-                    self.write_length(byteslen+1);
-                    self.buf.push(0);
-                } else {
-                    self.write_length(byteslen);
-                }
-                bytes.reverse();
-                self.buf.extend_from_slice(&bytes);
-                return;
-            },
-            Sign::Minus => {
-                let byteslen = bytes.len();
-                debug_assert!(bytes[byteslen-1] != 0);
-                let mut carry : usize = 1;
-                for b in bytes.iter_mut() {
-                    let bval = 255 - (*b as usize);
-                    *b = (bval + carry) as u8;
-                    carry = (bval + carry) >> 8;
-                }
-                if bytes[byteslen-1] < 128 {
-                    self.write_length(byteslen+1);
-                    self.buf.push(255);
-                } else {
-                    self.write_length(byteslen);
-                }
-                bytes.reverse();
-                self.buf.extend_from_slice(&bytes);
-                return;
+    pub fn write_i128(mut self, val: i128) {
+        let mut shiftnum = 120;
+        while shiftnum > 0 &&
+                (val >> (shiftnum-1) == 0 || val >> (shiftnum-1) == -1) {
+            shiftnum -= 8;
+        }
+        self.write_identifier(TAG_INTEGER, PCBit::Primitive);
+        self.write_length(shiftnum / 8 + 1);
+        loop {
+            self.push((val >> shiftnum) as u8);
+            if shiftnum == 0 {
+                break;
+            }
+            shiftnum -= 8;
+        }
+    }
+
+    /// Writes `u128` as an ASN.1 INTEGER value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_u128(18446744073709551616)
+    /// });
+    /// assert_eq!(der, vec![2, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// ```
+    pub fn write_u128(mut self, val: u128) {
+        let mut shiftnum = 128;
+        while shiftnum > 0 && val >> (shiftnum-1) == 0 {
+            shiftnum -= 8;
+        }
+        self.write_identifier(TAG_INTEGER, PCBit::Primitive);
+        self.write_length(shiftnum / 8 + 1);
+        if shiftnum == 128 {
+            self.push(0);
+            shiftnum -= 8;
+        }
+        loop {
+            self.push((val >> shiftnum) as u8);
+            if shiftnum == 0 {
+                break;
+            }
+            shiftnum -= 8;
+        }
+    }
+
+    /// Writes a big-endian two's complement integer as an ASN.1 INTEGER
+    /// value.
+    ///
+    /// Redundant leading `0x00`/`0xFF` octets are removed, so that the
+    /// output is always the minimal encoding.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `bytes` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_int_bytes(&[0, 0, 128, 1])
+    /// });
+    /// assert_eq!(der, vec![2, 3, 0, 128, 1]);
+    /// ```
+    pub fn write_int_bytes(mut self, bytes: &[u8]) {
+        assert!(!bytes.is_empty(), "INTEGER needs at least one octet");
+        let mut bytes = bytes;
+        while bytes.len() >= 2 &&
+                ((bytes[0] == 0 && bytes[1] < 128) ||
+                (bytes[0] == 255 && bytes[1] >= 128)) {
+            bytes = &bytes[1..];
+        }
+        self.write_identifier(TAG_INTEGER, PCBit::Primitive);
+        self.write_length(bytes.len());
+        self.extend(bytes);
+    }
+
+    /// Writes an integer given by its sign and big-endian magnitude as an
+    /// ASN.1 INTEGER value.
+    ///
+    /// This lets any big integer type be written, as long as it can
+    /// export its absolute value as bytes. Leading zeros in `magnitude`
+    /// are allowed, and an empty `magnitude` means zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_int_from_be_bytes(true, &[1, 0])
+    /// });
+    /// assert_eq!(der, vec![2, 2, 255, 0]);
+    /// ```
+    pub fn write_int_from_be_bytes(mut self, negative: bool,
+            magnitude: &[u8]) {
+        let start = magnitude.iter().position(|&b| b != 0)
+            .unwrap_or(magnitude.len());
+        let magnitude = &magnitude[start..];
+        self.write_identifier(TAG_INTEGER, PCBit::Primitive);
+        if magnitude.is_empty() {
+            self.write_length(1);
+            self.push(0);
+        } else if !negative {
+            if magnitude[0] >= 128 {
+                self.write_length(magnitude.len() + 1);
+                self.push(0);
+            } else {
+                self.write_length(magnitude.len());
+            }
+            self.extend(magnitude);
+        } else {
+            let mut bytes = magnitude.to_vec();
+            let mut carry : usize = 1;
+            for b in bytes.iter_mut().rev() {
+                let bval = 255 - (*b as usize);
+                *b = (bval + carry) as u8;
+                carry = (bval + carry) >> 8;
+            }
+            if bytes[0] < 128 {
+                self.write_length(bytes.len() + 1);
+                self.push(255);
+            } else {
+                self.write_length(bytes.len());
             }
+            self.extend(&bytes);
+        }
+    }
+
+    /// Writes `i32` as an ASN.1 INTEGER value.
+    pub fn write_i32(self, val: i32) {
+        self.write_i64(val as i64)
+    }
+
+    /// Writes `u32` as an ASN.1 INTEGER value.
+    pub fn write_u32(self, val: u32) {
+        self.write_i64(val as i64)
+    }
+
+    /// Writes `i16` as an ASN.1 INTEGER value.
+    pub fn write_i16(self, val: i16) {
+        self.write_i64(val as i64)
+    }
+
+    /// Writes `u16` as an ASN.1 INTEGER value.
+    pub fn write_u16(self, val: u16) {
+        self.write_i64(val as i64)
+    }
+
+    /// Writes `i8` as an ASN.1 INTEGER value.
+    pub fn write_i8(self, val: i8) {
+        self.write_i64(val as i64)
+    }
+
+    /// Writes `u8` as an ASN.1 INTEGER value.
+    pub fn write_u8(self, val: u8) {
+        self.write_i64(val as i64)
+    }
+
+    /// Writes `f64` as an ASN.1 REAL value.
+    ///
+    /// The value is written in the binary form of X.690 11.3.1: base 2,
+    /// no scaling factor, an odd mantissa and the shortest exponent.
+    /// Zero is written as an empty content, and infinities, NaN and
+    /// minus zero as the special values of X.690 8.5.9.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_real(1.5)
+    /// });
+    /// assert_eq!(der, vec![9, 3, 128, 255, 3]);
+    /// ```
+    pub fn write_real(mut self, val: f64) {
+        use super::tags::TAG_REAL;
+        self.write_identifier(TAG_REAL, PCBit::Primitive);
+        if self.write_real_special(val) {
+            return;
+        }
+        let bits = val.to_bits();
+        let negative = bits >> 63 != 0;
+        let biased_exponent = ((bits >> 52) & 0x7FF) as i64;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mut mantissa, mut exponent) = if biased_exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased_exponent - 1075)
+        };
+        let zeros = mantissa.trailing_zeros();
+        mantissa >>= zeros;
+        exponent += zeros as i64;
+        let exp_len = if (-128..128).contains(&exponent) { 1 } else { 2 };
+        let mant_len = (64 - mantissa.leading_zeros() as usize + 7) / 8;
+        self.write_length(1 + exp_len + mant_len);
+        self.push(128 | ((negative as u8) << 6) | (exp_len as u8 - 1));
+        for i in (0..exp_len).rev() {
+            self.push((exponent >> (8 * i)) as u8);
+        }
+        for i in (0..mant_len).rev() {
+            self.push((mantissa >> (8 * i)) as u8);
+        }
+    }
+
+    /// Writes `f64` as an ASN.1 REAL value in the decimal form.
+    ///
+    /// The value is written in the ISO 6093 NR3 form with the
+    /// restrictions of X.690 11.3.2 (e.g. `"15.E-1"`), using the shortest
+    /// digit sequence that reads back as the same `f64`. Special values
+    /// are written as in [`write_real`](Self::write_real). This is for
+    /// interoperability with implementations that only accept decimal
+    /// REALs; [`write_real`](Self::write_real) should be preferred
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_real_decimal(1.5)
+    /// });
+    /// assert_eq!(der, vec![9, 7, 3, 49, 53, 46, 69, 45, 49]);
+    /// ```
+    pub fn write_real_decimal(mut self, val: f64) {
+        use alloc::format;
+        use alloc::string::String;
+        use super::tags::TAG_REAL;
+        self.write_identifier(TAG_REAL, PCBit::Primitive);
+        if self.write_real_special(val) {
+            return;
+        }
+        // `{:e}` gives the shortest round-trip digits, e.g. "-3.75e-1".
+        let magnitude = if val < 0.0 { -val } else { val };
+        let sci = format!("{:e}", magnitude);
+        let (digits, exponent) = sci.split_at(sci.find('e').unwrap());
+        let digits : Vec<u8> = digits.bytes().filter(|&b| b != b'.').collect();
+        let mut exponent : i64 = exponent[1..].parse().unwrap();
+        exponent -= digits.len() as i64 - 1;
+        let end = digits.iter().rposition(|&b| b != b'0').unwrap() + 1;
+        exponent += (digits.len() - end) as i64;
+        let digits = &digits[..end];
+        let exponent = if exponent == 0 {
+            String::from("+0")
+        } else {
+            format!("{}", exponent)
         };
+        let sign_len = if val < 0.0 { 1 } else { 0 };
+        self.write_length(1 + sign_len + digits.len() + 2 + exponent.len());
+        self.push(3);
+        if val < 0.0 {
+            self.push(b'-');
+        }
+        self.extend(digits);
+        self.extend(b".E");
+        self.extend(exponent.as_bytes());
+    }
+
+    #[cfg(feature = "num-bigint")]
+    /// Writes `BigInt` as an ASN.1 INTEGER value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() {
+    /// use yasna;
+    /// use num_bigint::BigInt;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_bigint(
+    ///         &BigInt::parse_bytes(b"1234567890", 10).unwrap())
+    /// });
+    /// assert_eq!(der, vec![2, 4, 73, 150, 2, 210]);
+    /// # }
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `num` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["num-bigint"] }
+    /// ```
+    pub fn write_bigint(self, val: &BigInt) {
+        use num_bigint::Sign;
+        let (sign, bytes) = val.to_bytes_be();
+        self.write_int_from_be_bytes(sign == Sign::Minus, &bytes);
     }
 
     #[cfg(feature = "num-bigint")]
@@ -497,24 +1222,8 @@ impl<'a> DERWriter<'a> {
     /// [dependencies]
     /// yasna = { version = "*", features = ["num-bigint"] }
     /// ```
-    pub fn write_biguint(mut self, val: &BigUint) {
-        self.write_identifier(TAG_INTEGER, PCBit::Primitive);
-        let mut bytes = val.to_bytes_le();
-        if &bytes == &[0] {
-            self.write_length(1);
-            self.buf.push(0);
-            return;
-        }
-        let byteslen = bytes.len();
-        debug_assert!(bytes[byteslen-1] != 0);
-        if bytes[byteslen-1] >= 128 {
-            self.write_length(byteslen+1);
-            self.buf.push(0);
-        } else {
-            self.write_length(byteslen);
-        }
-        bytes.reverse();
-        self.buf.extend_from_slice(&bytes);
+    pub fn write_biguint(self, val: &BigUint) {
+        self.write_int_from_be_bytes(false, &val.to_bytes_be());
     }
 
     #[cfg(feature = "bit-vec")]
@@ -577,12 +1286,104 @@ impl<'a> DERWriter<'a> {
         debug_assert!(8 * bytes.len() < len + 8);
         self.write_length(1 + bytes.len());
         let len_diff = 8 * bytes.len() - len;
-        self.buf.push(len_diff as u8);
+        self.push(len_diff as u8);
         if bytes.len() > 0 {
-            self.buf.extend_from_slice(&bytes[0 .. bytes.len() - 1]);
+            self.extend(&bytes[0 .. bytes.len() - 1]);
             let mask = !(255u16 >> (8 - len_diff)) as u8;
-            self.buf.push(bytes[bytes.len() - 1] & mask);
+            self.push(bytes[bytes.len() - 1] & mask);
+        }
+    }
+
+    /// Writes `&[u8]` as an ASN.1 BITSTRING value with no unused bits.
+    ///
+    /// This is a shorthand of `write_bitvec_bytes` for the common
+    /// byte-aligned case, such as signatures and public keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_bitstring_bytes(&[117, 13, 64])
+    /// });
+    /// assert_eq!(&der, &[3, 4, 0, 117, 13, 64]);
+    /// ```
+    pub fn write_bitstring_bytes(self, bytes: &[u8]) {
+        self.write_bitvec_bytes(bytes, 8 * bytes.len());
+    }
+
+    /// Writes [`BitString`] as an ASN.1 BITSTRING value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use yasna::models::BitString;
+    /// let bits = BitString::from_bytes_and_len(vec![117, 13, 64], 18);
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_bitstring(&bits)
+    /// });
+    /// assert_eq!(&der, &[3, 4, 6, 117, 13, 64]);
+    /// ```
+    pub fn write_bitstring(self, bits: &BitString) {
+        self.write_bitvec_bytes(bits.as_bytes(), bits.len());
+    }
+
+    #[cfg(feature = "bitvec")]
+    /// Writes a `bitvec` slice as an ASN.1 BITSTRING value.
+    ///
+    /// Any storage and bit order are accepted; the bits are written in
+    /// the order of their indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use bitvec::prelude::*;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_bitslice(bits![u8, Lsb0; 1, 0, 1])
+    /// });
+    /// assert_eq!(&der, &[3, 2, 5, 160]);
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `bitvec` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["bitvec"] }
+    /// ```
+    pub fn write_bitslice<T: BitStore, O: BitOrder>(self,
+            bits: &BitSlice<T, O>) {
+        self.write_bitstring(&BitString::from(bits));
+    }
+
+    /// Writes a named bit list (such as `KeyUsage`) as an ASN.1 BITSTRING
+    /// value.
+    ///
+    /// `bits[i]` is the value of the bit named `i`. Trailing zero bits are
+    /// removed, as DER requires for named bit lists (X.690 11.2.2).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// // digitalSignature(0) and keyEncipherment(2)
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_named_bits(&[true, false, true, false, false])
+    /// });
+    /// assert_eq!(&der, &[3, 2, 5, 160]);
+    /// ```
+    pub fn write_named_bits(self, bits: &[bool]) {
+        let len = bits.iter().rposition(|&b| b).map_or(0, |i| i + 1);
+        let mut bytes = Scratch(vec![0u8; (len + 7) / 8]);
+        for (i, &b) in bits[..len].iter().enumerate() {
+            if b {
+                bytes.0[i / 8] |= 128 >> (i % 8);
+            }
         }
+        self.write_bitvec_bytes(&bytes.0, len);
     }
 
     /// Writes `&[u8]` as an ASN.1 OCTETSTRING value.
@@ -599,7 +1400,7 @@ impl<'a> DERWriter<'a> {
     pub fn write_bytes(mut self, bytes: &[u8]) {
         self.write_identifier(TAG_OCTETSTRING, PCBit::Primitive);
         self.write_length(bytes.len());
-        self.buf.extend_from_slice(bytes);
+        self.extend(bytes);
     }
 
     /// Writes `&str` as an ASN.1 UTF8String value.
@@ -616,7 +1417,7 @@ impl<'a> DERWriter<'a> {
     pub fn write_utf8_string(mut self, string: &str) {
         self.write_identifier(TAG_UTF8STRING, PCBit::Primitive);
         self.write_length(string.len());
-        self.buf.extend_from_slice(string.as_bytes());
+        self.extend(string.as_bytes());
     }
 
     /// Writes `&str` as an ASN.1 IA5String value.
@@ -634,11 +1435,18 @@ impl<'a> DERWriter<'a> {
         assert!(string.is_ascii(), "IA5 string must be ASCII");
         self.write_identifier(TAG_IA5STRING, PCBit::Primitive);
         self.write_length(string.len());
-        self.buf.extend_from_slice(string.as_bytes());
+        self.extend(string.as_bytes());
     }
 
     /// Writes `&str` as an ASN.1 BMPString value.
     ///
+    /// The content octets are UTF-16BE (UCS-2). Characters outside the
+    /// Basic Multilingual Plane cannot be represented.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains a character above U+FFFF.
+    ///
     /// # Examples
     ///
     /// ```
@@ -649,17 +1457,43 @@ impl<'a> DERWriter<'a> {
     /// assert_eq!(der, vec![30, 10, 39, 100, 3, 192, 0, 252, 0, 50, 0, 63]);
     /// ```
     pub fn write_bmp_string(mut self, string: &str) {
-        let utf16 : Vec<u16> = string.encode_utf16().collect();
-
-        let mut bytes = Vec::with_capacity(utf16.len() * 2);
-        for c in utf16 {
-            bytes.push((c / 256) as u8);
-            bytes.push((c % 256) as u8);
+        let mut bytes = Scratch::with_capacity(string.len() * 2);
+        for ch in string.chars() {
+            let c = ch as u32;
+            assert!(c <= 0xFFFF,
+                "Invalid BMPString: {:?} is outside the BMP", ch);
+            bytes.0.push((c / 256) as u8);
+            bytes.0.push((c % 256) as u8);
         }
 
         self.write_identifier(TAG_BMPSTRING, PCBit::Primitive);
-        self.write_length(bytes.len());
-        self.buf.extend_from_slice(&bytes);
+        self.write_length(bytes.0.len());
+        self.extend(&bytes.0);
+    }
+
+    /// Writes `&str` as an ASN.1 UniversalString value.
+    ///
+    /// Each character is encoded as a 4-byte big-endian code point (UCS-4).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_universal_string("a\u{1F600}")
+    /// });
+    /// assert_eq!(der, vec![28, 8, 0, 0, 0, 97, 0, 1, 246, 0]);
+    /// ```
+    pub fn write_universal_string(mut self, string: &str) {
+        use super::tags::TAG_UNIVERSALSTRING;
+        let mut bytes = Scratch::with_capacity(string.len() * 4);
+        for ch in string.chars() {
+            bytes.0.extend_from_slice(&(ch as u32).to_be_bytes());
+        }
+
+        self.write_identifier(TAG_UNIVERSALSTRING, PCBit::Primitive);
+        self.write_length(bytes.0.len());
+        self.extend(&bytes.0);
     }
 
     /// Writes the ASN.1 NULL value.
@@ -699,10 +1533,7 @@ impl<'a> DERWriter<'a> {
         assert!(oid.components().len() >= 2, "Invalid OID: too short");
         let id0 = oid.components()[0];
         let id1 = oid.components()[1];
-        assert!(
-            (id0 < 3) && (id1 < 18446744073709551535) &&
-            (id0 >= 2 || id1 < 40),
-            "Invalid OID {{{} {} ...}}", id0, id1);
+        assert!(oid.is_valid(), "Invalid OID {{{} {} ...}}", id0, id1);
         let subid0 = id0 * 40 + id1;
         let mut length = 0;
         for i in 1..oid.components().len() {
@@ -729,34 +1560,205 @@ impl<'a> DERWriter<'a> {
                 shiftnum -= 7;
             }
             while shiftnum > 0 {
-                self.buf.push(128 | ((((subid|1) >> shiftnum) & 127) as u8));
+                self.push(128 | ((((subid|1) >> shiftnum) & 127) as u8));
                 shiftnum -= 7;
             }
-            self.buf.push((subid & 127) as u8);
+            self.push((subid & 127) as u8);
         }
     }
 
-    /// Writes an ASN.1 UTF8String.
+    #[cfg(feature = "num-bigint")]
+    /// Writes an ASN.1 object identifier with arbitrarily large
+    /// components.
     ///
     /// # Examples
     ///
     /// ```
     /// use yasna;
+    /// use yasna::models::BigObjectIdentifier;
+    /// let oid: BigObjectIdentifier =
+    ///     "2.25.18446744073709551616".parse().unwrap();
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_utf8string("gnaw ροκανίζω 𪘂る")
+    ///     writer.write_big_oid(&oid)
     /// });
-    /// assert_eq!(&der, &[
-    ///     12, 29, 103, 110, 97, 119, 32, 207, 129, 206, 191, 206,
-    ///     186, 206, 177, 206, 189, 206, 175, 206, 182, 207,
-    ///     137, 32, 240, 170, 152, 130, 227, 130, 139]);
+    /// assert_eq!(&der, &[6, 11, 105, 130, 128, 128, 128, 128, 128, 128,
+    ///     128, 128, 0]);
     /// ```
-    pub fn write_utf8string(self, string: &str) {
-        self.write_tagged_implicit(TAG_UTF8STRING, |writer| {
-            writer.write_bytes(string.as_bytes())
-        })
-    }
-
-    /// Writes ASN.1 SEQUENCE.
+    ///
+    /// # Panics
+    ///
+    /// It panics when the OID is not [valid](BigObjectIdentifier::is_valid).
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `num-bigint` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["num-bigint"] }
+    /// ```
+    pub fn write_big_oid(mut self, oid: &BigObjectIdentifier) {
+        assert!(oid.is_valid(), "Invalid OID {}", oid);
+        let components = oid.components();
+        let subid0 = &components[0] * 40u8 + &components[1];
+        let subids = Some(subid0.to_radix_be(128)).into_iter()
+            .chain(components[2..].iter().map(|subid| subid.to_radix_be(128)))
+            .collect::<Vec<_>>();
+        let length = subids.iter().map(|digits| digits.len()).sum();
+        self.write_identifier(TAG_OID, PCBit::Primitive);
+        self.write_length(length);
+        for digits in &subids {
+            let (last, init) = digits.split_last().unwrap();
+            scratch::reserve(self.buf, digits.len());
+            self.buf.extend(init.iter().map(|&digit| 128 | digit));
+            self.push(*last);
+        }
+    }
+
+    /// Writes an ASN.1 RELATIVE-OID.
+    ///
+    /// Unlike [`write_oid`](Self::write_oid), every component is written
+    /// as a separate subidentifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use yasna::models::RelativeOid;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_relative_oid(&RelativeOid::from_slice(
+    ///         &[8571, 3, 2]))
+    /// });
+    /// assert_eq!(&der, &[13, 4, 194, 123, 3, 2]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It panics when the RELATIVE-OID has no components.
+    pub fn write_relative_oid(mut self, oid: &RelativeOid) {
+        use super::tags::TAG_RELATIVE_OID;
+        assert!(!oid.components().is_empty(), "Invalid RELATIVE-OID: empty");
+        let mut length = 0;
+        for &subid in oid.components() {
+            let mut subid = subid | 1;
+            while subid > 0 {
+                length += 1;
+                subid >>= 7;
+            }
+        }
+        self.write_identifier(TAG_RELATIVE_OID, PCBit::Primitive);
+        self.write_length(length);
+        for &subid in oid.components() {
+            let mut shiftnum = 63; // ceil(64 / 7) * 7 - 7
+            while ((subid|1) >> shiftnum) == 0 {
+                shiftnum -= 7;
+            }
+            while shiftnum > 0 {
+                self.push(128 | ((((subid|1) >> shiftnum) & 127) as u8));
+                shiftnum -= 7;
+            }
+            self.push((subid & 127) as u8);
+        }
+    }
+
+    /// Writes an ASN.1 EMBEDDED PDV.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use yasna::models::{EmbeddedPdv, ObjectIdentifier, PdvIdentification};
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_embedded_pdv(&EmbeddedPdv {
+    ///         identification: PdvIdentification::Syntax(
+    ///             ObjectIdentifier::from_slice(&[2, 1, 1])),
+    ///         data_value: b"Hi!".to_vec(),
+    ///     })
+    /// });
+    /// assert_eq!(&der, &[
+    ///     43, 11, 160, 4, 129, 2, 81, 1, 130, 3, 72, 105, 33]);
+    /// ```
+    pub fn write_embedded_pdv(self, pdv: &EmbeddedPdv) {
+        use super::tags::TAG_EMBEDDED_PDV;
+        self.write_tagged_implicit(TAG_EMBEDDED_PDV, |writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_tagged(Tag::context(0), |writer| {
+                    match pdv.identification {
+                        PdvIdentification::Syntaxes {
+                            ref abstract_syntax, ref transfer_syntax } => {
+                            writer.write_tagged_implicit(Tag::context(0),
+                                    |writer| {
+                                writer.write_sequence(|writer| {
+                                    writer.next().write_tagged_implicit(
+                                        Tag::context(0),
+                                        |writer| writer.write_oid(abstract_syntax));
+                                    writer.next().write_tagged_implicit(
+                                        Tag::context(1),
+                                        |writer| writer.write_oid(transfer_syntax));
+                                })
+                            })
+                        },
+                        PdvIdentification::Syntax(ref oid) => {
+                            writer.write_tagged_implicit(Tag::context(1),
+                                |writer| writer.write_oid(oid))
+                        },
+                        PdvIdentification::PresentationContextId(id) => {
+                            writer.write_tagged_implicit(Tag::context(2),
+                                |writer| writer.write_i64(id))
+                        },
+                        PdvIdentification::ContextNegotiation {
+                            presentation_context_id, ref transfer_syntax } => {
+                            writer.write_tagged_implicit(Tag::context(3),
+                                    |writer| {
+                                writer.write_sequence(|writer| {
+                                    writer.next().write_tagged_implicit(
+                                        Tag::context(0),
+                                        |writer| writer.write_i64(
+                                            presentation_context_id));
+                                    writer.next().write_tagged_implicit(
+                                        Tag::context(1),
+                                        |writer| writer.write_oid(transfer_syntax));
+                                })
+                            })
+                        },
+                        PdvIdentification::TransferSyntax(ref oid) => {
+                            writer.write_tagged_implicit(Tag::context(4),
+                                |writer| writer.write_oid(oid))
+                        },
+                        PdvIdentification::Fixed => {
+                            writer.write_tagged_implicit(Tag::context(5),
+                                |writer| writer.write_null())
+                        },
+                    }
+                });
+                writer.next().write_tagged_implicit(Tag::context(2), |writer| {
+                    writer.write_bytes(&pdv.data_value)
+                });
+            })
+        })
+    }
+
+    /// Writes an ASN.1 UTF8String.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_utf8string("gnaw ροκανίζω 𪘂る")
+    /// });
+    /// assert_eq!(&der, &[
+    ///     12, 29, 103, 110, 97, 119, 32, 207, 129, 206, 191, 206,
+    ///     186, 206, 177, 206, 189, 206, 175, 206, 182, 207,
+    ///     137, 32, 240, 170, 152, 130, 227, 130, 139]);
+    /// ```
+    pub fn write_utf8string(self, string: &str) {
+        self.write_tagged_implicit(TAG_UTF8STRING, |writer| {
+            writer.write_bytes(string.as_bytes())
+        })
+    }
+
+    /// Writes ASN.1 SEQUENCE.
     ///
     /// This function uses the loan pattern: `callback` is called back with
     /// a [`DERWriterSeq`], to which the contents of the
@@ -783,6 +1785,8 @@ impl<'a> DERWriter<'a> {
         return self.with_length(|writer| {
             callback(&mut DERWriterSeq {
                 buf: writer.buf,
+                plan: plan::reborrow(&mut writer.plan),
+                length_octets: writer.length_octets,
             })
         });
     }
@@ -814,238 +1818,929 @@ impl<'a> DERWriter<'a> {
         self.write_sequence(callback)
     }
 
-    /// Writes ASN.1 SET.
-    ///
-    /// This function uses the loan pattern: `callback` is called back with
-    /// a [`DERWriterSet`], to which the contents of the
-    /// SET are written.
-    ///
-    /// For SET OF values, use [`write_set_of`](Self::write_set_of) instead.
+    /// Writes ASN.1 SEQUENCE OF from the items of an iterator.
+    ///
+    /// `callback` is called back with a [`DERWriter`] for each item,
+    /// to which the item is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_sequence_of_iter(&[10, -129], |writer, &i| {
+    ///         writer.write_i64(i)
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![48, 7, 2, 1, 10, 2, 2, 255, 127]);
+    /// ```
+    pub fn write_sequence_of_iter<I, F>(self, iter: I, mut callback: F)
+        where I: IntoIterator, F: FnMut(DERWriter, I::Item) {
+        self.write_sequence(|writer| {
+            for item in iter {
+                callback(writer.next(), item);
+            }
+        })
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Writes ASN.1 SEQUENCE OF, encoding the elements in parallel.
+    ///
+    /// Each item is written with `callback` into its own buffer on the
+    /// rayon thread pool, and the buffers are concatenated in order. The
+    /// output is the same as [`write_sequence_of_iter`](
+    /// Self::write_sequence_of_iter) with the same callback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_sequence_of_par(&[10, -129], |writer, &i| {
+    ///         writer.write_i64(i)
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![48, 7, 2, 1, 10, 2, 2, 255, 127]);
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `rayon` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["rayon"] }
+    /// ```
+    pub fn write_sequence_of_par<T, F>(mut self, items: &[T], callback: F)
+        where T: Sync, F: Fn(DERWriter, &T) + Sync {
+        let bufs = self.encode_par(items, &callback);
+        self.write_elements(TAG_SEQUENCE, &bufs);
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Writes ASN.1 SET OF, encoding the elements in parallel.
+    ///
+    /// Each item is written with `callback` into its own buffer on the
+    /// rayon thread pool, and the buffers are sorted as in
+    /// [`write_set_of`](Self::write_set_of).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_set_of_par(&[10, -129], |writer, &i| {
+    ///         writer.write_i64(i)
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![49, 7, 2, 1, 10, 2, 2, 255, 127]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `callback` writes nothing for an item.
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `rayon` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["rayon"] }
+    /// ```
+    pub fn write_set_of_par<T, F>(mut self, items: &[T], callback: F)
+        where T: Sync, F: Fn(DERWriter, &T) + Sync {
+        use rayon::slice::ParallelSliceMut;
+        let mut bufs = self.encode_par(items, &callback);
+        assert!(bufs.iter().all(|buf| !buf.0.is_empty()),
+            "Empty output in write_set_of_par()");
+        bufs.par_sort_unstable_by(|buf0, buf1| buf0.0.cmp(&buf1.0));
+        self.write_elements(TAG_SET, &bufs);
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Encodes each item into its own buffer in parallel.
+    fn encode_par<T, F>(&self, items: &[T], callback: &F) -> Vec<Scratch>
+        where T: Sync, F: Fn(DERWriter, &T) + Sync {
+        use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+        let length_octets = self.length_octets;
+        return items.par_iter().map(|item| {
+            Scratch(construct_der(|writer| {
+                callback(writer.length_octets_hint(length_octets), item)
+            }))
+        }).collect();
+    }
+
+    /// Writes a constructed value consisting of already encoded elements.
+    fn write_elements<B: AsRef<[u8]>>(&mut self, tag: Tag, elements: &[B]) {
+        let length = elements.iter().map(|buf| buf.as_ref().len()).sum();
+        self.write_identifier(tag, PCBit::Constructed);
+        self.write_length(length);
+        for buf in elements {
+            self.extend(buf.as_ref());
+        }
+    }
+
+    /// Writes ASN.1 SET.
+    ///
+    /// This function uses the loan pattern: `callback` is called back with
+    /// a [`DERWriterSet`], to which the contents of the
+    /// SET are written.
+    ///
+    /// For SET OF values, use [`write_set_of`](Self::write_set_of) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_set(|writer| {
+    ///         writer.next().write_i64(10);
+    ///         writer.next().write_bool(true);
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![49, 6, 1, 1, 255, 2, 1, 10]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when a [`next`](DERWriterSet::next) writer was left unused.
+    /// Use [`try_write_set`](Self::try_write_set) to get an error instead.
+    ///
+    /// # Ordering
+    ///
+    /// The elements are sorted by their tags (X.690 10.3). Elements with
+    /// equal tags are sorted by their whole encodings, so the output
+    /// doesn't depend on the order in which they were written.
+    pub fn write_set<T, F>(self, callback: F) -> T
+        where F: FnOnce(&mut DERWriterSet) -> T {
+        match self.try_write_set(callback) {
+            Ok(result) => return result,
+            Err(_) => panic!("Empty output in write_set()"),
+        }
+    }
+
+    /// Tries to write ASN.1 SET.
+    ///
+    /// Same as [`write_set`](Self::write_set), only that it returns an
+    /// error instead of panicking when an element is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     let err = writer.try_write_set(|writer| {
+    ///         writer.next().write_i64(10);
+    ///         writer.next();
+    ///     }).unwrap_err();
+    ///     assert_eq!(err.index(), 1);
+    /// });
+    /// assert!(der.is_empty());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It returns [`EmptySetElement`] if a [`next`](DERWriterSet::next)
+    /// writer was left unused, in which case nothing is written.
+    pub fn try_write_set<T, F>(mut self, callback: F)
+        -> Result<T, EmptySetElement>
+        where F: FnOnce(&mut DERWriterSet) -> T {
+        let mut buf = Scratch::new();
+        let mut starts = Vec::new();
+        let result = callback(&mut DERWriterSet {
+            buf: &mut buf.0,
+            starts: &mut starts,
+            length_octets: self.length_octets,
+        });
+        let mut bufs = set_elements(&buf.0, &starts)?;
+        // Elements with the same tag, which a SET shouldn't have,
+        // are ordered by their encodings to keep the output deterministic.
+        bufs.sort_by(|buf0, buf1| {
+            set_tag_key(buf0).cmp(&set_tag_key(buf1))
+                .then_with(|| buf0.cmp(buf1))
+        });
+        self.write_elements(TAG_SET, &bufs);
+        return Ok(result);
+    }
+
+    /// Writes ASN.1 SET OF.
+    ///
+    /// This function uses the loan pattern: `callback` is called back with
+    /// a [`DERWriterSet`], to which the contents of the
+    /// SET OF are written.
+    ///
+    /// For SET values, use [`write_set`](Self::write_set) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_set_of(|writer| {
+    ///         for &i in &[10, -129] {
+    ///             writer.next().write_i64(i);
+    ///         }
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![49, 7, 2, 1, 10, 2, 2, 255, 127]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when a [`next`](DERWriterSet::next) writer was left unused.
+    /// Use [`try_write_set_of`](Self::try_write_set_of) to get an error
+    /// instead.
+    pub fn write_set_of<T, F>(self, callback: F) -> T
+        where F: FnOnce(&mut DERWriterSet) -> T {
+        match self.try_write_set_of(callback) {
+            Ok(result) => return result,
+            Err(_) => panic!("Empty output in write_set_of()"),
+        }
+    }
+
+    /// Tries to write ASN.1 SET OF.
+    ///
+    /// Same as [`write_set_of`](Self::write_set_of), only that it returns
+    /// an error instead of panicking when an element is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.try_write_set_of(|writer| {
+    ///         for &i in &[10, -129] {
+    ///             writer.next().write_i64(i);
+    ///         }
+    ///     }).unwrap();
+    /// });
+    /// assert_eq!(der, vec![49, 7, 2, 1, 10, 2, 2, 255, 127]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It returns [`EmptySetElement`] if a [`next`](DERWriterSet::next)
+    /// writer was left unused, in which case nothing is written.
+    pub fn try_write_set_of<T, F>(mut self, callback: F)
+        -> Result<T, EmptySetElement>
+        where F: FnOnce(&mut DERWriterSet) -> T {
+        let mut buf = Scratch::new();
+        let mut starts = Vec::new();
+        let result = callback(&mut DERWriterSet {
+            buf: &mut buf.0,
+            starts: &mut starts,
+            length_octets: self.length_octets,
+        });
+        let mut bufs = set_elements(&buf.0, &starts)?;
+        bufs.sort();
+        self.write_elements(TAG_SET, &bufs);
+        return Ok(result);
+    }
+
+    /// Writes an ASN.1 NumericString.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_numeric_string("128 256")
+    /// });
+    /// assert_eq!(&der, &[18, 7, 49, 50, 56, 32, 50, 53, 54]);
+    /// ```
+    pub fn write_numeric_string(self, string: &str) {
+        let bytes = string.as_bytes();
+        for &byte in bytes {
+            assert!(byte == b' ' || (b'0' <= byte && byte <= b'9'),
+                "Invalid NumericString: {:?} appeared", byte);
+        }
+        self.write_tagged_implicit(TAG_NUMERICSTRING, |writer| {
+            writer.write_bytes(bytes)
+        });
+    }
+
+    /// Writes an ASN.1 PrintableString.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_printable_string("Co., Ltd.")
+    /// });
+    /// assert_eq!(&der, &[19, 9, 67, 111, 46, 44, 32, 76, 116, 100, 46]);
+    /// ```
+    pub fn write_printable_string(self, string: &str) {
+        let bytes = string.as_bytes();
+        for &byte in bytes {
+            assert!(
+                byte == b' ' ||
+                (b'\'' <= byte && byte <= b':' && byte != b'*') ||
+                byte == b'=' ||
+                (b'A' <= byte && byte <= b'Z') ||
+                (b'a' <= byte && byte <= b'z'),
+                "Invalid PrintableString: {:?} appeared", byte);
+        }
+        self.write_tagged_implicit(TAG_PRINTABLESTRING, |writer| {
+            writer.write_bytes(bytes)
+        });
+    }
+
+    #[cfg(feature = "time")]
+    /// Writes an ASN.1 UTCTime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() {
+    /// use yasna;
+    /// use yasna::models::UTCTime;
+    /// use time::OffsetDateTime;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_utctime(
+    ///         &UTCTime::from_datetime(
+    ///             OffsetDateTime::from_unix_timestamp(378820800).unwrap()))
+    /// });
+    /// assert_eq!(&der, &[
+    ///     23, 13, 56, 50, 48, 49, 48, 50, 49, 50, 48, 48, 48, 48, 90]);
+    /// # }
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn write_utctime(self, datetime: &UTCTime) {
+        use super::tags::TAG_UTCTIME;
+        self.write_tagged_implicit(TAG_UTCTIME, |writer| {
+            writer.write_bytes(&datetime.to_bytes())
+        });
+    }
+
+    #[cfg(feature = "time")]
+    /// Writes an ASN.1 GeneralizedTime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() {
+    /// use yasna;
+    /// use yasna::models::GeneralizedTime;
+    /// use time::OffsetDateTime;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_generalized_time(
+    ///         &GeneralizedTime::from_datetime(
+    ///             OffsetDateTime::from_unix_timestamp_nanos(
+    ///                 500_159_309_724_000_000).unwrap()))
+    /// });
+    /// assert_eq!(&der, &[
+    ///     24, 19, 49, 57, 56, 53, 49, 49, 48, 54, 50,
+    ///     49, 48, 56, 50, 57, 46, 55, 50, 52, 90]);
+    /// # }
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn write_generalized_time(self, datetime: &GeneralizedTime) {
+        use super::tags::TAG_GENERALIZEDTIME;
+        self.write_tagged_implicit(TAG_GENERALIZEDTIME, |writer| {
+            writer.write_bytes(&datetime.to_bytes())
+        });
+    }
+
+    /// Writes an ASN.1 UTCTime from its fields, as they are.
+    ///
+    /// The output is DER only if [`UTCTimeFields::is_der`] holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use yasna::models::UTCTimeFields;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_utctime_fields(
+    ///         &UTCTimeFields::new(1982, 1, 2, 12, 0, 0).unwrap())
+    /// });
+    /// assert_eq!(&der, b"\x17\x0d820102120000Z");
+    /// ```
+    pub fn write_utctime_fields(self, fields: &UTCTimeFields) {
+        use super::tags::TAG_UTCTIME;
+        self.write_tagged_implicit(TAG_UTCTIME, |writer| {
+            writer.write_bytes(&fields.to_bytes())
+        });
+    }
+
+    /// Writes an ASN.1 GeneralizedTime from its fields, as they are.
+    ///
+    /// The output is DER only if [`GeneralizedTimeFields::is_der`] holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use yasna::models::GeneralizedTimeFields;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_generalized_time_fields(
+    ///         &GeneralizedTimeFields::new(1985, 11, 6, 21, 6, 27).unwrap())
+    /// });
+    /// assert_eq!(&der, b"\x18\x0f19851106210627Z");
+    /// ```
+    pub fn write_generalized_time_fields(self,
+            fields: &GeneralizedTimeFields) {
+        use super::tags::TAG_GENERALIZEDTIME;
+        self.write_tagged_implicit(TAG_GENERALIZEDTIME, |writer| {
+            writer.write_bytes(&fields.to_bytes())
+        });
+    }
+
+    #[cfg(feature = "std")]
+    /// Writes an ASN.1 UTCTime from a `SystemTime`, discarding the
+    /// fraction of a second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_utctime_system_time(
+    ///         UNIX_EPOCH + Duration::from_millis(378820800_500))
+    /// });
+    /// assert_eq!(&der, b"\x17\x0d820102120000Z");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when the year is not between 1950 and 2049.
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `std` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["std"] }
+    /// ```
+    pub fn write_utctime_system_time(self, time: std::time::SystemTime) {
+        self.write_utctime_fields(&UTCTimeFields::from_system_time(time));
+    }
+
+    #[cfg(feature = "std")]
+    /// Writes an ASN.1 GeneralizedTime from a `SystemTime`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_generalized_time_system_time(
+    ///         UNIX_EPOCH + Duration::from_secs(500159309))
+    /// });
+    /// assert_eq!(&der, b"\x18\x0f19851106210829Z");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when the year is not between 0 and 9999.
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `std` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["std"] }
+    /// ```
+    pub fn write_generalized_time_system_time(self,
+            time: std::time::SystemTime) {
+        self.write_generalized_time_fields(
+            &GeneralizedTimeFields::from_system_time(time));
+    }
+
+    #[cfg(feature = "chrono")]
+    /// Writes an ASN.1 UTCTime from a `chrono::DateTime<Utc>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use chrono::{TimeZone, Utc};
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_utctime_chrono(&Utc.timestamp_opt(378820800, 0).unwrap())
+    /// });
+    /// assert_eq!(&der, b"\x17\x0d820102120000Z");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when UTCTime can't represent the datetime. See
+    /// [`UTCTimeFields::from_chrono`].
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `chrono` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["chrono"] }
+    /// ```
+    pub fn write_utctime_chrono(self,
+            datetime: &chrono::DateTime<chrono::Utc>) {
+        self.write_utctime_fields(&UTCTimeFields::from_chrono(datetime));
+    }
+
+    #[cfg(feature = "chrono")]
+    /// Writes an ASN.1 GeneralizedTime from a `chrono::DateTime<Utc>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use chrono::{TimeZone, Utc};
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_generalized_time_chrono(
+    ///         &Utc.timestamp_opt(500159309, 724_000_000).unwrap())
+    /// });
+    /// assert_eq!(&der, b"\x18\x1319851106210829.724Z");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when the year is not between 0 and 9999.
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `chrono` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["chrono"] }
+    /// ```
+    pub fn write_generalized_time_chrono(self,
+            datetime: &chrono::DateTime<chrono::Utc>) {
+        self.write_generalized_time_fields(
+            &GeneralizedTimeFields::from_chrono(datetime));
+    }
+
+    #[cfg(feature = "time")]
+    /// Writes an ASN.1 UTCTime from an `OffsetDateTime`.
+    ///
+    /// This is a shorthand for [`write_utctime`](Self::write_utctime) with
+    /// [`UTCTime::from_datetime`]. A `PrimitiveDateTime` in UTC can be
+    /// written after `assume_utc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use time::{OffsetDateTime, UtcOffset};
+    /// let datetime = OffsetDateTime::from_unix_timestamp(378820800).unwrap()
+    ///     .to_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_utctime_datetime(datetime)
+    /// });
+    /// assert_eq!(&der, b"\x17\x0d820102120000Z");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when UTCTime can't represent the datetime. See
+    /// [`UTCTime::from_datetime`].
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn write_utctime_datetime(self, datetime: time::OffsetDateTime) {
+        self.write_utctime(&UTCTime::from_datetime(datetime));
+    }
+
+    #[cfg(feature = "time")]
+    /// Writes an ASN.1 GeneralizedTime from an `OffsetDateTime`.
+    ///
+    /// This is a shorthand for
+    /// [`write_generalized_time`](Self::write_generalized_time) with
+    /// [`GeneralizedTime::from_datetime`]. A `PrimitiveDateTime` in UTC can
+    /// be written after `assume_utc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use time::{Date, Month, PrimitiveDateTime, Time};
+    /// let datetime = PrimitiveDateTime::new(
+    ///     Date::from_calendar_date(1985, Month::November, 6).unwrap(),
+    ///     Time::from_hms_milli(21, 8, 29, 724).unwrap());
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_generalized_time_datetime(datetime.assume_utc())
+    /// });
+    /// assert_eq!(&der, b"\x18\x1319851106210829.724Z");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when the year is not between 0 and 9999.
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn write_generalized_time_datetime(self,
+            datetime: time::OffsetDateTime) {
+        self.write_generalized_time(&GeneralizedTime::from_datetime(datetime));
+    }
+
+    #[cfg(feature = "time")]
+    /// Writes an ASN.1 DATE.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() {
+    /// use yasna;
+    /// use yasna::models::Date;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_date(&Date::parse(b"20080401").unwrap())
+    /// });
+    /// assert_eq!(&der, b"\x1f\x1f\x0820080401");
+    /// # }
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn write_date(self, date: &Date) {
+        use super::tags::TAG_DATE;
+        self.write_tagged_implicit(TAG_DATE, |writer| {
+            writer.write_bytes(&date.to_bytes())
+        });
+    }
+
+    #[cfg(feature = "time")]
+    /// Writes an ASN.1 TIME-OF-DAY.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() {
+    /// use yasna;
+    /// use yasna::models::TimeOfDay;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_time_of_day(&TimeOfDay::parse(b"123456").unwrap())
+    /// });
+    /// assert_eq!(&der, b"\x1f\x20\x06123456");
+    /// # }
+    /// ```
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn write_time_of_day(self, time: &TimeOfDay) {
+        use super::tags::TAG_TIME_OF_DAY;
+        self.write_tagged_implicit(TAG_TIME_OF_DAY, |writer| {
+            writer.write_bytes(&time.to_bytes())
+        });
+    }
+
+    #[cfg(feature = "time")]
+    /// Writes an ASN.1 DATE-TIME.
     ///
     /// # Examples
     ///
     /// ```
+    /// # fn main() {
     /// use yasna;
+    /// use yasna::models::DateTime;
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_set(|writer| {
-    ///         writer.next().write_i64(10);
-    ///         writer.next().write_bool(true);
-    ///     })
+    ///     writer.write_date_time(&DateTime::parse(b"20080401123456").unwrap())
     /// });
-    /// assert_eq!(der, vec![49, 6, 1, 1, 255, 2, 1, 10]);
+    /// assert_eq!(&der, b"\x1f\x21\x0e20080401123456");
+    /// # }
     /// ```
-    pub fn write_set<T, F>(mut self, callback: F) -> T
-        where F: FnOnce(&mut DERWriterSet) -> T {
-        let mut bufs = Vec::new();
-        let result = callback(&mut DERWriterSet {
-            bufs: &mut bufs,
-        });
-        for buf in bufs.iter() {
-            assert!(buf.len() > 0, "Empty output in write_set()");
-        }
-        bufs.sort_by(|buf0, buf1| {
-            let buf00 = buf0[0] & 223;
-            let buf10 = buf1[0] & 223;
-            if buf00 != buf10 || (buf0[0] & 31) != 31 {
-                return buf00.cmp(&buf10);
-            }
-            let len0 = buf0[1..].iter().position(|x| x & 128 == 0).unwrap();
-            let len1 = buf1[1..].iter().position(|x| x & 128 == 0).unwrap();
-            if len0 != len1 {
-                return len0.cmp(&len1);
-            }
-            return buf0[1..].cmp(&buf1[1..]);
+    ///
+    /// # Features
+    ///
+    /// This method is enabled by `time` feature.
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// yasna = { version = "*", features = ["time"] }
+    /// ```
+    pub fn write_date_time(self, datetime: &DateTime) {
+        use super::tags::TAG_DATE_TIME;
+        self.write_tagged_implicit(TAG_DATE_TIME, |writer| {
+            writer.write_bytes(&datetime.to_bytes())
         });
-        // let bufs_len = bufs.iter().map(|buf| buf.len()).sum();
-        let bufs_len = bufs.iter().map(|buf| buf.len()).fold(0, |x, y| x + y);
-        self.write_identifier(TAG_SET, PCBit::Constructed);
-        self.write_length(bufs_len);
-        for buf in bufs.iter() {
-            self.buf.extend_from_slice(buf);
-        }
-        return result;
     }
 
-    /// Writes ASN.1 SET OF.
-    ///
-    /// This function uses the loan pattern: `callback` is called back with
-    /// a [`DERWriterSet`], to which the contents of the
-    /// SET OF are written.
-    ///
-    /// For SET values, use [`write_set`](Self::write_set) instead.
+    /// Writes an ASN.1 DURATION.
     ///
     /// # Examples
     ///
     /// ```
     /// use yasna;
+    /// use yasna::models::Duration;
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_set_of(|writer| {
-    ///         for &i in &[10, -129] {
-    ///             writer.next().write_i64(i);
-    ///         }
+    ///     writer.write_duration(&Duration {
+    ///         days: 1,
+    ///         hours: 12,
+    ///         ..Duration::default()
     ///     })
     /// });
-    /// assert_eq!(der, vec![49, 7, 2, 1, 10, 2, 2, 255, 127]);
+    /// assert_eq!(&der, b"\x1f\x22\x07P1DT12H");
     /// ```
-    pub fn write_set_of<T, F>(mut self, callback: F) -> T
-        where F: FnOnce(&mut DERWriterSet) -> T {
-        let mut bufs = Vec::new();
-        let result = callback(&mut DERWriterSet {
-            bufs: &mut bufs,
+    pub fn write_duration(self, duration: &Duration) {
+        use super::tags::TAG_DURATION;
+        self.write_tagged_implicit(TAG_DURATION, |writer| {
+            writer.write_bytes(&duration.to_bytes())
         });
-        for buf in bufs.iter() {
-            assert!(buf.len() > 0, "Empty output in write_set_of()");
-        }
-        bufs.sort();
-        // let bufs_len = bufs.iter().map(|buf| buf.len()).sum();
-        let bufs_len = bufs.iter().map(|buf| buf.len()).fold(0, |x, y| x + y);
-        self.write_identifier(TAG_SET, PCBit::Constructed);
-        self.write_length(bufs_len);
-        for buf in bufs.iter() {
-            self.buf.extend_from_slice(buf);
-        }
-        return result;
     }
 
-    /// Writes an ASN.1 NumericString.
+    /// Writes an ASN.1 VisibleString.
     ///
     /// # Examples
     ///
     /// ```
     /// use yasna;
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_numeric_string("128 256")
+    ///     writer.write_visible_string("Hi!")
     /// });
-    /// assert_eq!(&der, &[18, 7, 49, 50, 56, 32, 50, 53, 54]);
+    /// assert_eq!(&der, &[26, 3, 72, 105, 33]);
     /// ```
-    pub fn write_numeric_string(self, string: &str) {
+    pub fn write_visible_string(self, string: &str) {
         let bytes = string.as_bytes();
         for &byte in bytes {
-            assert!(byte == b' ' || (b'0' <= byte && byte <= b'9'),
-                "Invalid NumericString: {:?} appeared", byte);
+            assert!(b' ' <= byte && byte <= b'~',
+                "Invalid VisibleString: {:?} appeared", byte);
         }
-        self.write_tagged_implicit(TAG_NUMERICSTRING, |writer| {
+        self.write_tagged_implicit(TAG_VISIBLESTRING, |writer| {
             writer.write_bytes(bytes)
         });
     }
 
-    /// Writes an ASN.1 PrintableString.
+    /// Writes an ASN.1 ObjectDescriptor.
+    ///
+    /// Only the ASCII graphic characters and space are supported as its
+    /// GraphicString contents.
     ///
     /// # Examples
     ///
     /// ```
     /// use yasna;
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_printable_string("Co., Ltd.")
+    ///     writer.write_object_descriptor("Hi!")
     /// });
-    /// assert_eq!(&der, &[19, 9, 67, 111, 46, 44, 32, 76, 116, 100, 46]);
+    /// assert_eq!(&der, &[7, 3, 72, 105, 33]);
     /// ```
-    pub fn write_printable_string(self, string: &str) {
+    pub fn write_object_descriptor(self, string: &str) {
+        use super::tags::TAG_OBJECT_DESCRIPTOR;
         let bytes = string.as_bytes();
         for &byte in bytes {
-            assert!(
-                byte == b' ' ||
-                (b'\'' <= byte && byte <= b':' && byte != b'*') ||
-                byte == b'=' ||
-                (b'A' <= byte && byte <= b'Z') ||
-                (b'a' <= byte && byte <= b'z'),
-                "Invalid PrintableString: {:?} appeared", byte);
+            assert!((b' '..=b'~').contains(&byte),
+                "Invalid ObjectDescriptor: {:?} appeared", byte);
         }
-        self.write_tagged_implicit(TAG_PRINTABLESTRING, |writer| {
+        self.write_tagged_implicit(TAG_OBJECT_DESCRIPTOR, |writer| {
             writer.write_bytes(bytes)
         });
     }
 
-    #[cfg(feature = "time")]
-    /// Writes an ASN.1 UTCTime.
+    /// Writes an ASN.1 TeletexString (T61String) from raw content octets.
+    ///
+    /// The bytes are written as is; T.61 character set conformance is
+    /// not checked. See also
+    /// [`write_teletex_string_latin1`](Self::write_teletex_string_latin1).
     ///
     /// # Examples
     ///
     /// ```
-    /// # fn main() {
     /// use yasna;
-    /// use yasna::models::UTCTime;
-    /// use time::OffsetDateTime;
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_utctime(
-    ///         &UTCTime::from_datetime(
-    ///             OffsetDateTime::from_unix_timestamp(378820800).unwrap()))
+    ///     writer.write_teletex_string(b"Hi!")
     /// });
-    /// assert_eq!(&der, &[
-    ///     23, 13, 56, 50, 48, 49, 48, 50, 49, 50, 48, 48, 48, 48, 90]);
-    /// # }
+    /// assert_eq!(&der, &[20, 3, 72, 105, 33]);
     /// ```
+    pub fn write_teletex_string(self, bytes: &[u8]) {
+        use super::tags::TAG_TELETEXSTRING;
+        self.write_tagged_implicit(TAG_TELETEXSTRING, |writer| {
+            writer.write_bytes(bytes)
+        });
+    }
+
+    /// Writes `&str` as an ASN.1 TeletexString (T61String), encoding each
+    /// character as a single Latin-1 (ISO 8859-1) octet.
     ///
-    /// # Features
+    /// This follows the common practice of treating TeletexString as
+    /// Latin-1 in certificates.
     ///
-    /// This method is enabled by `time` feature.
+    /// # Panics
+    ///
+    /// Panics if `string` contains a character above U+00FF.
+    ///
+    /// # Examples
     ///
-    /// ```toml
-    /// [dependencies]
-    /// yasna = { version = "*", features = ["time"] }
     /// ```
-    pub fn write_utctime(self, datetime: &UTCTime) {
-        use super::tags::TAG_UTCTIME;
-        self.write_tagged_implicit(TAG_UTCTIME, |writer| {
-            writer.write_bytes(&datetime.to_bytes())
-        });
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_teletex_string_latin1("Zürich")
+    /// });
+    /// assert_eq!(&der, &[20, 6, 90, 252, 114, 105, 99, 104]);
+    /// ```
+    pub fn write_teletex_string_latin1(self, string: &str) {
+        let mut bytes = Scratch::with_capacity(string.len());
+        for ch in string.chars() {
+            assert!((ch as u32) <= 0xFF,
+                "Invalid Latin-1 TeletexString: {:?} appeared", ch);
+            bytes.0.push(ch as u8);
+        }
+        self.write_teletex_string(&bytes.0);
     }
 
-    #[cfg(feature = "time")]
-    /// Writes an ASN.1 GeneralizedTime.
+    /// Writes an ASN.1 VideotexString from raw content octets.
+    ///
+    /// The bytes are written as is.
     ///
     /// # Examples
     ///
     /// ```
-    /// # fn main() {
     /// use yasna;
-    /// use yasna::models::GeneralizedTime;
-    /// use time::OffsetDateTime;
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_generalized_time(
-    ///         &GeneralizedTime::from_datetime(
-    ///             OffsetDateTime::from_unix_timestamp_nanos(
-    ///                 500_159_309_724_000_000).unwrap()))
+    ///     writer.write_videotex_string(b"Hi!")
     /// });
-    /// assert_eq!(&der, &[
-    ///     24, 19, 49, 57, 56, 53, 49, 49, 48, 54, 50,
-    ///     49, 48, 56, 50, 57, 46, 55, 50, 52, 90]);
-    /// # }
+    /// assert_eq!(&der, &[21, 3, 72, 105, 33]);
     /// ```
+    pub fn write_videotex_string(self, bytes: &[u8]) {
+        use super::tags::TAG_VIDEOTEXSTRING;
+        self.write_tagged_implicit(TAG_VIDEOTEXSTRING, |writer| {
+            writer.write_bytes(bytes)
+        });
+    }
+
+    /// Writes an ASN.1 GraphicString from raw content octets.
     ///
-    /// # Features
+    /// The bytes are written as is.
     ///
-    /// This method is enabled by `time` feature.
+    /// # Examples
     ///
-    /// ```toml
-    /// [dependencies]
-    /// yasna = { version = "*", features = ["time"] }
     /// ```
-    pub fn write_generalized_time(self, datetime: &GeneralizedTime) {
-        use super::tags::TAG_GENERALIZEDTIME;
-        self.write_tagged_implicit(TAG_GENERALIZEDTIME, |writer| {
-            writer.write_bytes(&datetime.to_bytes())
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_graphic_string(b"Hi!")
+    /// });
+    /// assert_eq!(&der, &[25, 3, 72, 105, 33]);
+    /// ```
+    pub fn write_graphic_string(self, bytes: &[u8]) {
+        use super::tags::TAG_GRAPHICSTRING;
+        self.write_tagged_implicit(TAG_GRAPHICSTRING, |writer| {
+            writer.write_bytes(bytes)
         });
     }
 
-    /// Writes an ASN.1 VisibleString.
+    /// Writes an ASN.1 GeneralString from raw content octets.
+    ///
+    /// The bytes are written as is.
     ///
     /// # Examples
     ///
     /// ```
     /// use yasna;
     /// let der = yasna::construct_der(|writer| {
-    ///     writer.write_visible_string("Hi!")
+    ///     writer.write_general_string(b"Hi!")
     /// });
-    /// assert_eq!(&der, &[26, 3, 72, 105, 33]);
+    /// assert_eq!(&der, &[27, 3, 72, 105, 33]);
     /// ```
-    pub fn write_visible_string(self, string: &str) {
-        let bytes = string.as_bytes();
-        for &byte in bytes {
-            assert!(b' ' <= byte && byte <= b'~',
-                "Invalid VisibleString: {:?} appeared", byte);
-        }
-        self.write_tagged_implicit(TAG_VISIBLESTRING, |writer| {
+    pub fn write_general_string(self, bytes: &[u8]) {
+        use super::tags::TAG_GENERALSTRING;
+        self.write_tagged_implicit(TAG_GENERALSTRING, |writer| {
             writer.write_bytes(bytes)
         });
     }
@@ -1083,7 +2778,11 @@ impl<'a> DERWriter<'a> {
         where F: FnOnce(DERWriter) -> T {
         self.write_identifier(tag, PCBit::Constructed);
         return self.with_length(|writer| {
-            callback(DERWriter::from_buf(writer.buf))
+            let length_octets = writer.length_octets;
+            let mut writer = DERWriter::from_buf(writer.buf,
+                plan::reborrow(&mut writer.plan));
+            writer.length_octets = length_octets;
+            callback(writer)
         });
     }
 
@@ -1105,11 +2804,89 @@ impl<'a> DERWriter<'a> {
         where F: FnOnce(DERWriter) -> T {
         let tag = if let Some(tag) = self.implicit_tag { tag } else { tag };
         self.implicit_tag = None;
-        let mut writer = DERWriter::from_buf(self.buf);
+        let mut writer = DERWriter::from_buf(self.buf, self.plan);
         writer.implicit_tag = Some(tag);
+        writer.length_octets = self.length_octets;
         return callback(writer);
     }
 
+    /// Writes an (explicitly) context-specific tagged value.
+    ///
+    /// This is a shorthand of `write_tagged(Tag::context(tag_number), ..)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_context(3, |writer| {
+    ///         writer.write_i64(10)
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![163, 3, 2, 1, 10]);
+    /// ```
+    pub fn write_context<T, F>(self, tag_number: u64, callback: F) -> T
+        where F: FnOnce(DERWriter) -> T {
+        self.write_tagged(Tag::context(tag_number), callback)
+    }
+
+    /// Writes an implicitly context-specific tagged value.
+    ///
+    /// This is a shorthand of
+    /// `write_tagged_implicit(Tag::context(tag_number), ..)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_context_implicit(3, |writer| {
+    ///         writer.write_i64(10)
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![131, 1, 10]);
+    /// ```
+    pub fn write_context_implicit<T, F>(self, tag_number: u64, callback: F)
+        -> T where F: FnOnce(DERWriter) -> T {
+        self.write_tagged_implicit(Tag::context(tag_number), callback)
+    }
+
+    /// Writes an (explicitly) application tagged value.
+    ///
+    /// This is a shorthand of
+    /// `write_tagged(Tag::application(tag_number), ..)`.
+    pub fn write_application<T, F>(self, tag_number: u64, callback: F) -> T
+        where F: FnOnce(DERWriter) -> T {
+        self.write_tagged(Tag::application(tag_number), callback)
+    }
+
+    /// Writes an implicitly application tagged value.
+    ///
+    /// This is a shorthand of
+    /// `write_tagged_implicit(Tag::application(tag_number), ..)`.
+    pub fn write_application_implicit<T, F>
+        (self, tag_number: u64, callback: F) -> T
+        where F: FnOnce(DERWriter) -> T {
+        self.write_tagged_implicit(Tag::application(tag_number), callback)
+    }
+
+    /// Writes an (explicitly) private tagged value.
+    ///
+    /// This is a shorthand of `write_tagged(Tag::private(tag_number), ..)`.
+    pub fn write_private<T, F>(self, tag_number: u64, callback: F) -> T
+        where F: FnOnce(DERWriter) -> T {
+        self.write_tagged(Tag::private(tag_number), callback)
+    }
+
+    /// Writes an implicitly private tagged value.
+    ///
+    /// This is a shorthand of
+    /// `write_tagged_implicit(Tag::private(tag_number), ..)`.
+    pub fn write_private_implicit<T, F>(self, tag_number: u64, callback: F)
+        -> T where F: FnOnce(DERWriter) -> T {
+        self.write_tagged_implicit(Tag::private(tag_number), callback)
+    }
+
     /// Writes the arbitrary tagged DER value in `der`.
     ///
     /// # Examples
@@ -1130,7 +2907,7 @@ impl<'a> DERWriter<'a> {
     pub fn write_tagged_der(mut self, der: &TaggedDerValue) {
         self.write_identifier(der.tag(), der.pcbit());
         self.write_length(der.value().len());
-        self.buf.extend_from_slice(der.value());
+        self.extend(der.value());
     }
 
     /// Writes `&[u8]` into the DER output buffer directly. Properly encoded tag
@@ -1148,8 +2925,44 @@ impl<'a> DERWriter<'a> {
     /// });
     /// assert_eq!(raw_der, der);
     /// ```
-    pub fn write_der(self, der: &[u8]) {
-        self.buf.extend_from_slice(der);
+    pub fn write_der(mut self, der: &[u8]) {
+        self.extend(der);
+    }
+
+    /// Writes a constructed value with the indefinite length form.
+    ///
+    /// This function uses the loan pattern: `callback` is called back with
+    /// a [`DERWriterSeq`], to which the contents are written. The
+    /// identifier and the `0x80` length octet are written before the
+    /// callback, and the end-of-contents octets after it.
+    ///
+    /// The indefinite length form is not allowed in DER; the output is
+    /// only valid BER. It can be read with [`parse_ber`](crate::parse_ber).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// use yasna::tags::TAG_SEQUENCE;
+    /// let ber = yasna::construct_der(|writer| {
+    ///     writer.write_indefinite(TAG_SEQUENCE, |writer| {
+    ///         writer.next().write_i64(10);
+    ///         writer.next().write_bool(true);
+    ///     })
+    /// });
+    /// assert_eq!(ber, vec![48, 128, 2, 1, 10, 1, 1, 255, 0, 0]);
+    /// ```
+    pub fn write_indefinite<T, F>(mut self, tag: Tag, callback: F) -> T
+        where F: FnOnce(&mut DERWriterSeq) -> T {
+        self.write_identifier(tag, PCBit::Constructed);
+        self.push(128);
+        let result = callback(&mut DERWriterSeq {
+            buf: self.buf,
+            plan: plan::reborrow(&mut self.plan),
+            length_octets: self.length_octets,
+        });
+        self.extend(&[0, 0]);
+        return result;
     }
 }
 
@@ -1175,12 +2988,69 @@ impl<'a> DERWriter<'a> {
 #[derive(Debug)]
 pub struct DERWriterSeq<'a> {
     buf: &'a mut Vec<u8>,
+    plan: Option<&'a mut (dyn Plan + 'a)>,
+    length_octets: usize,
 }
 
 impl<'a> DERWriterSeq<'a> {
     /// Generates a new [`DERWriter`].
     pub fn next<'b>(&'b mut self) -> DERWriter<'b> {
-        return DERWriter::from_buf(self.buf);
+        plan::flush_if_full(&mut self.plan, self.buf);
+        let mut writer = DERWriter::from_buf(self.buf,
+            plan::reborrow(&mut self.plan));
+        writer.length_octets = self.length_octets;
+        return writer;
+    }
+
+    /// Writes the value in `value` with `callback` if it is `Some`.
+    ///
+    /// Used to write OPTIONAL elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_sequence(|writer| {
+    ///         writer.write_optional(None, |writer, i| writer.write_i64(i));
+    ///         writer.write_optional(Some(true), |writer, b| {
+    ///             writer.write_bool(b)
+    ///         });
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![48, 3, 1, 1, 255]);
+    /// ```
+    pub fn write_optional<T, F>(&mut self, value: Option<T>, callback: F)
+        where F: FnOnce(DERWriter, T) {
+        if let Some(value) = value {
+            callback(self.next(), value);
+        }
+    }
+
+    /// Writes `value` with `callback` unless it is equal to `default`.
+    ///
+    /// Used to write DEFAULT elements, which DER requires to be omitted
+    /// when they have the default value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yasna;
+    /// let der = yasna::construct_der(|writer| {
+    ///     writer.write_sequence(|writer| {
+    ///         writer.write_default(10, 10, |writer, i| writer.write_i64(i));
+    ///         writer.write_default(false, true, |writer, b| {
+    ///             writer.write_bool(b)
+    ///         });
+    ///     })
+    /// });
+    /// assert_eq!(der, vec![48, 3, 1, 1, 0]);
+    /// ```
+    pub fn write_default<T, F>(&mut self, value: T, default: T, callback: F)
+        where T: PartialEq, F: FnOnce(DERWriter, T) {
+        if value != default {
+            callback(self.next(), value);
+        }
     }
 }
 
@@ -1203,15 +3073,45 @@ impl<'a> DERWriterSeq<'a> {
 /// ```
 #[derive(Debug)]
 pub struct DERWriterSet<'a> {
-    bufs: &'a mut Vec<Vec<u8>>,
+    buf: &'a mut Vec<u8>,
+    starts: &'a mut Vec<usize>,
+    length_octets: usize,
 }
 
 impl<'a> DERWriterSet<'a> {
     /// Generates a new [`DERWriter`].
     pub fn next<'b>(&'b mut self) -> DERWriter<'b> {
-        self.bufs.push(Vec::new());
-        return DERWriter::from_buf(self.bufs.last_mut().unwrap());
+        self.starts.push(self.buf.len());
+        // The elements are sorted afterwards, so they are always
+        // buffered as a whole.
+        let mut writer = DERWriter::from_buf(self.buf, None);
+        writer.length_octets = self.length_octets;
+        return writer;
+    }
+}
+
+/// Splits the output of a [`DERWriterSet`] into its elements, given
+/// their start positions.
+fn set_elements<'b>(buf: &'b [u8], starts: &[usize])
+        -> Result<Vec<&'b [u8]>, EmptySetElement> {
+    return starts.iter().enumerate().map(|(i, &start)| {
+        let end = starts.get(i + 1).map_or(buf.len(), |&end| end);
+        if start == end {
+            return Err(EmptySetElement { index: i });
+        }
+        return Ok(&buf[start..end]);
+    }).collect();
+}
+
+/// Returns a sort key for the tag of an encoded element,
+/// ignoring the primitive/constructed bit.
+fn set_tag_key(buf: &[u8]) -> (u8, usize, &[u8]) {
+    if buf[0] & 31 != 31 {
+        return (buf[0] & 223, 0, &[]);
     }
+    let len = buf[1..].iter().position(|x| x & 128 == 0)
+        .map_or(buf.len() - 1, |i| i + 1);
+    return (buf[0] & 223, len, &buf[1..1+len]);
 }
 
 #[cfg(test)]
@@ -0,0 +1,136 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Two-pass construction of DER-encoded data.
+//!
+//! The first pass runs the callbacks only to measure the length of
+//! every constructed value. The second pass runs them again, writing
+//! each length before the contents, so that the output is produced
+//! front to back and can be handed to a sink piece by piece.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::sink::DERSink;
+
+/// The amount of buffered output above which it is flushed to the sink.
+pub(super) const FLUSH_THRESHOLD: usize = 8192;
+
+/// The length of a constructed value, as seen when it is opened.
+pub(super) enum Length {
+    /// Measuring pass: the length is to be recorded at this index.
+    Unknown(usize),
+    /// Emitting pass: the length recorded in the measuring pass.
+    Known(usize),
+}
+
+/// The state shared by all writers in a two-pass construction.
+pub(super) trait Plan: fmt::Debug {
+    /// Called when the contents of a constructed value start.
+    fn open(&mut self) -> Length;
+    /// Records the length of a value opened in the measuring pass.
+    fn close(&mut self, index: usize, length: usize);
+    /// Returns the number of bytes already taken out of the buffer.
+    fn flushed(&self) -> usize;
+    /// Takes all the bytes out of the buffer.
+    fn flush(&mut self, buf: &mut Vec<u8>);
+}
+
+/// Flushes `buf` if it is in a two-pass construction and has grown
+/// large enough.
+pub(super) fn flush_if_full(plan: &mut Option<&mut (dyn Plan + '_)>,
+        buf: &mut Vec<u8>) {
+    if let Some(plan) = plan {
+        if buf.len() >= FLUSH_THRESHOLD {
+            plan.flush(buf);
+        }
+    }
+}
+
+/// Reborrows the plan for a nested writer.
+pub(super) fn reborrow<'b>(plan: &'b mut Option<&mut (dyn Plan + '_)>)
+        -> Option<&'b mut (dyn Plan + 'b)> {
+    match plan {
+        Some(plan) => Some(&mut **plan),
+        None => None,
+    }
+}
+
+/// A [`Plan`] writing its second pass into `S`.
+pub(super) struct TwoPass<S> {
+    lengths: Vec<usize>,
+    next: usize,
+    measuring: bool,
+    flushed: usize,
+    pub(super) sink: S,
+}
+
+impl<S: DERSink> TwoPass<S> {
+    pub(super) fn new(sink: S) -> Self {
+        return TwoPass {
+            lengths: Vec::new(),
+            next: 0,
+            measuring: true,
+            flushed: 0,
+            sink,
+        };
+    }
+
+    /// Ends the current pass, whose remaining output is in `buf`.
+    /// Returns the number of bytes written in the pass.
+    pub(super) fn finish_pass(&mut self, buf: &mut Vec<u8>) -> usize {
+        self.flush(buf);
+        if self.measuring {
+            self.measuring = false;
+        } else {
+            assert!(self.next == self.lengths.len(),
+                "The callback wrote different values in the two passes");
+        }
+        let length = self.flushed;
+        self.flushed = 0;
+        return length;
+    }
+}
+
+impl<S> fmt::Debug for TwoPass<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TwoPass")
+            .field("lengths", &self.lengths)
+            .field("next", &self.next)
+            .field("measuring", &self.measuring)
+            .field("flushed", &self.flushed)
+            .finish()
+    }
+}
+
+impl<S: DERSink> Plan for TwoPass<S> {
+    fn open(&mut self) -> Length {
+        if self.measuring {
+            self.lengths.push(0);
+            return Length::Unknown(self.lengths.len() - 1);
+        }
+        let length = *self.lengths.get(self.next)
+            .expect("The callback wrote different values in the two passes");
+        self.next += 1;
+        return Length::Known(length);
+    }
+
+    fn close(&mut self, index: usize, length: usize) {
+        self.lengths[index] = length;
+    }
+
+    fn flushed(&self) -> usize {
+        self.flushed
+    }
+
+    fn flush(&mut self, buf: &mut Vec<u8>) {
+        if !self.measuring {
+            self.sink.feed(buf);
+        }
+        self.flushed += buf.len();
+        buf.clear();
+    }
+}
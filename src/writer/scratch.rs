@@ -0,0 +1,79 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Work buffers for intermediate encodings.
+//!
+//! The writers keep parts of the output in buffers that are dropped
+//! once the data has been copied out, or when the construction fails.
+//! With `zeroize` feature, these buffers are wiped on drop, including
+//! their unused capacity, so that secrets don't linger in freed memory.
+//! The writers grow their buffers with [`reserve`], which wipes the
+//! allocations left behind as well.
+
+use alloc::vec::Vec;
+#[cfg(feature = "zeroize")]
+use core::cmp;
+use core::mem;
+
+/// A buffer holding intermediate output, zeroized on drop when
+/// `zeroize` feature is enabled.
+pub(super) struct Scratch(pub(super) Vec<u8>);
+
+impl Scratch {
+    pub(super) fn new() -> Self {
+        return Scratch(Vec::new());
+    }
+
+    pub(super) fn with_capacity(capacity: usize) -> Self {
+        return Scratch(Vec::with_capacity(capacity));
+    }
+
+    /// Takes out the contents as the final output, which is left to
+    /// the caller to dispose of.
+    pub(super) fn take(&mut self) -> Vec<u8> {
+        return mem::replace(&mut self.0, Vec::new());
+    }
+}
+
+impl AsRef<[u8]> for Scratch {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+/// Makes room for `additional` more bytes in `buf`.
+///
+/// With `zeroize` feature, a buffer that has to grow is copied to a new
+/// allocation and the old one is wiped, instead of being reallocated in
+/// place where the allocator may leave a stale copy.
+#[cfg(feature = "zeroize")]
+pub(super) fn reserve(buf: &mut Vec<u8>, additional: usize) {
+    if buf.capacity() - buf.len() >= additional {
+        return;
+    }
+    let required = buf.len().checked_add(additional)
+        .expect("capacity overflow");
+    let capacity = cmp::max(cmp::max(required, buf.capacity().saturating_mul(2)), 8);
+    let mut grown = Vec::with_capacity(capacity);
+    grown.extend_from_slice(buf);
+    zeroize::Zeroize::zeroize(buf);
+    *buf = grown;
+}
+
+/// Makes room for `additional` more bytes in `buf`.
+///
+/// Without `zeroize` feature, the buffer is left to grow by itself.
+#[cfg(not(feature = "zeroize"))]
+#[inline]
+pub(super) fn reserve(_buf: &mut Vec<u8>, _additional: usize) {
+}
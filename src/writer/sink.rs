@@ -0,0 +1,201 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io;
+
+/// A destination of DER-encoded data produced piece by piece.
+///
+/// See [`construct_der_to_sink`](crate::construct_der_to_sink).
+///
+/// # Examples
+///
+/// A sink counting the bytes:
+///
+/// ```
+/// use yasna::DERSink;
+/// struct Counter(usize);
+/// impl DERSink for Counter {
+///     fn feed(&mut self, bytes: &[u8]) {
+///         self.0 += bytes.len();
+///     }
+/// }
+/// let mut counter = Counter(0);
+/// yasna::construct_der_to_sink(&mut counter, |writer| {
+///     writer.write_i64(10)
+/// });
+/// assert_eq!(counter.0, 3);
+/// ```
+pub trait DERSink {
+    /// Receives the next piece of the output.
+    fn feed(&mut self, bytes: &[u8]);
+}
+
+impl<S: DERSink + ?Sized> DERSink for &mut S {
+    fn feed(&mut self, bytes: &[u8]) {
+        (**self).feed(bytes);
+    }
+}
+
+impl DERSink for Vec<u8> {
+    fn feed(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+/// A sink feeding the output into two sinks.
+///
+/// More destinations can be added by nesting.
+///
+/// # Examples
+///
+/// ```
+/// use yasna::Tee;
+/// let mut first = Vec::new();
+/// let mut second = Vec::new();
+/// let mut third = Vec::new();
+/// let mut tee = Tee::new(&mut first, Tee::new(&mut second, &mut third));
+/// yasna::construct_der_to_sink(&mut tee, |writer| {
+///     writer.write_i64(10)
+/// });
+/// assert_eq!(first, vec![2, 1, 10]);
+/// assert_eq!(second, vec![2, 1, 10]);
+/// assert_eq!(third, vec![2, 1, 10]);
+/// ```
+#[derive(Debug)]
+pub struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Tee<A, B> {
+    /// Constructs a sink feeding `first` and `second`.
+    pub fn new(first: A, second: B) -> Self {
+        return Tee {
+            first,
+            second,
+        };
+    }
+
+    /// Unwraps the two sinks.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: DERSink, B: DERSink> DERSink for Tee<A, B> {
+    fn feed(&mut self, bytes: &[u8]) {
+        self.first.feed(bytes);
+        self.second.feed(bytes);
+    }
+}
+
+#[cfg(feature = "std")]
+/// A sink writing into an [`io::Write`](std::io::Write).
+///
+/// Once an error occurs, the rest of the output is dropped. The error
+/// is returned from [`finish`](IoSink::finish).
+///
+/// # Features
+///
+/// This struct is enabled by `std` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["std"] }
+/// ```
+#[derive(Debug)]
+pub struct IoSink<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> IoSink<W> {
+    /// Constructs a sink writing into `inner`.
+    pub fn new(inner: W) -> Self {
+        return IoSink {
+            inner,
+            error: None,
+        };
+    }
+
+    /// Flushes the underlying writer and unwraps it, or returns the
+    /// first error that occurred.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.inner.flush()?;
+        return Ok(self.inner);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> DERSink for IoSink<W> {
+    fn feed(&mut self, bytes: &[u8]) {
+        if self.error.is_none() {
+            if let Err(e) = self.inner.write_all(bytes) {
+                self.error = Some(e);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "digest")]
+/// A sink feeding a hash function.
+///
+/// # Features
+///
+/// This struct is enabled by `digest` feature.
+///
+/// ```toml
+/// [dependencies]
+/// yasna = { version = "*", features = ["digest"] }
+/// ```
+#[derive(Debug)]
+pub struct DigestSink<'d, D: ?Sized> {
+    digest: &'d mut D,
+}
+
+#[cfg(feature = "digest")]
+impl<'d, D: digest::Update + ?Sized> DigestSink<'d, D> {
+    /// Constructs a sink feeding `digest`.
+    pub fn new(digest: &'d mut D) -> Self {
+        return DigestSink {
+            digest,
+        };
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<'d, D: digest::Update + ?Sized> DERSink for DigestSink<'d, D> {
+    fn feed(&mut self, bytes: &[u8]) {
+        self.digest.update(bytes);
+    }
+}
+
+/// A sink for measuring only.
+pub(super) struct Discard;
+
+impl DERSink for Discard {
+    fn feed(&mut self, _bytes: &[u8]) {}
+}
+
+/// A sink writing into a slice, which must be large enough.
+pub(super) struct SliceSink<'s> {
+    pub(super) buf: &'s mut [u8],
+    pub(super) pos: usize,
+}
+
+impl<'s> DERSink for SliceSink<'s> {
+    fn feed(&mut self, bytes: &[u8]) {
+        let end = self.pos + bytes.len();
+        self.buf[self.pos..end].copy_from_slice(bytes);
+        self.pos = end;
+    }
+}
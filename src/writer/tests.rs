@@ -77,6 +77,124 @@ fn test_der_write_u64() {
             writer.write_u64(value)
         });
         assert_eq!(data, edata);
+        let data = construct_der(|writer| {
+            writer.write_enum_u64(value)
+        });
+        assert_eq!(data[0], 10);
+        assert_eq!(&data[1..], &edata[1..]);
+    }
+}
+
+#[test]
+fn test_der_write_i128() {
+    let tests : &[(i128, &[u8])] = &[
+        (-170141183460469231731687303715884105728,
+            &[2, 16, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (-18446744073709551616, &[2, 9, 255, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (-9223372036854775809,
+            &[2, 9, 255, 127, 255, 255, 255, 255, 255, 255, 255]),
+        (-129, &[2, 2, 255, 127]),
+        (-128, &[2, 1, 128]),
+        (-1, &[2, 1, 255]),
+        (0, &[2, 1, 0]),
+        (1, &[2, 1, 1]),
+        (127, &[2, 1, 127]),
+        (128, &[2, 2, 0, 128]),
+        (9223372036854775808, &[2, 9, 0, 128, 0, 0, 0, 0, 0, 0, 0]),
+        (18446744073709551616, &[2, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (170141183460469231731687303715884105727,
+            &[2, 16, 127, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_i128(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+fn test_der_write_u128() {
+    let tests : &[(u128, &[u8])] = &[
+        (0, &[2, 1, 0]),
+        (1, &[2, 1, 1]),
+        (127, &[2, 1, 127]),
+        (128, &[2, 2, 0, 128]),
+        (18446744073709551615,
+            &[2, 9, 0, 255, 255, 255, 255, 255, 255, 255, 255]),
+        (18446744073709551616, &[2, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (170141183460469231731687303715884105727,
+            &[2, 16, 127, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255]),
+        (170141183460469231731687303715884105728,
+            &[2, 17, 0, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (340282366920938463463374607431768211455,
+            &[2, 17, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_u128(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+fn test_der_write_int_bytes() {
+    let tests : &[(&[u8], &[u8])] = &[
+        (&[0], &[2, 1, 0]),
+        (&[0, 0, 0], &[2, 1, 0]),
+        (&[127], &[2, 1, 127]),
+        (&[0, 128], &[2, 2, 0, 128]),
+        (&[0, 0, 128], &[2, 2, 0, 128]),
+        (&[255], &[2, 1, 255]),
+        (&[255, 255], &[2, 1, 255]),
+        (&[255, 127], &[2, 2, 255, 127]),
+        (&[255, 255, 128, 0], &[2, 2, 128, 0]),
+        (&[1, 0, 0, 0, 0, 0, 0, 0, 0], &[2, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_int_bytes(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_der_write_int_bytes_empty() {
+    construct_der(|writer| {
+        writer.write_int_bytes(&[])
+    });
+}
+
+#[test]
+fn test_der_write_int_from_be_bytes() {
+    let tests : &[(bool, &[u8], &[u8])] = &[
+        (false, &[], &[2, 1, 0]),
+        (true, &[], &[2, 1, 0]),
+        (true, &[0, 0], &[2, 1, 0]),
+        (false, &[127], &[2, 1, 127]),
+        (false, &[0, 128], &[2, 2, 0, 128]),
+        (true, &[1], &[2, 1, 255]),
+        (true, &[128], &[2, 1, 128]),
+        (true, &[129], &[2, 2, 255, 127]),
+        (true, &[0, 1, 0], &[2, 2, 255, 0]),
+        (true, &[128, 0], &[2, 2, 128, 0]),
+        (true, &[128, 1], &[2, 3, 255, 127, 255]),
+        (false, &[1, 0, 0, 0, 0, 0, 0, 0, 0],
+            &[2, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        (true, &[1, 0, 0, 0, 0, 0, 0, 0, 0],
+            &[2, 9, 255, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ];
+    for &(negative, magnitude, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_int_from_be_bytes(negative, magnitude)
+        });
+        assert_eq!(data, edata);
     }
 }
 
@@ -205,6 +323,68 @@ fn test_der_write_u8() {
     }
 }
 
+#[test]
+fn test_der_write_real() {
+    let tests : &[(f64, &[u8])] = &[
+        (0.0, &[9, 0]),
+        (1.0, &[9, 3, 128, 0, 1]),
+        (-1.0, &[9, 3, 192, 0, 1]),
+        (1.5, &[9, 3, 128, 255, 3]),
+        (-0.375, &[9, 3, 192, 253, 3]),
+        (6.0, &[9, 3, 128, 1, 3]),
+        (1.0e300, &[9, 10, 129, 3, 178, 5, 249, 15, 34, 0, 29, 103]),
+        (f64::from_bits(1), &[9, 4, 129, 251, 206, 1]),
+        (-0.0, &[9, 1, 67]),
+        (core::f64::INFINITY, &[9, 1, 64]),
+        (core::f64::NEG_INFINITY, &[9, 1, 65]),
+        (core::f64::NAN, &[9, 1, 66]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_real(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+fn test_der_real_roundtrip() {
+    use super::super::{decode_der, encode_der};
+    let tests : &[f64] = &[
+        0.0, -0.0, 1.0, -0.375, 1.0e300, f64::from_bits(1),
+        core::f64::INFINITY, core::f64::NEG_INFINITY, core::f64::NAN,
+    ];
+    for &value in tests {
+        let data = encode_der(&value);
+        assert_eq!(data, construct_der(|writer| writer.write_real(value)));
+        let decoded : f64 = decode_der(&data).unwrap();
+        assert_eq!(decoded.to_bits(), value.to_bits());
+    }
+}
+
+#[test]
+fn test_der_write_real_decimal() {
+    let tests : &[(f64, &[u8])] = &[
+        (0.0, &[9, 0]),
+        (1.5, b"\x09\x07\x0315.E-1"),
+        (-3.0, b"\x09\x07\x03-3.E+0"),
+        (100.0, b"\x09\x05\x031.E2"),
+        (-0.375, b"\x09\x09\x03-375.E-3"),
+        (1.0e300, b"\x09\x07\x031.E300"),
+        (f64::from_bits(1), b"\x09\x08\x035.E-324"),
+        (-0.0, &[9, 1, 67]),
+        (core::f64::INFINITY, &[9, 1, 64]),
+        (core::f64::NEG_INFINITY, &[9, 1, 65]),
+        (core::f64::NAN, &[9, 1, 66]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_real_decimal(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
 #[cfg(feature = "num-bigint")]
 #[test]
 fn test_der_write_bigint() {
@@ -306,6 +486,192 @@ fn test_der_write_bytes() {
     }
 }
 
+#[test]
+fn test_der_write_bitstring_bytes() {
+    let tests : &[(&[u8], &[u8])] = &[
+        (&[], &[3, 1, 0]),
+        (&[255], &[3, 2, 0, 255]),
+        (&[1, 0, 100, 255], &[3, 5, 0, 1, 0, 100, 255]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_bitstring_bytes(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+fn test_der_write_named_bits() {
+    let tests : &[(&[bool], &[u8])] = &[
+        (&[], &[3, 1, 0]),
+        (&[false, false, false], &[3, 1, 0]),
+        (&[true], &[3, 2, 7, 128]),
+        (&[true, false, true, false, false], &[3, 2, 5, 160]),
+        (&[false, false, false, false, false, false, false, false, true],
+            &[3, 3, 7, 0, 128]),
+        (&[true, true, true, true, true, true, true, true, false],
+            &[3, 2, 0, 255]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_named_bits(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+fn test_der_write_bmp_string() {
+    let tests : &[(&str, &[u8])] = &[
+        ("", &[30, 0]),
+        ("A", &[30, 2, 0, 65]),
+        ("£π\u{FFFD}", &[30, 6, 0, 163, 3, 192, 255, 253]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_bmp_string(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_der_write_bmp_string_non_bmp() {
+    construct_der(|writer| {
+        writer.write_bmp_string("\u{1F600}")
+    });
+}
+
+#[test]
+fn test_der_write_universal_string() {
+    let tests : &[(&str, &[u8])] = &[
+        ("", &[28, 0]),
+        ("A", &[28, 4, 0, 0, 0, 65]),
+        ("π\u{10FFFF}", &[28, 8, 0, 0, 3, 192, 0, 16, 255, 255]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_universal_string(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+fn test_der_write_teletex_string_latin1() {
+    let tests : &[(&str, &[u8])] = &[
+        ("", &[20, 0]),
+        ("A", &[20, 1, 65]),
+        ("\u{A0}é\u{FF}", &[20, 3, 160, 233, 255]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_teletex_string_latin1(value)
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_der_write_teletex_string_latin1_non_latin1() {
+    construct_der(|writer| {
+        writer.write_teletex_string_latin1("π")
+    });
+}
+
+#[test]
+fn test_der_write_relative_oid() {
+    use super::super::models::RelativeOid;
+    let tests : &[(&[u64], &[u8])] = &[
+        (&[0], &[13, 1, 0]),
+        (&[8571, 3, 2], &[13, 4, 194, 123, 3, 2]),
+        (&[16384], &[13, 3, 129, 128, 0]),
+        (&[18446744073709551615],
+            &[13, 10, 129, 255, 255, 255, 255, 255, 255, 255, 255, 127]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_relative_oid(&RelativeOid::from_slice(value))
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn test_der_write_big_oid() {
+    use alloc::string::ToString;
+    use super::super::models::BigObjectIdentifier;
+    let tests : &[(&str, &[u8])] = &[
+        ("0.0", &[6, 1, 0]),
+        ("1.2.840.113549.1.1", &[6, 8, 42, 134, 72, 134, 247, 13, 1, 1]),
+        ("1.2.16384", &[6, 4, 42, 129, 128, 0]),
+        ("2.999.3", &[6, 3, 136, 55, 3]),
+        ("2.18446744073709551535",
+            &[6, 10, 129, 255, 255, 255, 255, 255, 255, 255, 255, 127]),
+        ("2.25.329800735698586629295641978511506172918",
+            &[6, 20, 105, 131, 240, 157, 167, 235, 207, 222, 224, 199, 161,
+            167, 178, 192, 148, 140, 200, 249, 215, 118]),
+    ];
+    for &(value, edata) in tests {
+        let oid = value.parse::<BigObjectIdentifier>().unwrap();
+        let data = construct_der(|writer| writer.write_big_oid(&oid));
+        assert_eq!(data, edata, "{}", value);
+        if let Some(oid) = oid.to_oid() {
+            assert_eq!(construct_der(|writer| writer.write_oid(&oid)), edata);
+            let oid = BigObjectIdentifier::from(oid);
+            assert_eq!(oid.to_string(), value);
+        }
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+#[should_panic]
+fn test_der_write_big_oid_invalid() {
+    use super::super::models::BigObjectIdentifier;
+    use num_bigint::BigUint;
+    let oid = BigObjectIdentifier::new(vec![BigUint::from(1u8),
+        BigUint::from(40u8)]);
+    construct_der(|writer| writer.write_big_oid(&oid));
+}
+
+#[test]
+fn test_der_write_embedded_pdv() {
+    use super::super::models::{EmbeddedPdv,ObjectIdentifier,PdvIdentification};
+    let tests : Vec<(PdvIdentification, &[u8])> = vec![
+        (PdvIdentification::Syntaxes {
+            abstract_syntax: ObjectIdentifier::from_slice(&[2, 1, 1]),
+            transfer_syntax: ObjectIdentifier::from_slice(&[1, 0, 8571]),
+        }, &[43, 16, 160, 11, 160, 9, 128, 2, 81, 1, 129, 3, 40, 194, 123,
+            130, 1, 65]),
+        (PdvIdentification::Syntax(ObjectIdentifier::from_slice(&[2, 1, 1])),
+            &[43, 9, 160, 4, 129, 2, 81, 1, 130, 1, 65]),
+        (PdvIdentification::PresentationContextId(3),
+            &[43, 8, 160, 3, 130, 1, 3, 130, 1, 65]),
+        (PdvIdentification::ContextNegotiation {
+            presentation_context_id: 3,
+            transfer_syntax: ObjectIdentifier::from_slice(&[2, 1, 1]),
+        }, &[43, 14, 160, 9, 163, 7, 128, 1, 3, 129, 2, 81, 1, 130, 1, 65]),
+        (PdvIdentification::TransferSyntax(
+            ObjectIdentifier::from_slice(&[2, 1, 1])),
+            &[43, 9, 160, 4, 132, 2, 81, 1, 130, 1, 65]),
+        (PdvIdentification::Fixed, &[43, 7, 160, 2, 133, 0, 130, 1, 65]),
+    ];
+    for (identification, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_embedded_pdv(&EmbeddedPdv {
+                identification,
+                data_value: b"A".to_vec(),
+            })
+        });
+        assert_eq!(data, edata);
+    }
+}
+
 #[test]
 fn test_der_write_null() {
     let data = construct_der(|writer| {
@@ -374,6 +740,60 @@ fn test_der_write_sequence_large() {
     assert_eq!(data.len(), 20000012);
 }
 
+#[test]
+fn test_der_write_sequence_of_iter() {
+    let data = construct_der(|writer| {
+        writer.write_sequence_of_iter(Vec::<i64>::new(), |writer, i| {
+            writer.write_i64(i)
+        })
+    });
+    assert_eq!(data, vec![48, 0]);
+    let data = construct_der(|writer| {
+        writer.write_sequence_of_iter(["A", "BC"].iter(), |writer, s| {
+            writer.write_utf8string(s)
+        })
+    });
+    assert_eq!(data, vec![48, 7, 12, 1, 65, 12, 2, 66, 67]);
+}
+
+#[test]
+fn test_der_write_optional() {
+    let tests : &[(Option<i64>, &[u8])] = &[
+        (None, &[48, 3, 1, 1, 255]),
+        (Some(0), &[48, 6, 2, 1, 0, 1, 1, 255]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.write_optional(value, |writer, value| {
+                    writer.write_i64(value)
+                });
+                writer.next().write_bool(true);
+            })
+        });
+        assert_eq!(data, edata);
+    }
+}
+
+#[test]
+fn test_der_write_default() {
+    let tests : &[(i64, &[u8])] = &[
+        (0, &[48, 3, 1, 1, 255]),
+        (2, &[48, 6, 2, 1, 2, 1, 1, 255]),
+    ];
+    for &(value, edata) in tests {
+        let data = construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.write_default(value, 0, |writer, value| {
+                    writer.write_i64(value)
+                });
+                writer.next().write_bool(true);
+            })
+        });
+        assert_eq!(data, edata);
+    }
+}
+
 #[test]
 fn test_der_write_set() {
     let data = construct_der(|writer| {
@@ -397,6 +817,81 @@ fn test_der_write_set() {
         77, 5, 4, 3, 66, 97, 114, 191, 149, 140, 78, 5, 4, 3, 70, 111, 111]);
 }
 
+#[test]
+fn test_der_write_set_equal_tags() {
+    let expected = vec![49, 14, 2, 1, 1, 2, 1, 2, 4, 0, 129, 0, 161, 2, 5, 0];
+    let orders : &[[usize; 5]] = &[
+        [0, 1, 2, 3, 4], [4, 3, 2, 1, 0], [2, 4, 1, 3, 0],
+    ];
+    for order in orders {
+        let data = construct_der(|writer| {
+            writer.write_set(|writer| {
+                for &i in order {
+                    let writer = writer.next();
+                    match i {
+                        0 => writer.write_i64(2),
+                        1 => writer.write_bytes(&[]),
+                        2 => writer.write_i64(1),
+                        3 => writer.write_tagged_implicit(Tag::context(1),
+                            |writer| writer.write_null()),
+                        _ => writer.write_tagged(Tag::context(1),
+                            |writer| writer.write_null()),
+                    }
+                }
+            })
+        });
+        assert_eq!(data, expected);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_der_write_set_empty_element() {
+    construct_der(|writer| {
+        writer.write_set(|writer| {
+            writer.next();
+            writer.next().write_null();
+        })
+    });
+}
+
+#[test]
+#[should_panic]
+fn test_der_write_set_of_empty_element() {
+    construct_der(|writer| {
+        writer.write_set_of(|writer| {
+            writer.next().write_null();
+            writer.next();
+        })
+    });
+}
+
+#[test]
+fn test_der_try_write_set() {
+    let data = construct_der(|writer| {
+        writer.write_sequence(|writer| {
+            let err = writer.next().try_write_set(|writer| {
+                writer.next().write_null();
+                writer.next();
+            }).unwrap_err();
+            assert_eq!(err.index(), 1);
+            let err = writer.next().try_write_set_of(|writer| {
+                writer.next();
+                writer.next().write_null();
+            }).unwrap_err();
+            assert_eq!(err.index(), 0);
+            let result = writer.next().try_write_set(|writer| {
+                writer.next().write_i64(10);
+                writer.next().write_null();
+                10
+            }).unwrap();
+            assert_eq!(result, 10);
+            writer.next().try_write_set_of(|_| {}).unwrap();
+        })
+    });
+    assert_eq!(data, vec![48, 9, 49, 5, 2, 1, 10, 5, 0, 49, 0]);
+}
+
 #[test]
 fn test_der_write_set_of() {
     let tests : &[(&[i64], &[u8])] = &[
@@ -420,6 +915,70 @@ fn test_der_write_set_of() {
     }
 }
 
+#[test]
+fn test_der_write_set_of_nested() {
+    let data = construct_der(|writer| {
+        writer.write_set_of(|writer| {
+            for i in (0..200).rev() {
+                writer.next().write_set_of(|writer| {
+                    writer.next().write_i64(i);
+                    writer.next().write_i64(-i);
+                });
+            }
+        })
+    });
+    let mut elements = (0..200).map(|i| {
+        construct_der(|writer| {
+            writer.write_set_of(|writer| {
+                writer.next().write_i64(-i);
+                writer.next().write_i64(i);
+            })
+        })
+    }).collect::<Vec<_>>();
+    elements.sort();
+    let mut contents = Vec::new();
+    for element in &elements {
+        contents.extend_from_slice(element);
+    }
+    assert_eq!(&data[..4], &[49, 130, (contents.len() >> 8) as u8,
+        contents.len() as u8]);
+    assert_eq!(&data[4..], &contents[..]);
+}
+
+#[test]
+fn test_der_write_length_octets_hint() {
+    fn write(writer: DERWriter, size: usize) {
+        writer.write_sequence(|writer| {
+            writer.next().write_bytes(&vec![0; size]);
+            writer.next().write_tagged(Tag::context(0), |writer| {
+                writer.write_sequence(|writer| {
+                    writer.next().write_bytes(&vec![1; size / 2]);
+                })
+            });
+            writer.next().write_set(|writer| {
+                writer.next().write_sequence(|writer| {
+                    writer.next().write_null();
+                });
+            });
+        })
+    }
+    for &size in &[0, 100, 127, 128, 255, 256, 65535, 65536, 16777216] {
+        let expected = construct_der(|writer| write(writer, size));
+        for count in 1..10 {
+            let data = construct_der(|writer| {
+                write(writer.length_octets_hint(count), size)
+            });
+            assert_eq!(data, expected);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_der_write_length_octets_hint_zero() {
+    construct_der(|writer| writer.length_octets_hint(0).write_null());
+}
+
 #[test]
 fn test_der_write_tagged() {
     let data = construct_der(|writer| {
@@ -439,3 +998,407 @@ fn test_der_write_tagged_implicit() {
     });
     assert_eq!(data, vec![131, 1, 10]);
 }
+
+#[test]
+fn test_der_write_tag_class_shorthands() {
+    let data = construct_der(|writer| {
+        writer.write_sequence(|writer| {
+            writer.next().write_context(0, |writer| writer.write_null());
+            writer.next().write_context_implicit(1, |writer| {
+                writer.write_null()
+            });
+            writer.next().write_application(2, |writer| writer.write_null());
+            writer.next().write_application_implicit(3, |writer| {
+                writer.write_null()
+            });
+            writer.next().write_private(4, |writer| writer.write_null());
+            writer.next().write_private_implicit(5, |writer| {
+                writer.write_null()
+            });
+        })
+    });
+    assert_eq!(data, vec![48, 18, 160, 2, 5, 0, 129, 0, 98, 2, 5, 0, 67, 0,
+        228, 2, 5, 0, 197, 0]);
+}
+
+#[test]
+fn test_der_write_indefinite() {
+    use super::super::tags::TAG_SEQUENCE;
+    let data = construct_der(|writer| {
+        writer.write_indefinite(TAG_SEQUENCE, |writer| {
+            writer.next().write_indefinite(Tag::context(0), |writer| {
+                writer.next().write_bytes(b"Hi");
+            });
+            writer.next().write_sequence(|_| {});
+        })
+    });
+    assert_eq!(data, vec![48, 128, 160, 128, 4, 2, 72, 105, 0, 0, 48, 0,
+        0, 0]);
+    let data = construct_der(|writer| {
+        writer.write_tagged_implicit(Tag::application(1), |writer| {
+            writer.write_indefinite(TAG_SEQUENCE, |_| {})
+        })
+    });
+    assert_eq!(data, vec![97, 128, 0, 0]);
+}
+
+#[test]
+fn test_der_write_with_capacity() {
+    let data = construct_der_with_capacity(1000, |writer| {
+        writer.write_bytes(&[1; 500])
+    });
+    assert_eq!(data, construct_der(|writer| writer.write_bytes(&[1; 500])));
+    assert!(data.capacity() >= 1000);
+    let data = construct_der_with_capacity(0, |writer| writer.write_null());
+    assert_eq!(data, vec![5, 0]);
+}
+
+#[test]
+fn test_der_write_two_pass() {
+    fn write_nested(writer: DERWriter, depth: usize) {
+        writer.write_sequence(|writer| {
+            writer.next().write_bytes(&vec![depth as u8; depth * 50]);
+            if depth > 0 {
+                write_nested(writer.next(), depth - 1);
+            }
+            writer.next().write_set_of(|writer| {
+                writer.next().write_i64(depth as i64);
+                writer.next().write_sequence(|_| {});
+            });
+        })
+    }
+    for &depth in &[0, 1, 2, 10, 100, 1000] {
+        let data = construct_der_two_pass(|writer| {
+            write_nested(writer, depth)
+        });
+        assert_eq!(data, construct_der(|writer| write_nested(writer, depth)));
+        assert_eq!(data.len(), data.capacity());
+    }
+    let data = construct_der_two_pass(|writer| {
+        writer.write_tagged_implicit(Tag::context(0), |writer| {
+            writer.write_tagged(Tag::context(1), |writer| writer.write_null())
+        })
+    });
+    assert_eq!(data, vec![160, 2, 5, 0]);
+}
+
+#[test]
+fn test_der_write_reserve() {
+    let mut buf = vec![1, 2, 3];
+    buf.shrink_to_fit();
+    scratch::reserve(&mut buf, 10);
+    assert_eq!(buf, vec![1, 2, 3]);
+    if cfg!(feature = "zeroize") {
+        assert!(buf.capacity() >= 13);
+    }
+    let data = construct_der(|writer| {
+        writer.write_sequence_of(|writer| {
+            for i in 0..1000 {
+                writer.next().write_bytes(&vec![i as u8; i % 300]);
+            }
+        })
+    });
+    assert_eq!(data, construct_der_two_pass(|writer| {
+        writer.write_sequence_of(|writer| {
+            for i in 0..1000 {
+                writer.next().write_bytes(&vec![i as u8; i % 300]);
+            }
+        })
+    }));
+}
+
+#[test]
+#[should_panic]
+fn test_der_write_two_pass_nondeterministic() {
+    let mut count = 0;
+    construct_der_two_pass(|writer| {
+        count += 1;
+        writer.write_sequence(|writer| {
+            if count == 1 {
+                writer.next().write_sequence(|_| {});
+            }
+        })
+    });
+}
+
+#[test]
+fn test_der_encoded_len() {
+    fn write(writer: DERWriter, size: usize) {
+        writer.write_sequence(|writer| {
+            writer.next().write_bytes(&vec![0; size]);
+            writer.next().write_tagged(Tag::context(0), |writer| {
+                writer.write_sequence(|writer| {
+                    writer.next().write_i64(size as i64);
+                })
+            });
+            writer.next().write_set(|writer| {
+                writer.next().write_null();
+                writer.next().write_bool(false);
+            });
+        })
+    }
+    for &size in &[0, 100, 127, 128, 255, 256, 65535, 65536, 20000000] {
+        assert_eq!(encoded_len(|writer| write(writer, size)),
+            construct_der(|writer| write(writer, size)).len());
+    }
+    assert_eq!(encoded_len(|_| {}), 0);
+}
+
+#[test]
+fn test_der_write_in_slice() {
+    fn write(writer: DERWriter) {
+        writer.write_sequence(|writer| {
+            writer.next().write_bytes(&[7; 20000]);
+            writer.next().write_sequence_of(|writer| {
+                for i in 0..100 {
+                    writer.next().write_i64(i);
+                }
+            });
+        })
+    }
+    let expected = construct_der(write);
+    let mut buf = vec![0; expected.len() + 10];
+    assert_eq!(construct_der_in(&mut buf, write), Ok(expected.len()));
+    assert_eq!(&buf[..expected.len()], &expected[..]);
+    assert!(buf[expected.len()..].iter().all(|&b| b == 0));
+
+    let mut buf = vec![0; expected.len()];
+    assert_eq!(construct_der_in(&mut buf, write), Ok(expected.len()));
+    assert_eq!(buf, expected);
+
+    let mut buf = vec![0; expected.len() - 1];
+    let err = construct_der_in(&mut buf, write).unwrap_err();
+    assert_eq!(err.required(), expected.len());
+    assert!(buf.iter().all(|&b| b == 0));
+
+    assert_eq!(construct_der_in(&mut [], |writer| writer.write_null()),
+        Err(BufferTooSmall { required: 2 }));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_der_write_to_writer() {
+    use super::super::tags::TAG_SEQUENCE;
+    fn write(writer: DERWriter) {
+        writer.write_sequence(|writer| {
+            writer.next().write_i64(10);
+            writer.next().write_bytes(&[1; 300]);
+            writer.next().write_set(|writer| {
+                writer.next().write_bool(true);
+                writer.next().write_sequence(|writer| {
+                    writer.next().write_null();
+                });
+            });
+            writer.next().write_tagged(Tag::context(0), |writer| {
+                writer.write_sequence_of(|writer| {
+                    for i in 0..5000 {
+                        writer.next().write_sequence(|writer| {
+                            writer.next().write_i64(i);
+                        });
+                    }
+                })
+            });
+            writer.next().write_tagged_implicit(Tag::context(1), |writer| {
+                writer.write_sequence(|_| {})
+            });
+            writer.next().write_indefinite(TAG_SEQUENCE, |writer| {
+                writer.next().write_sequence(|writer| {
+                    writer.next().write_u8(1);
+                });
+            });
+        })
+    }
+    #[derive(Default)]
+    struct Chunks(Vec<Vec<u8>>);
+    impl std::io::Write for Chunks {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.push(buf.to_vec());
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut chunks = Chunks::default();
+    construct_der_to_writer(&mut chunks, write).unwrap();
+    assert!(chunks.0.len() > 1);
+    assert!(chunks.0.iter().all(|chunk| {
+        chunk.len() < 2 * super::plan::FLUSH_THRESHOLD
+    }));
+    let joined = chunks.0.iter().flat_map(|chunk| chunk.iter().cloned())
+        .collect::<Vec<u8>>();
+    assert_eq!(joined, construct_der(write));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_der_write_to_writer_error() {
+    let mut buf = [0; 4];
+    let e = construct_der_to_writer(&mut buf[..], |writer| {
+        writer.write_bytes(b"Hello!")
+    }).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::WriteZero);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn test_der_write_to_writer_nondeterministic() {
+    let mut count = 0;
+    let _ = construct_der_to_writer(std::io::sink(), |writer| {
+        count += 1;
+        writer.write_sequence(|writer| {
+            for _ in 0..count {
+                writer.next().write_null();
+            }
+        })
+    });
+}
+
+/// A `digest::Update` recording the data and the number of updates.
+#[cfg(feature = "digest")]
+struct RecordingDigest {
+    data: Vec<u8>,
+    updates: usize,
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for RecordingDigest {
+    fn update(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+        self.updates += 1;
+    }
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_der_write_to_digest() {
+    fn write(writer: DERWriter) {
+        writer.write_sequence(|writer| {
+            for i in 0..3000 {
+                writer.next().write_sequence(|writer| {
+                    writer.next().write_i64(i);
+                    writer.next().write_utf8_string("yasna");
+                });
+            }
+        })
+    }
+    let mut hasher = RecordingDigest { data: Vec::new(), updates: 0 };
+    construct_der_to_digest(&mut hasher, write);
+    assert_eq!(hasher.data, construct_der(write));
+    assert!(hasher.updates > 1);
+}
+
+#[test]
+fn test_der_write_to_sink() {
+    struct Counter(usize, usize);
+    impl DERSink for Counter {
+        fn feed(&mut self, bytes: &[u8]) {
+            self.0 += 1;
+            self.1 += bytes.len();
+        }
+    }
+    fn write(writer: DERWriter) {
+        writer.write_sequence_of(|writer| {
+            for i in 0..5000 {
+                writer.next().write_set(|writer| {
+                    writer.next().write_i64(i);
+                    writer.next().write_null();
+                });
+            }
+        })
+    }
+    let expected = construct_der(write);
+    let mut data = Vec::new();
+    let mut counter = Counter(0, 0);
+    let mut copies = (Vec::new(), Vec::new());
+    let mut tee = Tee::new(&mut data, Tee::new(&mut counter,
+        Tee::new(&mut copies.0, &mut copies.1)));
+    construct_der_to_sink(&mut tee, write);
+    assert_eq!(data, expected);
+    assert!(counter.0 > 1);
+    assert_eq!(counter.1, expected.len());
+    assert_eq!(copies.0, expected);
+    assert_eq!(copies.1, expected);
+}
+
+#[cfg(all(feature = "std", feature = "digest"))]
+#[test]
+fn test_der_write_to_io_and_digest() {
+    let mut hasher = RecordingDigest { data: Vec::new(), updates: 0 };
+    let mut sink = Tee::new(IoSink::new(Vec::new()),
+        DigestSink::new(&mut hasher));
+    construct_der_to_sink(&mut sink, |writer| {
+        writer.write_bytes(&[1; 100000])
+    });
+    let (io_sink, _) = sink.into_inner();
+    let data = io_sink.finish().unwrap();
+    assert_eq!(data, construct_der(|writer| writer.write_bytes(&[1; 100000])));
+    assert_eq!(hasher.data, data);
+
+    let mut buf = [0; 4];
+    let mut sink = IoSink::new(&mut buf[..]);
+    construct_der_to_sink(&mut sink, |writer| writer.write_bytes(b"Hello!"));
+    sink.finish().unwrap_err();
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_der_write_sequence_of_par() {
+    let items = (-1000i64..1000).map(|i| i * 977).collect::<Vec<_>>();
+    let expected = construct_der(|writer| {
+        writer.write_sequence_of_iter(items.iter(), |writer, &i| {
+            writer.write_i64(i)
+        })
+    });
+    let data = construct_der(|writer| {
+        writer.write_sequence_of_par(&items, |writer, &i| writer.write_i64(i))
+    });
+    assert_eq!(data, expected);
+    let data = construct_der_two_pass(|writer| {
+        writer.write_sequence_of_par(&items, |writer, &i| writer.write_i64(i))
+    });
+    assert_eq!(data, expected);
+    let data = construct_der(|writer| {
+        writer.write_sequence_of_par(&[] as &[i64], |writer, &i| {
+            writer.write_i64(i)
+        })
+    });
+    assert_eq!(data, vec![48, 0]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_der_write_set_of_par() {
+    let items = (-1000i64..1000).map(|i| i * 977).collect::<Vec<_>>();
+    let expected = construct_der(|writer| {
+        writer.write_set_of(|writer| {
+            for &i in &items {
+                writer.next().write_i64(i);
+            }
+        })
+    });
+    let data = construct_der(|writer| {
+        writer.write_set_of_par(&items, |writer, &i| writer.write_i64(i))
+    });
+    assert_eq!(data, expected);
+    let data = construct_der(|writer| {
+        writer.write_sequence(|writer| {
+            writer.next().write_set_of_par(&items, |writer, &i| {
+                writer.write_sequence(|writer| writer.next().write_i64(i))
+            })
+        })
+    });
+    let expected = construct_der(|writer| {
+        writer.write_sequence(|writer| {
+            writer.next().write_set_of(|writer| {
+                for &i in &items {
+                    writer.next().write_sequence(|writer| {
+                        writer.next().write_i64(i)
+                    });
+                }
+            })
+        })
+    });
+    assert_eq!(data, expected);
+}